@@ -29,4 +29,10 @@ fn main() {
     let _client = R3Client::builder()
         .credentials(default_profile)
         .build();
+
+    // If you only need the default options, you can load the credentials and build the client in one step.
+    let _client = R3Client::from_profile("default").unwrap();
+
+    // The same works with the `R3_ACCESS_KEY_ID` and `R3_SECRET_ACCESS_KEY` environment variables.
+    let _client = R3Client::from_env().unwrap();
 }
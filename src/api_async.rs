@@ -1,66 +1,483 @@
-//! Enabled by the `async` feature. Contains blocking implementations of the pre-written queries.
+//! Enabled by the `async` feature. Contains async implementations of the pre-written queries.
 //!
 //! On the docs page of this module, you can only see the builder structs for the functions.
 //!
 //! Please see [`R3Client`] for the actual functions you can call.
+//!
+//! # Cancellation
+//! All functions in this module are plain `async fn`s, so they can be cancelled by dropping the returned future,
+//! for example when it loses a `tokio::select!` or a `tokio::time::timeout` expires.
+//! Dropping the future also aborts the HTTP request that is currently in flight.
+//! Long running polling loops like [`R3Client::wait_for_job_with_cancel_async()`] additionally accept a [`CancellationToken`].
 
-use crate::auth::{build_auth_header, get_date};
-use crate::operations::{cancel_job, delete_file, delete_file_version, get_application_types, get_devices, get_files, get_jobs, get_owned_organization, start_job, CancelJob, DeleteFile, DeleteFileVersion, DeviceState, GetApplicationTypes, GetDevices, GetFiles, GetJobs, GetOwnedOrganization, StartJob};
-use crate::{R3Client, BASE_URL, GRAPHQL_PATH};
+use crate::auth::{build_auth_header, create_digest};
+use crate::operations::{
+    add_device_tag, add_service_to_network, cancel_job, create_connection, create_network,
+    create_service, delete_file, delete_file_version, delete_network, get_application_types,
+    get_audit_log, get_connections, get_device_count, get_device_events, get_device_reachability,
+    get_device_shares, get_device_tags, get_devices, get_file_versions, get_files, get_job,
+    get_job_results, get_job_summary, get_jobs, get_networks, get_organization_members,
+    get_owned_organization, get_plan, get_self, invite_organization_member, remove_connection,
+    remove_device_tag, remove_organization_member, remove_service, remove_service_from_network,
+    set_service_enabled, share_device, start_job, transfer_device, unique_device_id,
+    unshare_device, AccountPlan, ActiveConnection, AddDeviceTag, AddServiceToNetwork,
+    AuditLogEntry, CancelJob, Connection, ConnectionOptions, CreateConnection, CreateNetwork,
+    CreateService, DeleteFile, DeleteFileVersion, DeleteNetwork, Device, DeviceEvent,
+    DeviceReachability, DeviceShare, DeviceState, FileVersion, GetApplicationTypes, GetAuditLog,
+    GetConnections, GetDeviceCount, GetDeviceEvents, GetDeviceReachability, GetDeviceShares,
+    GetDeviceTags, GetDevices, GetFileVersions, GetFiles, GetJob, GetJobResults, GetJobSummary,
+    GetJobs, GetNetworks, GetOrganizationMembers, GetOwnedOrganization, GetPlan, GetSelf,
+    InviteOrganizationMember, Job, JobDeviceResult, Network, NetworkId, OrgDevice,
+    OrganizationRole, RemoveConnection, RemoveDeviceTag, RemoveOrganizationMember, RemoveService,
+    RemoveServiceFromNetwork, ServiceId, ServiceState, SetServiceEnabled, ShareDevice,
+    SharePermission, StartJob, TransferDevice, TransferTarget, UnshareDevice,
+};
+use crate::paginator::jobs_page;
+use crate::retry::{is_retryable_error, is_retryable_status};
+use crate::{
+    batch_query, generate_idempotency_key, into_send_error, paginate, parse_graphql_response,
+    response_data, typed_response, DeleteFileResult, DisconnectResult, PageCursor, PagedResult,
+    R3Client, R3Error, ResponseMeta, StartJobResult, IDEMPOTENCY_KEY_HEADER,
+};
 use bon::bon;
+use futures::{stream, Stream, StreamExt, TryStreamExt};
 use graphql_client::{GraphQLQuery, QueryBody, Response};
-use reqwest::Client;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::error::Error;
+use std::future::Future;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 
 #[bon]
 impl R3Client {
-    /// Sends a signed GraphQL request to the remote.it API in a blocking way.
+    /// Sends a signed GraphQL request to the remote.it API asynchronously.
     ///
-    /// You probably don't want to use this function directly, but rather use the other functions in this module like [`R3Client::get_files()`].
+    /// You probably don't want to use this function directly, but rather use the other functions in this module like [`R3Client::get_files_async()`].
+    /// Like [`R3Client::send_remoteit_graphql_request()`], this also sends queries you defined yourself, see the example there.
     ///
     /// # Errors
     /// - Any error that occurs during the request.
     /// - Any error that occurs during deserialization of the response.
+    ///   An empty body on a success status is not an error, but a response without data.
+    /// - [`R3Error::GraphQl`](crate::R3Error::GraphQl) if the response contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
     pub async fn send_remoteit_graphql_request_async<V: Serialize, R: for<'a> Deserialize<'a>>(
         &self,
         query_body: &QueryBody<V>,
     ) -> Result<Response<R>, Box<dyn Error>> {
-        let date = get_date();
+        let (response, _) = self
+            .send_remoteit_graphql_request_inner_async(query_body, None)
+            .await?;
+        Ok(response)
+    }
+
+    /// Same as [`R3Client::send_remoteit_graphql_request_async()`], but also returns the [`ResponseMeta`] of the response.
+    /// Use this to read the request ID and the rate limit headers, e.g. to implement client-side throttling.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request.
+    /// - Any error that occurs during deserialization of the response.
+    /// - [`R3Error::GraphQl`](crate::R3Error::GraphQl) if the response contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+    pub async fn send_remoteit_graphql_request_with_meta_async<
+        V: Serialize,
+        R: for<'a> Deserialize<'a>,
+    >(
+        &self,
+        query_body: &QueryBody<V>,
+    ) -> Result<(Response<R>, ResponseMeta), Box<dyn Error>> {
+        self.send_remoteit_graphql_request_inner_async(query_body, None)
+            .await
+    }
+
+    /// Same as [`R3Client::send_remoteit_graphql_request_async()`], but returns the data as untyped JSON, regardless of the query.
+    /// Use [`ResponseExt::to_pretty_json`](crate::ResponseExt::to_pretty_json) to dump the response, e.g. for logging or as a test fixture.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request.
+    /// - Any error that occurs during deserialization of the response.
+    /// - [`R3Error::GraphQl`](crate::R3Error::GraphQl) if the response contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+    pub async fn send_remoteit_graphql_request_value_async<V: Serialize>(
+        &self,
+        query_body: &QueryBody<V>,
+    ) -> Result<Response<serde_json::Value>, Box<dyn Error>> {
+        self.send_remoteit_graphql_request_async(query_body).await
+    }
+
+    /// Sends a signed GraphQL request and returns the raw HTTP response, without deserializing it.
+    /// Use this if you need more control over the response than [`R3Client::send_remoteit_graphql_request_async()`] gives you,
+    /// e.g. to stream the body or to deserialize it into your own types.
+    ///
+    /// The caller is responsible for checking the status and for reading and parsing the body.
+    /// Like the other requests, transient failures are retried according to the [`RetryPolicy`](crate::RetryPolicy) of the client.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request. A response with an error status is not an error.
+    pub async fn send_remoteit_graphql_request_raw_async<V: Serialize>(
+        &self,
+        query_body: &QueryBody<V>,
+    ) -> Result<reqwest::Response, Box<dyn Error>> {
+        self.post_graphql_with_retry_async(query_body, None).await
+    }
+
+    /// Sends a signed GraphQL request, optionally with an idempotency key in the [`IDEMPOTENCY_KEY_HEADER`] header.
+    pub(crate) async fn send_remoteit_graphql_request_inner_async<
+        B: Serialize + ?Sized,
+        R: for<'a> Deserialize<'a>,
+    >(
+        &self,
+        query_body: &B,
+        idempotency_key: Option<String>,
+    ) -> Result<(Response<R>, ResponseMeta), Box<dyn Error>> {
+        let response = self
+            .post_graphql_with_retry_async(query_body, idempotency_key)
+            .await?;
+        let meta = ResponseMeta::new(response.status(), response.headers());
+        let body = response.bytes().await?;
+        let response = self.check_graphql_errors(parse_graphql_response(meta.status, &body)?)?;
+        Ok((response, meta))
+    }
+
+    /// Sends multiple GraphQL operations in a single HTTP request, using GraphQL request batching.
+    /// Use [`batch_query`](crate::batch_query) to turn the typed queries of [`crate::operations`] into untyped ones.
+    ///
+    /// Only batch queries, which read data (e.g. [`GetDevices`], [`GetJobs`], [`GetFiles`]).
+    /// Mutations (e.g. [`StartJob`], [`DeleteFile`]) should be sent on their own:
+    /// if the batch fails, you can't tell which of them were applied, and no idempotency key is sent for them.
+    ///
+    /// This requires the API gateway to accept a JSON array of operations. If it doesn't, an error is returned.
+    ///
+    /// # Returns
+    /// One response per operation, in the same order as `queries`. No request is sent, if `queries` is empty.
+    ///
+    /// Like the other requests, transient failures are retried according to the [`RetryPolicy`](crate::RetryPolicy) of the client.
+    ///
+    /// # Errors
+    /// - [`R3Error::BatchSizeMismatch`] if the API returns a different number of responses than operations were sent.
+    /// - [`R3Error::GraphQl`] if any of the responses contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+    /// - Any error that occurs during the request, including an error status of the response,
+    ///   e.g. if the API gateway doesn't accept batches.
+    /// - Any error that occurs during deserialization of the response.
+    pub async fn send_batch_async(
+        &self,
+        queries: &[QueryBody<serde_json::Value>],
+    ) -> Result<Vec<Response<serde_json::Value>>, Box<dyn Error>> {
+        if queries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .post_graphql_with_retry_async(queries, None)
+            .await?
+            .error_for_status()?;
+        let body = response.bytes().await?;
+        let responses: Vec<Response<serde_json::Value>> = serde_json::from_slice(&body)?;
+        if responses.len() != queries.len() {
+            return Err(R3Error::BatchSizeMismatch {
+                expected: queries.len(),
+                actual: responses.len(),
+            }
+            .into());
+        }
+        Ok(responses
+            .into_iter()
+            .map(|response| self.check_graphql_errors(response))
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Sends an arbitrary GraphQL query and returns the response as untyped JSON.
+    ///
+    /// This is an escape hatch for fields, which the queries in [`crate::operations`] don't select (yet).
+    /// For queries you send regularly, prefer a typed query with [`R3Client::send_remoteit_graphql_request_async()`].
+    ///
+    /// Like the other requests, transient failures are retried according to the [`RetryPolicy`](crate::RetryPolicy) of the client.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request.
+    /// - Any error that occurs during deserialization of the response.
+    /// - [`R3Error::GraphQl`] if the response contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+    #[builder]
+    pub async fn execute_query_async(
+        &self,
+        /// The GraphQL query document.
+        query: &str,
+        /// Optional variables for the query.
+        variables: Option<serde_json::Value>,
+        /// Optional name of the operation to execute, if `query` contains multiple operations.
+        operation_name: Option<&str>,
+    ) -> Result<Response<serde_json::Value>, Box<dyn Error>> {
+        let body = serde_json::json!({
+            "query": query,
+            "variables": variables.unwrap_or_else(|| serde_json::json!({})),
+            "operationName": operation_name,
+        });
+        self.send_remoteit_graphql_request_inner_async(&body, None)
+            .await
+            .map(|(response, _)| response)
+    }
+
+    /// Get the devices and the jobs in a single HTTP request. See [`R3Client::send_batch_async()`].
+    ///
+    /// # Errors
+    /// - Any error that [`R3Client::send_batch_async()`] can return.
+    /// - Any error that occurs during deserialization of the individual responses.
+    #[builder]
+    pub async fn get_devices_and_jobs_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        device_limit: Option<i64>,
+        /// Optional limit for the number of jobs to return.
+        job_limit: Option<i64>,
+    ) -> Result<
+        (
+            Response<get_devices::ResponseData>,
+            Response<get_jobs::ResponseData>,
+        ),
+        Box<dyn Error>,
+    > {
+        let devices = GetDevices::build_query(get_devices::Variables {
+            org_id: org_id.clone(),
+            limit: device_limit,
+            offset: None,
+            state: None,
+            name: None,
+        });
+        let jobs = GetJobs::build_query(get_jobs::Variables {
+            org_id,
+            limit: job_limit,
+            after: None,
+            job_ids: None,
+            statuses: None,
+        });
+        let mut responses = self
+            .send_batch_async(&[batch_query(&devices)?, batch_query(&jobs)?])
+            .await?
+            .into_iter();
+        let (Some(devices), Some(jobs)) = (responses.next(), responses.next()) else {
+            unreachable!("send_batch_async returns exactly one response per query");
+        };
+        Ok((typed_response(devices)?, typed_response(jobs)?))
+    }
+
+    /// Measures the difference between the local clock and the clock of the remote.it API,
+    /// using the `Date` header of a lightweight request.
+    ///
+    /// The API rejects requests, if the signed `Date` header is too far off, which results in a cryptic `401`.
+    /// After calling this, all requests of this client and its clones are signed with the corrected time.
+    /// Call this once, e.g. on startup of a long-running process.
+    ///
+    /// # Returns
+    /// The measured clock skew (API time minus local time).
+    ///
+    /// # Errors
+    /// - [`R3Error::NoServerDate`] if the response has no valid `Date` header.
+    /// - Any error that occurs during the request.
+    pub async fn check_clock_skew_async(&self) -> Result<chrono::Duration, Box<dyn Error>> {
+        let response = self
+            .post_graphql_async(&serde_json::json!({ "query": "{ __typename }" }), None)
+            .await?;
+        Ok(self
+            .apply_server_date(response.headers())
+            .ok_or(R3Error::NoServerDate)?)
+    }
+
+    /// Signs and sends a JSON body to the GraphQL endpoint.
+    /// Same as `post_graphql_async`, but retries transient failures according to the [`RetryPolicy`](crate::RetryPolicy) of the client.
+    async fn post_graphql_with_retry_async<B: Serialize + ?Sized>(
+        &self,
+        body: &B,
+        idempotency_key: Option<String>,
+    ) -> Result<reqwest::Response, Box<dyn Error>> {
+        let mut retry = 0;
+        loop {
+            // The result must not be held across the sleep, because `Box<dyn Error>` is not `Send`,
+            // which would make the futures of all requests not `Send`.
+            let delay = {
+                let result = self.post_graphql_async(body, idempotency_key.clone()).await;
+                let retryable = match &result {
+                    Ok(response) => {
+                        self.record_rate_limit(response.status(), response.headers());
+                        is_retryable_status(response.status())
+                    }
+                    Err(error) => is_retryable_error(error.as_ref()),
+                };
+                if !retryable || retry >= self.retry_policy.max_retries {
+                    return result;
+                }
+                self.retry_policy.backoff.delay(retry)
+            };
+            tokio::time::sleep(delay).await;
+            retry += 1;
+        }
+    }
+
+    /// Builds a signed request to the remote.it API, which is ready to be sent.
+    /// Use this to call parts of the API, that this crate doesn't wrap (yet).
+    ///
+    /// The `Date`, `Content-Type` and `Authorization` headers are set and signed the same way as for the requests of this crate.
+    /// If the client signs body digests (see [`R3Client::sign_body_digest`]) and there is a body, the `Digest` header is set and signed as well.
+    /// Don't change these headers, the method, the path or the body afterwards, or the signature becomes invalid.
+    ///
+    /// # Returns
+    /// A [`reqwest::RequestBuilder`] for the base URL of the client plus `path`.
+    #[builder]
+    pub fn signed_request_async(
+        &self,
+        /// The HTTP method of the request.
+        method: Method,
+        /// The path of the request, e.g. [`GRAPHQL_PATH`](crate::GRAPHQL_PATH). It is appended to the base URL and is part of the signature.
+        path: &str,
+        /// Optional body of the request.
+        body: Option<Vec<u8>>,
+        /// The content type of the request. It is signed even if there is no body. Defaults to `application/json`.
+        #[builder(default = "application/json")]
+        content_type: &str,
+    ) -> reqwest::RequestBuilder {
+        let digest = body
+            .as_deref()
+            .filter(|_| self.sign_body_digest)
+            .map(create_digest);
+        let date = self.signing_date();
         let auth_header = build_auth_header()
             .key_id(&self.credentials.r3_access_key_id)
-            .key(&self.credentials.key)
-            .content_type("application/json")
-            .method(&Method::POST)
-            .path(GRAPHQL_PATH)
+            .key(self.credentials.signing_key())
+            .content_type(content_type)
+            .method(&method)
+            .path(path)
             .date(&date)
-            .call();
-        let client = Client::new();
-        let response = client
-            .post(format!("{BASE_URL}{GRAPHQL_PATH}"))
+            .host(self.environment.host());
+        let auth_header = match &digest {
+            Some(digest) => auth_header.digest(digest).call(),
+            None => auth_header.call(),
+        };
+        let mut request = self
+            .http_client
+            .request(method, format!("{}{}", self.base_url, path))
             .header("Date", date)
-            .header("Content-Type", "application/json")
-            .header("Authorization", auth_header)
-            .json(&query_body)
-            .send()
-            .await?;
-        let response: Response<R> = response.json().await?;
-        Ok(response)
+            .header("Content-Type", content_type)
+            .header("Authorization", auth_header);
+        if let Some(digest) = digest {
+            request = request.header("Digest", digest);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        request
+    }
+
+    async fn post_graphql_async<B: Serialize + ?Sized>(
+        &self,
+        body: &B,
+        idempotency_key: Option<String>,
+    ) -> Result<reqwest::Response, Box<dyn Error>> {
+        let mut request = self
+            .signed_request_async()
+            .method(Method::POST)
+            .path(&self.graphql_path)
+            .body(serde_json::to_vec(body)?)
+            .call();
+        if let Some(idempotency_key) = idempotency_key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, idempotency_key);
+        }
+        Ok(request.send().await?)
     }
 
     // region Scripting
 
     /// Get a list of files that were uploaded to remote.it.
+    ///
+    /// Set `executable` to only get executable scripts (`true`), or only assets (`false`),
+    /// and `owner_id` to only get the files owned by the user with that ID.
+    /// The API supports neither filter, so all files are still fetched and then filtered on the client side.
     #[builder]
     pub async fn get_files_async(
         &self,
         /// Optional organization ID for org context.
         org_id: Option<String>,
+        /// Optional filter for executable scripts or assets.
+        executable: Option<bool>,
+        /// Optional ID of the user, whose files to get.
+        owner_id: Option<String>,
     ) -> Result<Response<get_files::ResponseData>, Box<dyn Error>> {
         let request_body = GetFiles::build_query(get_files::Variables { org_id });
-        self.send_remoteit_graphql_request_async(&request_body)
-            .await
+        let mut response: Response<get_files::ResponseData> = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        if let (Some(executable), Some(data)) = (executable, response.data.as_mut()) {
+            data.retain_executable(executable);
+        }
+        if let (Some(owner_id), Some(data)) = (owner_id, response.data.as_mut()) {
+            data.retain_owner(&owner_id);
+        }
+        Ok(response)
+    }
+
+    /// Get the files owned by the user with the given ID, e.g. to hide the files of other users in a shared organization.
+    /// Like the `owner_id` filter of [`R3Client::get_files_async()`], this filters on the client side.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn my_files_async(
+        &self,
+        /// The ID of the user, whose files to get.
+        user_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<get_files::GetFilesLoginAccountFiles>, Box<dyn Error>> {
+        let response = self
+            .get_files_async()
+            .maybe_org_id(org_id)
+            .owner_id(user_id)
+            .call()
+            .await?;
+        Ok(response_data::<get_files::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| account.files)
+            .unwrap_or_default())
+    }
+
+    /// Get the versions of a single file.
+    ///
+    /// # Returns
+    /// The versions of the file, ordered from oldest to newest. Empty, if there is no file with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_file_versions_async(
+        &self,
+        /// The ID of the file.
+        /// You can get this from the response of [`R3Client::get_files_async()`].
+        file_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<FileVersion>, Box<dyn Error>> {
+        let request_body =
+            GetFileVersions::build_query(get_file_versions::Variables { file_id, org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let mut versions = response_data::<get_file_versions::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .and_then(|account| account.files.into_iter().next())
+            .map(|file| file.versions)
+            .unwrap_or_default();
+        versions.sort_by(|a, b| a.version.total_cmp(&b.version));
+        Ok(versions)
     }
 
     /// Delete a file from remote.it. Deletes all versions of the file.
@@ -70,10 +487,60 @@ impl R3Client {
         /// The ID of the file to delete.
         /// You can get this from the response of [`R3Client::get_files()`].
         file_id: String,
+        /// Optional idempotency key, sent in the [`IDEMPOTENCY_KEY_HEADER`] header.
+        /// Use the same key when retrying the request, so the API doesn't apply the mutation twice.
+        idempotency_key: Option<String>,
     ) -> Result<Response<delete_file::ResponseData>, Box<dyn Error>> {
         let request_body = DeleteFile::build_query(delete_file::Variables { file_id });
-        self.send_remoteit_graphql_request_async(&request_body)
+        self.send_remoteit_graphql_request_inner_async(&request_body, idempotency_key)
             .await
+            .map(|(response, _)| response)
+    }
+
+    /// Delete multiple files from remote.it. (All versions)
+    ///
+    /// Up to `concurrency` files are deleted at the same time.
+    ///
+    /// # Returns
+    /// One result per file, in the same order as `file_ids`.
+    /// A failing delete does not stop the remaining files from being deleted.
+    /// Every delete is sent with a newly generated idempotency key.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `concurrency` is zero. No request is sent in this case.
+    #[builder]
+    pub async fn delete_files_async(
+        &self,
+        /// The IDs of the files to delete.
+        /// You can get these from the response of [`R3Client::get_files_async()`].
+        file_ids: Vec<String>,
+        /// The maximum number of files to delete at the same time. Defaults to 4.
+        #[builder(default = 4)]
+        concurrency: usize,
+    ) -> Result<Vec<DeleteFileResult>, Box<dyn Error>> {
+        if concurrency == 0 {
+            return Err(
+                R3Error::InvalidArgument("`concurrency` must not be zero".to_string()).into(),
+            );
+        }
+        let mut results: Vec<(usize, DeleteFileResult)> =
+            stream::iter(file_ids.into_iter().enumerate())
+                .map(|(index, file_id)| async move {
+                    let result = self
+                        .delete_file_async()
+                        .file_id(file_id)
+                        .idempotency_key(generate_idempotency_key())
+                        .call()
+                        .await
+                        .and_then(|response| Ok(response_data(response)?.delete_file))
+                        .map_err(into_send_error);
+                    (index, result)
+                })
+                .buffer_unordered(concurrency)
+                .collect()
+                .await;
+        results.sort_by_key(|(index, _)| *index);
+        Ok(results.into_iter().map(|(_, result)| result).collect())
     }
 
     /// Delete a version of a file from remote.it. (Not the whole file)
@@ -83,14 +550,22 @@ impl R3Client {
         /// The ID of the file version to delete.
         /// You can get this from the response of [`R3Client::get_files()`].
         file_version_id: String,
+        /// Optional idempotency key, sent in the [`IDEMPOTENCY_KEY_HEADER`] header.
+        /// Use the same key when retrying the request, so the API doesn't apply the mutation twice.
+        idempotency_key: Option<String>,
     ) -> Result<Response<delete_file_version::ResponseData>, Box<dyn Error>> {
         let request_body =
             DeleteFileVersion::build_query(delete_file_version::Variables { file_version_id });
-        self.send_remoteit_graphql_request_async(&request_body)
+        self.send_remoteit_graphql_request_inner_async(&request_body, idempotency_key)
             .await
+            .map(|(response, _)| response)
     }
 
     /// Start scripting jobs on one or more devices.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `device_ids` is empty. No request is sent in this case.
+    /// - Any error that occurs during the request.
     #[builder]
     pub async fn start_job_async(
         &self,
@@ -104,16 +579,101 @@ impl R3Client {
         /// Arguments to pass to the script.
         /// These are optional.
         /// For more information on script arguments please consult the remote.it API documentation.
+        /// Use [`start_job::ArgumentInput::from_pairs`] to create them from name/value pairs.
         #[builder(default)]
         arguments: Vec<start_job::ArgumentInput>,
+        /// Optional idempotency key, sent in the [`IDEMPOTENCY_KEY_HEADER`] header.
+        /// Use the same key when retrying the request, so the API doesn't apply the mutation twice.
+        idempotency_key: Option<String>,
     ) -> Result<Response<start_job::ResponseData>, Box<dyn Error>> {
+        if device_ids.is_empty() {
+            return Err(R3Error::InvalidArgument(
+                "`device_ids` must contain at least one device ID".to_string(),
+            )
+            .into());
+        }
         let request_body = StartJob::build_query(start_job::Variables {
             file_id,
             device_ids,
             arguments,
         });
-        self.send_remoteit_graphql_request_async(&request_body)
+        self.send_remoteit_graphql_request_inner_async(&request_body, idempotency_key)
             .await
+            .map(|(response, _)| response)
+    }
+
+    /// Start scripting jobs on many devices, by splitting the devices into chunks and starting one job per chunk.
+    ///
+    /// This is useful, when the number of devices is too large for a single [`R3Client::start_job_async()`] request.
+    /// Up to `concurrency` chunks are started at the same time.
+    ///
+    /// # Returns
+    /// One result per chunk, in the order of the chunks. Each successful result contains the ID of the started job.
+    /// A failing chunk does not stop the remaining chunks from being started.
+    /// Every chunk is sent with a newly generated idempotency key.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `device_ids` is empty, or `chunk_size` or `concurrency` is zero. No request is sent in this case.
+    #[builder]
+    pub async fn start_job_bulk_async(
+        &self,
+        /// The ID of the script file to run.
+        /// Note that this needs to be an executable file.
+        /// Get a list of files using [`R3Client::get_files_async()`].
+        file_id: String,
+        /// The IDs of the devices to run the script on.
+        /// Get a list of devices using [`R3Client::get_devices_async()`].
+        device_ids: Vec<String>,
+        /// The maximum number of devices per job.
+        chunk_size: usize,
+        /// Arguments to pass to the script. The same arguments are used for every chunk.
+        /// Use [`start_job::ArgumentInput::from_pairs`] to create them from name/value pairs.
+        #[builder(default)]
+        arguments: Vec<start_job::ArgumentInput>,
+        /// The maximum number of chunks to start at the same time. Defaults to 4.
+        #[builder(default = 4)]
+        concurrency: usize,
+    ) -> Result<Vec<StartJobResult>, Box<dyn Error>> {
+        if device_ids.is_empty() {
+            return Err(R3Error::InvalidArgument(
+                "`device_ids` must contain at least one device ID".to_string(),
+            )
+            .into());
+        }
+        if chunk_size == 0 || concurrency == 0 {
+            return Err(R3Error::InvalidArgument(
+                "`chunk_size` and `concurrency` must not be zero".to_string(),
+            )
+            .into());
+        }
+        let mut results: Vec<(usize, StartJobResult)> = stream::iter(
+            device_ids
+                .chunks(chunk_size)
+                .map(<[String]>::to_vec)
+                .enumerate(),
+        )
+        .map(|(index, chunk)| {
+            let arguments = arguments.clone();
+            let file_id = file_id.clone();
+            async move {
+                let result = self
+                    .start_job_async()
+                    .file_id(file_id)
+                    .device_ids(chunk)
+                    .arguments(arguments)
+                    .idempotency_key(generate_idempotency_key())
+                    .call()
+                    .await
+                    .and_then(|response| Ok(response_data(response)?.start_job))
+                    .map_err(into_send_error);
+                (index, result)
+            }
+        })
+        .buffer_unordered(concurrency)
+        .collect()
+        .await;
+        results.sort_by_key(|(index, _)| *index);
+        Ok(results.into_iter().map(|(_, result)| result).collect())
     }
 
     /// Cancel a job. See remote.it docs on more information on when jobs can be cancelled.
@@ -123,10 +683,14 @@ impl R3Client {
         /// The ID of the job to cancel.
         /// You get this after starting a job using [`R3Client::start_job()`].
         job_id: String,
+        /// Optional idempotency key, sent in the [`IDEMPOTENCY_KEY_HEADER`] header.
+        /// Use the same key when retrying the request, so the API doesn't apply the mutation twice.
+        idempotency_key: Option<String>,
     ) -> Result<Response<cancel_job::ResponseData>, Box<dyn Error>> {
         let request_body = CancelJob::build_query(cancel_job::Variables { job_id });
-        self.send_remoteit_graphql_request_async(&request_body)
+        self.send_remoteit_graphql_request_inner_async(&request_body, idempotency_key)
             .await
+            .map(|(response, _)| response)
     }
 
     /// Get a list of jobs that were started on remote.it.
@@ -145,6 +709,7 @@ impl R3Client {
         let request_body = GetJobs::build_query(get_jobs::Variables {
             org_id,
             limit,
+            after: None,
             job_ids: job_id_filter,
             statuses: status_filter,
         });
@@ -152,90 +717,1562 @@ impl R3Client {
             .await
     }
 
-    // endregion
-    // region Organizations
-    /// Get data on your own organization, which belongs to the current user.
-    /// This Organization may or may not exist. You can create and configure your organization through the remote.it Web UI.
+    /// Stream all jobs, fetching the next page when the jobs of the previous page are consumed.
+    /// Takes the same filters as [`R3Client::get_jobs_async()`].
     ///
-    /// # Returns
-    /// Data on your organization, if you have one.
+    /// The stream ends after the last page, or after the first error.
+    /// No request is sent until the stream is polled.
     #[builder]
-    pub async fn get_owned_organization_async(
+    pub fn get_jobs_stream(
         &self,
-    ) -> Result<Response<get_owned_organization::ResponseData>, Box<dyn Error>> {
-        let request_body = GetOwnedOrganization::build_query(get_owned_organization::Variables {});
-        self.send_remoteit_graphql_request_async(&request_body)
-            .await
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// How many jobs to fetch per request.
+        #[builder(default = 100)]
+        page_size: i64,
+        /// Optional list of job IDs to filter by.
+        job_id_filter: Option<Vec<String>>,
+        /// Optional list of job statuses to filter by.
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> impl Stream<Item = Result<get_jobs::GetJobsLoginAccountJobsItems, Box<dyn Error>>> + '_
+    {
+        self.stream_jobs(get_jobs::Variables {
+            org_id,
+            limit: Some(page_size),
+            after: None,
+            job_ids: job_id_filter,
+            statuses: status_filter,
+        })
     }
-    // endregion
-    // region Devices and Services
 
-    /// Get a list of application types that are available on remote.it.
-    #[builder]
-    pub async fn get_application_types_async(
+    /// Streams the jobs matching `variables`, page by page. The `after` cursor of `variables` is overwritten for each page.
+    fn stream_jobs(
         &self,
-    ) -> Result<Response<get_application_types::ResponseData>, Box<dyn Error>> {
-        let request_body = GetApplicationTypes::build_query(get_application_types::Variables {});
-        self.send_remoteit_graphql_request_async(&request_body)
-            .await
+        variables: get_jobs::Variables,
+    ) -> impl Stream<Item = Result<get_jobs::GetJobsLoginAccountJobsItems, Box<dyn Error>>> + '_
+    {
+        // The state are the variables for the next page, or `None` after the last page.
+        stream::try_unfold(Some(variables), move |variables| async move {
+            let Some(variables) = variables else {
+                return Ok::<_, Box<dyn Error>>(None);
+            };
+            let request_body = GetJobs::build_query(variables.clone());
+            let response = self
+                .send_remoteit_graphql_request_async(&request_body)
+                .await?;
+            let page = jobs_page(response_data(response)?);
+            let next = page.next.map(|cursor| get_jobs::Variables {
+                after: match cursor {
+                    PageCursor::After(after) => Some(after),
+                    PageCursor::Offset(_) => None,
+                },
+                ..variables
+            });
+            let jobs = stream::iter(page.items.into_iter().map(Ok::<_, Box<dyn Error>>));
+            Ok(Some((jobs, next)))
+        })
+        .try_flatten()
     }
 
-    /// Get a list of devices.
+    /// Get all jobs, fetching page after page until there are no more.
+    /// Takes the same filters as [`R3Client::get_jobs_async()`]. Use [`R3Client::get_jobs_stream()`] to process the jobs while they are fetched.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the requests. The first error stops the remaining requests.
     #[builder]
-    pub async fn get_devices_async(
+    pub async fn get_all_jobs_async(
         &self,
         /// Optional organization ID for org context.
         org_id: Option<String>,
-        /// Optional limit for the number of devices to return.
-        limit: Option<i64>,
-        /// Optional offset for the devices. Useful for pagination.
-        offset: Option<i64>,
-        /// Optional state to filter by.
-        state: Option<DeviceState>
-    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
-        let request_body = GetDevices::build_query(get_devices::Variables {
+        /// How many jobs to fetch per request.
+        #[builder(default = 100)]
+        page_size: i64,
+        /// Optional list of job IDs to filter by.
+        job_id_filter: Option<Vec<String>>,
+        /// Optional list of job statuses to filter by.
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Result<Vec<get_jobs::GetJobsLoginAccountJobsItems>, Box<dyn Error>> {
+        self.stream_jobs(get_jobs::Variables {
             org_id,
-            limit,
-            offset,
-            state: state.map(|s| s.to_string()),
-        });
-        self.send_remoteit_graphql_request_async(&request_body)
-            .await
-    }
-
-    // endregion
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::credentials::Credentials;
-    use std::path::PathBuf;
-
-    fn get_credentials() -> Credentials {
-        Credentials::load_from_disk()
-            .custom_credentials_path(PathBuf::from(".env.remoteit"))
-            .call()
-            .unwrap()
-            .take_profile("default")
-            .unwrap()
-            .unwrap()
+            limit: Some(page_size),
+            after: None,
+            job_ids: job_id_filter,
+            statuses: status_filter,
+        })
+        .try_collect()
+        .await
     }
 
-    fn get_client() -> R3Client {
-        R3Client::builder().credentials(get_credentials()).build()
+    /// Count the jobs per status, e.g. to show "3 running, 10 succeeded, 1 failed" on a dashboard.
+    /// The jobs themselves are not fetched, the API only returns the number of jobs for each status.
+    ///
+    /// # Returns
+    /// The number of jobs for each status in [`all_job_statuses`](crate::operations::all_job_statuses).
+    /// Empty, if there is no account with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_job_summary_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<HashMap<get_jobs::JobStatusEnum, u64>, Box<dyn Error>> {
+        let request_body = GetJobSummary::build_query(get_job_summary::Variables { org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(response_data::<get_job_summary::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(get_job_summary::GetJobSummaryLoginAccount::into_counts)
+            .unwrap_or_default())
     }
 
-    #[tokio::test]
-    async fn test_get_files_async() {
-        let response = get_client().get_files_async().call().await.unwrap();
-        assert!(response.data.is_some());
-        assert!(response.errors.is_none());
+    /// Get a single job, including the status of the job on each device.
+    ///
+    /// # Returns
+    /// - [`None`] if there is no job with the given ID.
+    /// - [`Some`] containing the job, if it exists.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_job_async(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job_async()`].
+        job_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Option<Job>, Box<dyn Error>> {
+        let request_body = GetJob::build_query(get_job::Variables { job_id, org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let job = response_data::<get_job::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .and_then(|account| account.jobs.items.into_iter().next());
+        Ok(job)
     }
 
-    #[tokio::test]
-    async fn test_get_jobs_async() {
-        let response = get_client().get_jobs_async().limit(1).call().await.unwrap();
+    /// Wait until a job has finished, by polling [`R3Client::get_job_async()`].
+    ///
+    /// This can be cancelled by dropping the returned future.
+    /// See [`R3Client::wait_for_job_with_cancel_async()`] for a version, which can be cancelled using a [`CancellationToken`].
+    ///
+    /// # Returns
+    /// The job, once its status [is finished](get_job::JobStatusEnum::is_finished).
+    ///
+    /// # Errors
+    /// - [`R3Error::JobNotFound`] if there is no job with the given ID.
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn wait_for_job_async(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job_async()`].
+        job_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// How long to wait between two polls. Defaults to 5 seconds.
+        #[builder(default = Duration::from_secs(5))]
+        poll_interval: Duration,
+    ) -> Result<Job, Box<dyn Error>> {
+        self.poll_job_async(job_id, org_id, poll_interval, &CancellationToken::new())
+            .await
+    }
+
+    /// Same as [`R3Client::wait_for_job_async()`], but stops polling as soon as `cancel_token` is cancelled.
+    /// This also aborts the request that is currently in flight, if any.
+    ///
+    /// # Returns
+    /// The job, once its status [is finished](get_job::JobStatusEnum::is_finished).
+    ///
+    /// # Errors
+    /// - [`R3Error::Cancelled`] if `cancel_token` was cancelled before the job finished.
+    /// - [`R3Error::JobNotFound`] if there is no job with the given ID.
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn wait_for_job_with_cancel_async(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job_async()`].
+        job_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// How long to wait between two polls. Defaults to 5 seconds.
+        #[builder(default = Duration::from_secs(5))]
+        poll_interval: Duration,
+        /// The token used to cancel waiting.
+        cancel_token: CancellationToken,
+    ) -> Result<Job, Box<dyn Error>> {
+        self.poll_job_async(job_id, org_id, poll_interval, &cancel_token)
+            .await
+    }
+
+    async fn poll_job_async(
+        &self,
+        job_id: String,
+        org_id: Option<String>,
+        poll_interval: Duration,
+        cancel_token: &CancellationToken,
+    ) -> Result<Job, Box<dyn Error>> {
+        loop {
+            let request_body = GetJob::build_query(get_job::Variables {
+                job_id: job_id.clone(),
+                org_id: org_id.clone(),
+            });
+            let response = tokio::select! {
+                () = cancel_token.cancelled() => return Err(R3Error::Cancelled.into()),
+                response = self.send_remoteit_graphql_request_async(&request_body) => response?,
+            };
+            let job = response_data::<get_job::ResponseData>(response)?
+                .login
+                .and_then(|login| login.account)
+                .and_then(|account| account.jobs.items.into_iter().next())
+                .ok_or_else(|| R3Error::JobNotFound(job_id.clone()))?;
+            if job.status.is_finished() {
+                return Ok(job);
+            }
+            tokio::select! {
+                () = cancel_token.cancelled() => return Err(R3Error::Cancelled.into()),
+                () = tokio::time::sleep(poll_interval) => {}
+            }
+        }
+    }
+
+    /// Get the result of a job on each device it ran on, including the exit code and output of the script.
+    ///
+    /// # Returns
+    /// One [`JobDeviceResult`] per device. Empty, if there is no job with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_job_results_async(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job_async()`].
+        job_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<JobDeviceResult>, Box<dyn Error>> {
+        let request_body =
+            GetJobResults::build_query(get_job_results::Variables { job_id, org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let results = response_data::<get_job_results::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .and_then(|account| account.jobs.items.into_iter().next())
+            .map(|job| {
+                job.job_devices
+                    .into_iter()
+                    .map(JobDeviceResult::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(results)
+    }
+
+    // endregion
+    // region Organizations
+    /// Get data on your own organization, which belongs to the current user.
+    /// This Organization may or may not exist. You can create and configure your organization through the remote.it Web UI.
+    ///
+    /// # Returns
+    /// Data on your organization, if you have one.
+    #[builder]
+    pub async fn get_owned_organization_async(
+        &self,
+    ) -> Result<Response<get_owned_organization::ResponseData>, Box<dyn Error>> {
+        let request_body = GetOwnedOrganization::build_query(get_owned_organization::Variables {});
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+    /// Get the members of an organization.
+    ///
+    /// The remote.it API returns all members at once, so `limit` and `offset` are applied client-side.
+    ///
+    /// # Returns
+    /// The organization with its members. Each member contains the user ID, email and role.
+    #[builder]
+    pub async fn get_organization_members_async(
+        &self,
+        /// Optional organization ID. If omitted, your own organization is used.
+        org_id: Option<String>,
+        /// Optional limit for the number of members to return.
+        limit: Option<i64>,
+        /// Optional offset for the members. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_organization_members::ResponseData>, Box<dyn Error>> {
+        let request_body =
+            GetOrganizationMembers::build_query(get_organization_members::Variables { org_id });
+        let mut response: Response<get_organization_members::ResponseData> = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        if let Some(organization) = response
+            .data
+            .as_mut()
+            .and_then(|data| data.login.as_mut())
+            .and_then(|login| login.account.as_mut())
+            .and_then(|account| account.organization.as_mut())
+        {
+            paginate(&mut organization.members, limit, offset);
+        }
+        Ok(response)
+    }
+
+    /// Invite a user to an organization, or change the role of an existing member.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `role` is [`OrganizationRole::Unknown`]. No request is sent in this case.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn invite_org_member_async(
+        &self,
+        /// Optional organization ID. If omitted, your own organization is used.
+        org_id: Option<String>,
+        /// The email address of the user to invite.
+        email: String,
+        /// The role of the user within the organization.
+        role: OrganizationRole,
+    ) -> Result<Response<invite_organization_member::ResponseData>, Box<dyn Error>> {
+        let role = invite_organization_member::OrganizationRole::try_from(role)?;
+        let request_body =
+            InviteOrganizationMember::build_query(invite_organization_member::Variables {
+                org_id,
+                emails: vec![email],
+                role: Some(role),
+            });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Remove a member from an organization.
+    /// The remote.it API identifies members by their email address.
+    #[builder]
+    pub async fn remove_org_member_async(
+        &self,
+        /// Optional organization ID. If omitted, your own organization is used.
+        org_id: Option<String>,
+        /// The email address of the member to remove.
+        email: String,
+    ) -> Result<Response<remove_organization_member::ResponseData>, Box<dyn Error>> {
+        let request_body =
+            RemoveOrganizationMember::build_query(remove_organization_member::Variables {
+                org_id,
+                emails: vec![email],
+            });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    // endregion
+    // region Account
+
+    /// Get the account of the currently authenticated user, i.e. the owner of the credentials.
+    ///
+    /// # Returns
+    /// The ID, email and basic profile fields of your account.
+    #[builder]
+    pub async fn get_self_async(&self) -> Result<Response<get_self::ResponseData>, Box<dyn Error>> {
+        let request_body = GetSelf::build_query(get_self::Variables {});
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+    /// Get the audit log of your account, or the organization with the given ID.
+    ///
+    /// # Returns
+    /// A [`PagedResult`] with the entries on the requested page, ordered from oldest to newest.
+    /// The API pages from the newest entry, so `offset` 0 is the page with the most recent entries.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_audit_log_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of entries to return.
+        limit: Option<i64>,
+        /// Optional offset for the entries. Useful for pagination.
+        offset: Option<i64>,
+        /// Optionally only return entries from this point in time on.
+        since: Option<chrono::DateTime<chrono::Local>>,
+        /// Optionally only return entries up to this point in time.
+        until: Option<chrono::DateTime<chrono::Local>>,
+    ) -> Result<PagedResult<AuditLogEntry>, Box<dyn Error>> {
+        let request_body = GetAuditLog::build_query(get_audit_log::Variables {
+            org_id,
+            limit,
+            offset,
+            min_date: since,
+            max_date: until,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let events = response_data::<get_audit_log::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| account.events);
+        Ok(match events {
+            Some(events) => {
+                let mut items: Vec<AuditLogEntry> =
+                    events.items.into_iter().map(AuditLogEntry::from).collect();
+                items.sort_by_key(|entry| entry.timestamp);
+                PagedResult {
+                    items,
+                    total: events.total,
+                    has_more: events.has_more,
+                    offset,
+                    limit,
+                }
+            }
+            None => PagedResult {
+                items: Vec::new(),
+                total: 0,
+                has_more: false,
+                offset,
+                limit,
+            },
+        })
+    }
+
+    /// Get the plan of your account, or the organization with the given ID, and how many devices it uses.
+    ///
+    /// # Returns
+    /// The [`AccountPlan`], or `None` if there is no account with the given ID.
+    /// Free accounts have no license, so their plan ID and name are `None`.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_plan_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Option<AccountPlan>, Box<dyn Error>> {
+        let request_body = GetPlan::build_query(get_plan::Variables { org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(response_data::<get_plan::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(AccountPlan::from))
+    }
+
+    // endregion
+    // region Devices and Services
+
+    /// Get a list of application types that are available on remote.it.
+    #[builder]
+    pub async fn get_application_types_async(
+        &self,
+    ) -> Result<Response<get_application_types::ResponseData>, Box<dyn Error>> {
+        let request_body = GetApplicationTypes::build_query(get_application_types::Variables {});
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Get a list of devices.
+    #[builder]
+    pub async fn get_devices_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        /// The filter is applied server-side. The remote.it API returns devices whose name contains the given text.
+        name: Option<String>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: state.map(|s| s.to_string()),
+            name,
+        });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Get the number of devices, without transferring the device data.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_device_count_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<u64, Box<dyn Error>> {
+        let request_body = GetDeviceCount::build_query(get_device_count::Variables { org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let total = response_data::<get_device_count::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map_or(0, |account| account.devices.total);
+        Ok(u64::try_from(total).unwrap_or(0))
+    }
+
+    /// Get a page of devices, together with the total number of devices matching the filters.
+    /// Takes the same filters as [`R3Client::get_devices_async()`].
+    ///
+    /// # Returns
+    /// A [`PagedResult`] with the devices on the requested page. Use [`PagedResult::total`] to show e.g. "showing 20 of 340".
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_devices_paged_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        name: Option<String>,
+    ) -> Result<PagedResult<get_devices::GetDevicesLoginAccountDevicesItems>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: state.map(|s| s.to_string()),
+            name,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let devices = response_data::<get_devices::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| account.devices);
+        Ok(match devices {
+            Some(devices) => PagedResult {
+                items: devices.items,
+                total: devices.total,
+                has_more: devices.has_more,
+                offset,
+                limit,
+            },
+            None => PagedResult {
+                items: Vec::new(),
+                total: 0,
+                has_more: false,
+                offset,
+                limit,
+            },
+        })
+    }
+
+    /// Get a list of devices as flat [`Device`] structs, instead of the nested generated response.
+    /// Takes the same filters as [`R3Client::get_devices_async()`].
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_devices_typed_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        name: Option<String>,
+    ) -> Result<Vec<Device>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: state.map(|s| s.to_string()),
+            name,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let devices = response_data::<get_devices::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| {
+                account
+                    .devices
+                    .items
+                    .into_iter()
+                    .map(Device::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(devices)
+    }
+
+    /// Get the devices of multiple organizations as one list.
+    ///
+    /// Up to `concurrency` organizations are queried at the same time.
+    ///
+    /// # Returns
+    /// The devices of all organizations, in the order of `org_ids`, each tagged with the organization it was listed in.
+    /// A device shared with multiple of the organizations is listed once per organization.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `concurrency` is zero. No request is sent in this case.
+    /// - [`R3Error::GraphQl`] if the API returns errors for any of the organizations.
+    /// - Any error that occurs during the requests.
+    ///
+    /// The first error is returned right away, without waiting for the remaining organizations.
+    #[builder]
+    pub async fn get_devices_all_orgs_async(
+        &self,
+        /// The IDs of the organizations to list the devices of.
+        org_ids: Vec<String>,
+        /// The maximum number of organizations to query at the same time. Defaults to 4.
+        #[builder(default = 4)]
+        concurrency: usize,
+    ) -> Result<Vec<OrgDevice>, Box<dyn Error>> {
+        if concurrency == 0 {
+            return Err(
+                R3Error::InvalidArgument("`concurrency` must not be zero".to_string()).into(),
+            );
+        }
+        let devices: Vec<Vec<OrgDevice>> = stream::iter(org_ids)
+            .map(|org_id| async move {
+                let devices = self
+                    .get_devices_typed_async()
+                    .org_id(org_id.clone())
+                    .call()
+                    .await
+                    .map_err(into_send_error)?;
+                Ok(devices
+                    .into_iter()
+                    .map(|device| OrgDevice {
+                        org_id: org_id.clone(),
+                        device,
+                    })
+                    .collect())
+            })
+            .buffered(concurrency)
+            .try_collect()
+            .await
+            .map_err(|error: Box<dyn Error + Send + Sync>| -> Box<dyn Error> { error })?;
+        Ok(devices.into_iter().flatten().collect())
+    }
+
+    /// Run the same operation for many devices, with up to `concurrency` devices at the same time.
+    ///
+    /// `f` is called once per device ID, with this client and the ID, and returns the future to run for that device.
+    /// ```no_run
+    /// # async fn example(client: remoteit_api::R3Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let events = client
+    ///     .for_each_device_async()
+    ///     .device_ids(vec!["device-1".to_string(), "device-2".to_string()])
+    ///     .f(|client, device_id| client.get_device_events_async().device_id(device_id).call())
+    ///     .call()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Returns
+    /// The result of `f` for every device, tagged with the device ID, in the order the futures completed.
+    /// A failure for one device does not stop the others.
+    ///
+    /// # Errors
+    /// [`R3Error::InvalidArgument`] if `concurrency` is zero. `f` is not called in this case.
+    #[builder]
+    pub async fn for_each_device_async<'a, F, Fut, T>(
+        &'a self,
+        /// The IDs of the devices to run `f` for.
+        device_ids: Vec<String>,
+        /// The maximum number of devices to run `f` for at the same time. Defaults to 4.
+        #[builder(default = 4)]
+        concurrency: usize,
+        /// Creates the future to run for a device.
+        f: F,
+    ) -> Result<Vec<(String, Result<T, Box<dyn Error>>)>, Box<dyn Error>>
+    where
+        F: Fn(&'a R3Client, String) -> Fut,
+        Fut: Future<Output = Result<T, Box<dyn Error>>> + 'a,
+    {
+        if concurrency == 0 {
+            return Err(
+                R3Error::InvalidArgument("`concurrency` must not be zero".to_string()).into(),
+            );
+        }
+        let results = stream::iter(device_ids)
+            .map(|device_id| {
+                let future = f(self, device_id.clone());
+                async move { (device_id, future.await) }
+            })
+            .buffer_unordered(concurrency)
+            .collect()
+            .await;
+        Ok(results)
+    }
+
+    /// Check whether a device is online, without fetching the rest of the device.
+    ///
+    /// # Returns
+    /// The [`DeviceReachability`] of the device, including when it last reported to remote.it.
+    ///
+    /// # Errors
+    /// - [`R3Error::DeviceNotFound`] if there is no device with the given ID.
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn is_device_reachable_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+    ) -> Result<DeviceReachability, Box<dyn Error>> {
+        let request_body = GetDeviceReachability::build_query(get_device_reachability::Variables {
+            device_id: device_id.clone(),
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let device = response_data::<get_device_reachability::ResponseData>(response)?
+            .login
+            .and_then(|login| login.device)
+            .and_then(|devices| devices.into_iter().next())
+            .ok_or(R3Error::DeviceNotFound(device_id))?;
+        Ok(DeviceReachability::from(device))
+    }
+
+    /// Make sure a device is online, e.g. as a guard before [`R3Client::start_job_async()`].
+    ///
+    /// # Returns
+    /// The [`DeviceReachability`] of the device, if it is online.
+    ///
+    /// # Errors
+    /// - [`R3Error::DeviceOffline`] if the device is offline.
+    /// - Any error of [`R3Client::is_device_reachable_async()`].
+    #[builder]
+    pub async fn require_online_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+    ) -> Result<DeviceReachability, Box<dyn Error>> {
+        let reachability = self
+            .is_device_reachable_async()
+            .device_id(device_id)
+            .call()
+            .await?;
+        if !reachability.online {
+            return Err(R3Error::DeviceOffline(reachability.device_id).into());
+        }
+        Ok(reachability)
+    }
+
+    /// Get a list of online devices.
+    /// This is a shorthand for [`R3Client::get_devices_async()`] with the state [`DeviceState::Active`].
+    #[builder]
+    pub async fn get_online_devices_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: Some(DeviceState::Active.to_string()),
+            name: None,
+        });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Get a list of offline devices.
+    /// This is a shorthand for [`R3Client::get_devices_async()`] with the state [`DeviceState::Inactive`].
+    #[builder]
+    pub async fn get_offline_devices_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: Some(DeviceState::Inactive.to_string()),
+            name: None,
+        });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Search for devices by name.
+    /// This is a shorthand for [`R3Client::get_devices_async()`] with the `name` filter.
+    ///
+    /// The search is done server-side. The remote.it API returns devices whose name contains `query`, so this is a substring search.
+    #[builder]
+    pub async fn search_devices_async(
+        &self,
+        /// The text to search for in the device names.
+        query: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: None,
+            name: Some(query),
+        });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Find the ID of the device with the given name.
+    ///
+    /// The name has to match exactly. Devices, whose name only contains `name`, are ignored.
+    ///
+    /// # Errors
+    /// - [`R3Error::DeviceNameNotFound`] if there is no device with the given name.
+    /// - [`R3Error::AmbiguousDeviceName`] if there are multiple devices with the given name.
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn resolve_device_id_async(
+        &self,
+        /// The name of the device.
+        name: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<String, Box<dyn Error>> {
+        let search = self.get_devices_typed_async().name(name.clone());
+        let devices = match org_id {
+            Some(org_id) => search.org_id(org_id).call().await?,
+            None => search.call().await?,
+        };
+        Ok(unique_device_id(&name, devices)?)
+    }
+
+    /// Same as [`R3Client::resolve_device_id_async()`], but for multiple names.
+    ///
+    /// # Returns
+    /// The IDs of the devices, in the same order as `names`.
+    ///
+    /// # Errors
+    /// The first error of [`R3Client::resolve_device_id_async()`] for any of the names.
+    #[builder]
+    pub async fn resolve_device_ids_async(
+        &self,
+        /// The names of the devices.
+        names: Vec<String>,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut device_ids = Vec::with_capacity(names.len());
+        for name in names {
+            let resolve = self.resolve_device_id_async().name(name);
+            let device_id = match &org_id {
+                Some(org_id) => resolve.org_id(org_id.clone()).call().await?,
+                None => resolve.call().await?,
+            };
+            device_ids.push(device_id);
+        }
+        Ok(device_ids)
+    }
+
+    /// Get the tags of a device.
+    #[builder]
+    pub async fn get_device_tags_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Response<get_device_tags::ResponseData>, Box<dyn Error>> {
+        let request_body =
+            GetDeviceTags::build_query(get_device_tags::Variables { device_id, org_id });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Add a tag to a device.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `tag` is empty. No request is sent in this case.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn add_device_tag_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The tag to add. Tags, that don't exist yet, are created.
+        tag: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Response<add_device_tag::ResponseData>, Box<dyn Error>> {
+        if tag.trim().is_empty() {
+            return Err(R3Error::InvalidArgument("`tag` must not be empty".to_string()).into());
+        }
+        let request_body = AddDeviceTag::build_query(add_device_tag::Variables {
+            device_ids: vec![device_id],
+            tags: vec![tag],
+            org_id,
+        });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Remove a tag from a device.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `tag` is empty. No request is sent in this case.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn remove_device_tag_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The tag to remove.
+        tag: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Response<remove_device_tag::ResponseData>, Box<dyn Error>> {
+        if tag.trim().is_empty() {
+            return Err(R3Error::InvalidArgument("`tag` must not be empty".to_string()).into());
+        }
+        let request_body = RemoveDeviceTag::build_query(remove_device_tag::Variables {
+            device_ids: vec![device_id],
+            tags: vec![tag],
+            org_id,
+        });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Transfer a device to another user or organization.
+    ///
+    /// **This can not be undone.** After the transfer you lose access to the device, unless the new owner shares it with you.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn transfer_device_async(
+        &self,
+        /// The ID of the device to transfer.
+        device_id: String,
+        /// The new owner of the device.
+        target: TransferTarget,
+    ) -> Result<Response<transfer_device::ResponseData>, Box<dyn Error>> {
+        let email = match target {
+            TransferTarget::User { email } => email,
+            TransferTarget::Organization { owner_email } => owner_email,
+        };
+        let request_body =
+            TransferDevice::build_query(transfer_device::Variables { device_id, email });
+        self.send_remoteit_graphql_request_async(&request_body)
+            .await
+    }
+
+    /// Enable or disable a service. A disabled service can't be connected to.
+    ///
+    /// # Returns
+    /// The state of the service after the change.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the service does not exist.
+    /// - [`R3Error::NoData`] if the API did not return the service.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn set_service_enabled_async(
+        &self,
+        /// The ID of the service.
+        service_id: ServiceId,
+        /// Whether the service should be enabled.
+        enabled: bool,
+    ) -> Result<ServiceState, Box<dyn Error>> {
+        let request_body = SetServiceEnabled::build_query(set_service_enabled::Variables {
+            service_id: service_id.0,
+            enabled,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(
+            response_data::<set_service_enabled::ResponseData>(response)?
+                .update_service
+                .ok_or(R3Error::NoData)?,
+        )
+    }
+
+    /// Add a service to a device. The service is enabled right away.
+    ///
+    /// # Returns
+    /// The ID of the new service.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if there is no application type with the given ID.
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the device does not exist.
+    /// - [`R3Error::NoData`] if the API did not return the new service.
+    /// - Any error that occurs during the requests.
+    #[builder]
+    pub async fn create_service_async(
+        &self,
+        /// The ID of the device to add the service to.
+        device_id: String,
+        /// The ID of the application type of the service, e.g. `28` for SSH.
+        /// Use [`R3Client::get_application_types_async()`] to get the available application types.
+        application_type_id: i64,
+        /// The port of the service on the device.
+        port: u16,
+        /// Optional name of the service.
+        name: Option<String>,
+    ) -> Result<ServiceId, Box<dyn Error>> {
+        let application_types = self.get_application_types_async().call().await?;
+        let known = response_data(application_types)?
+            .application_types
+            .iter()
+            .any(|application_type| application_type.id == application_type_id);
+        if !known {
+            return Err(R3Error::InvalidArgument(format!(
+                "There is no application type with the ID `{application_type_id}`"
+            ))
+            .into());
+        }
+
+        let request_body = CreateService::build_query(create_service::Variables {
+            device_id,
+            application: application_type_id,
+            port: i64::from(port),
+            name,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let service = response_data::<create_service::ResponseData>(response)?
+            .add_service
+            .ok_or(R3Error::NoData)?;
+        Ok(ServiceId(service.id))
+    }
+
+    /// Remove a service from its device.
+    ///
+    /// **This can not be undone.**
+    ///
+    /// # Returns
+    /// Whether the service was removed.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the service does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn remove_service_async(
+        &self,
+        /// The ID of the service to remove.
+        service_id: ServiceId,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body = RemoveService::build_query(remove_service::Variables {
+            service_id: service_id.0,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(response_data::<remove_service::ResponseData>(response)?.remove_service)
+    }
+
+    /// Create a proxy connection to a service.
+    ///
+    /// # Returns
+    /// The connection, including the host and port or URL to connect to.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the service does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn create_connection_async(
+        &self,
+        /// The ID of the service to connect to.
+        service_id: ServiceId,
+        /// Options for the connection.
+        options: ConnectionOptions,
+    ) -> Result<Connection, Box<dyn Error>> {
+        let request_body = CreateConnection::build_query(create_connection::Variables {
+            service_id: service_id.0,
+            host_ip: options.host_ip,
+            proxy_type: options.proxy_type,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(response_data::<create_connection::ResponseData>(response)?.connect)
+    }
+
+    /// Get the active connections to services of your account, or the organization with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_connections_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<ActiveConnection>, Box<dyn Error>> {
+        let request_body = GetConnections::build_query(get_connections::Variables { org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let sessions = response_data::<get_connections::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .and_then(|account| account.sessions)
+            .unwrap_or_default();
+        Ok(sessions.into_iter().map(ActiveConnection::from).collect())
+    }
+
+    /// Close a connection to a service.
+    ///
+    /// # Returns
+    /// Whether the API reported the connection as closed.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn disconnect_async(
+        &self,
+        /// The ID of the connection.
+        /// You get this from [`R3Client::get_connections_async()`] or [`R3Client::create_connection_async()`].
+        connection_id: String,
+        /// Optional ID of the service the connection goes to.
+        service_id: Option<ServiceId>,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body = RemoveConnection::build_query(remove_connection::Variables {
+            connection_id,
+            service_id: service_id.map(|service_id| service_id.0),
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(response_data::<remove_connection::ResponseData>(response)?.disconnect)
+    }
+
+    /// Close all active connections, one after another. See [`R3Client::get_connections_async()`] and [`R3Client::disconnect_async()`].
+    ///
+    /// # Returns
+    /// The ID of each connection together with the result of closing it.
+    /// A failing disconnect does not stop the remaining connections from being closed.
+    ///
+    /// # Errors
+    /// - Any error that occurs while listing the connections. No connection is closed in this case.
+    #[builder]
+    pub async fn disconnect_all_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<(String, DisconnectResult)>, Box<dyn Error>> {
+        let connections = match org_id {
+            Some(org_id) => self.get_connections_async().org_id(org_id).call().await?,
+            None => self.get_connections_async().call().await?,
+        };
+        let mut results = Vec::with_capacity(connections.len());
+        for connection in connections {
+            let result = match connection.service_id {
+                Some(service_id) => {
+                    self.disconnect_async()
+                        .connection_id(connection.id.clone())
+                        .service_id(service_id)
+                        .call()
+                        .await
+                }
+                None => {
+                    self.disconnect_async()
+                        .connection_id(connection.id.clone())
+                        .call()
+                        .await
+                }
+            };
+            results.push((connection.id, result.map_err(into_send_error)));
+        }
+        Ok(results)
+    }
+
+    /// Get the event history of a device, like connects, disconnects and state changes.
+    ///
+    /// # Returns
+    /// A [`PagedResult`] with the events on the requested page, ordered from oldest to newest.
+    /// The API pages from the newest event, so `offset` 0 is the page with the most recent events.
+    /// Empty, if there is no device with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_device_events_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// Optional limit for the number of events to return.
+        limit: Option<i64>,
+        /// Optional offset for the events. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<PagedResult<DeviceEvent>, Box<dyn Error>> {
+        let request_body = GetDeviceEvents::build_query(get_device_events::Variables {
+            device_id,
+            limit,
+            offset,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let events = response_data::<get_device_events::ResponseData>(response)?
+            .login
+            .and_then(|login| login.device)
+            .and_then(|devices| devices.into_iter().next())
+            .map(|device| device.events);
+        Ok(match events {
+            Some(events) => {
+                let mut items: Vec<DeviceEvent> =
+                    events.items.into_iter().map(DeviceEvent::from).collect();
+                items.sort_by_key(|event| event.timestamp);
+                PagedResult {
+                    items,
+                    total: events.total,
+                    has_more: events.has_more,
+                    offset,
+                    limit,
+                }
+            }
+            None => PagedResult {
+                items: Vec::new(),
+                total: 0,
+                has_more: false,
+                offset,
+                limit,
+            },
+        })
+    }
+
+    /// Share a device with another user.
+    ///
+    /// # Returns
+    /// All users the device is shared with after the change.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the device does not exist.
+    /// - Any error that occurs during the requests.
+    #[allow(clippy::needless_pass_by_value)]
+    #[builder]
+    pub async fn share_device_async(
+        &self,
+        /// The ID of the device to share.
+        device_id: String,
+        /// The email of the user to share the device with.
+        email: String,
+        /// The permissions to grant. [`SharePermission::Connect`] is always granted.
+        #[builder(default)]
+        permissions: BTreeSet<SharePermission>,
+        /// Whether remote.it should notify the user by email.
+        #[builder(default)]
+        notify: bool,
+    ) -> Result<Vec<DeviceShare>, Box<dyn Error>> {
+        let request_body = ShareDevice::build_query(share_device::Variables {
+            device_id: device_id.clone(),
+            emails: vec![email],
+            scripting: permissions.contains(&SharePermission::Scripting),
+            notify,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        response_data::<share_device::ResponseData>(response)?;
+        self.get_device_shares_async()
+            .device_id(device_id)
+            .call()
+            .await
+    }
+
+    /// Revoke the access of another user to a device.
+    ///
+    /// The API identifies users by their email. You can look it up using [`R3Client::get_device_shares_async()`].
+    ///
+    /// # Returns
+    /// All users the device is shared with after the change.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the device does not exist.
+    /// - Any error that occurs during the requests.
+    #[builder]
+    pub async fn unshare_device_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The email of the user to revoke the access of.
+        email: String,
+    ) -> Result<Vec<DeviceShare>, Box<dyn Error>> {
+        let request_body = UnshareDevice::build_query(unshare_device::Variables {
+            device_id: device_id.clone(),
+            emails: vec![email],
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        response_data::<unshare_device::ResponseData>(response)?;
+        self.get_device_shares_async()
+            .device_id(device_id)
+            .call()
+            .await
+    }
+
+    /// Get the users a device is shared with, and their permissions on the device.
+    ///
+    /// # Returns
+    /// One [`DeviceShare`] per user. Empty, if the device is not shared or there is no device with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_device_shares_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+    ) -> Result<Vec<DeviceShare>, Box<dyn Error>> {
+        let request_body = GetDeviceShares::build_query(get_device_shares::Variables { device_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let access = response_data::<get_device_shares::ResponseData>(response)?
+            .login
+            .and_then(|login| login.device)
+            .and_then(|devices| devices.into_iter().next())
+            .map(|device| device.access)
+            .unwrap_or_default();
+        Ok(access.into_iter().map(DeviceShare::from).collect())
+    }
+
+    // endregion
+    // region Networks
+
+    /// Get the networks of your account, or the organization with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn get_networks_async(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<Network>, Box<dyn Error>> {
+        let request_body = GetNetworks::build_query(get_networks::Variables { org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let networks = response_data::<get_networks::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| account.networks)
+            .unwrap_or_default();
+        Ok(networks.into_iter().map(Network::from).collect())
+    }
+
+    /// Create a new, empty network.
+    ///
+    /// # Returns
+    /// The ID of the new network.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - [`R3Error::NoData`] if the API did not return the new network.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn create_network_async(
+        &self,
+        /// The name of the network.
+        name: String,
+        /// Optional organization ID. If set, the network is created in the organization instead of your own account.
+        org_id: Option<String>,
+    ) -> Result<NetworkId, Box<dyn Error>> {
+        let request_body = CreateNetwork::build_query(create_network::Variables { name, org_id });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        let network = response_data::<create_network::ResponseData>(response)?
+            .create_network
+            .ok_or(R3Error::NoData)?;
+        Ok(NetworkId(network.id))
+    }
+
+    /// Delete a network. The services in the network are not affected.
+    ///
+    /// # Returns
+    /// Whether the network was deleted.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the network does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn delete_network_async(
+        &self,
+        /// The ID of the network.
+        network_id: NetworkId,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body = DeleteNetwork::build_query(delete_network::Variables {
+            network_id: network_id.0,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(response_data::<delete_network::ResponseData>(response)?.delete_network)
+    }
+
+    /// Add a service to a network.
+    ///
+    /// # Returns
+    /// Whether the service was added.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the network or service does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn add_service_to_network_async(
+        &self,
+        /// The ID of the network.
+        network_id: NetworkId,
+        /// The ID of the service to add.
+        service_id: ServiceId,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body = AddServiceToNetwork::build_query(add_service_to_network::Variables {
+            network_id: network_id.0,
+            service_id: service_id.0,
+        });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(
+            response_data::<add_service_to_network::ResponseData>(response)?
+                .add_network_connection
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Remove a service from a network. The service itself is not affected.
+    ///
+    /// # Returns
+    /// Whether the service was removed.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the network or service does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub async fn remove_service_from_network_async(
+        &self,
+        /// The ID of the network.
+        network_id: NetworkId,
+        /// The ID of the service to remove.
+        service_id: ServiceId,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body =
+            RemoveServiceFromNetwork::build_query(remove_service_from_network::Variables {
+                network_id: network_id.0,
+                service_id: service_id.0,
+            });
+        let response = self
+            .send_remoteit_graphql_request_async(&request_body)
+            .await?;
+        Ok(
+            response_data::<remove_service_from_network::ResponseData>(response)?
+                .remove_network_connection
+                .unwrap_or_default(),
+        )
+    }
+
+    // endregion
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::Credentials;
+    use crate::{BackoffStrategy, RetryPolicy, GRAPHQL_PATH};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn get_credentials() -> Credentials {
+        Credentials::load_from_disk()
+            .custom_credentials_path(PathBuf::from(".env.remoteit"))
+            .call()
+            .unwrap()
+            .take_profile("default")
+            .unwrap()
+            .unwrap()
+    }
+
+    fn get_client() -> R3Client {
+        R3Client::builder().credentials(get_credentials()).build()
+    }
+
+    fn get_mock_client(server: &mockito::Server) -> R3Client {
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        R3Client::builder()
+            .credentials(credentials)
+            .base_url(server.url())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_get_files_async() {
+        let response = get_client().get_files_async().call().await.unwrap();
+        assert!(response.data.is_some());
+        assert!(response.errors.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_jobs_async() {
+        let response = get_client().get_jobs_async().limit(1).call().await.unwrap();
         assert!(response.data.is_some());
         assert!(response.errors.is_none());
     }
@@ -270,4 +2307,1029 @@ mod tests {
         assert!(response.data.is_some());
         assert!(response.errors.is_none());
     }
+
+    #[tokio::test]
+    async fn test_cloned_client_concurrent_calls_async() {
+        let client = get_client();
+        let cloned = client.clone();
+        let (first, second) = tokio::join!(
+            client.get_files_async().call(),
+            cloned.get_files_async().call()
+        );
+        assert!(first.unwrap().data.is_some());
+        assert!(second.unwrap().data.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_same_client_multiple_calls_async() {
+        let client = get_client();
+        let first = client.get_files_async().call().await.unwrap();
+        let second = client.get_files_async().call().await.unwrap();
+        assert!(first.data.is_some());
+        assert!(second.data.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_start_job_without_devices_async() {
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        let client = R3Client::builder().credentials(credentials).build();
+        let error = client
+            .start_job_async()
+            .file_id("file".to_string())
+            .device_ids(vec![])
+            .call()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_is_stable_across_retry_async() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header(IDEMPOTENCY_KEY_HEADER, "start-reboot-1")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let ok_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header(IDEMPOTENCY_KEY_HEADER, "start-reboot-1")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "startJob": "job-1" } }"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = R3Client::builder()
+            .credentials(get_mock_client(&server).credentials().clone())
+            .base_url(server.url())
+            .retry_policy(RetryPolicy {
+                max_retries: 1,
+                backoff: BackoffStrategy::Fixed(Duration::ZERO),
+            })
+            .build();
+
+        client
+            .start_job_async()
+            .file_id("file-1".to_string())
+            .device_ids(vec!["device-1".to_string()])
+            .idempotency_key("start-reboot-1".to_string())
+            .call()
+            .await
+            .unwrap();
+
+        unavailable_mock.assert_async().await;
+        ok_mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_start_job_bulk_async() {
+        let mut server = mockito::Server::new_async().await;
+        let ok_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "deviceIds": ["a", "b"] }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "startJob": "job-1" } }"#)
+            .create_async()
+            .await;
+        let error_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "deviceIds": ["c"] }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "errors": [{ "message": "Device not found" }] }"#)
+            .create_async()
+            .await;
+
+        let results = get_mock_client(&server)
+            .start_job_bulk_async()
+            .file_id("file".to_string())
+            .device_ids(["a", "b", "c"].map(String::from).to_vec())
+            .chunk_size(2)
+            .concurrency(2)
+            .call()
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), "job-1");
+        assert!(matches!(
+            results[1].as_ref().unwrap_err().downcast_ref::<R3Error>(),
+            Some(R3Error::GraphQl(_))
+        ));
+        ok_mock.assert_async().await;
+        error_mock.assert_async().await;
+    }
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[tokio::test]
+    async fn test_start_job_bulk_async_is_send() {
+        let server = mockito::Server::new_async().await;
+        let client = get_mock_client(&server);
+        let future = client
+            .start_job_bulk_async()
+            .file_id("file".to_string())
+            .device_ids(vec!["a".to_string()])
+            .chunk_size(1)
+            .call();
+        assert_send(&future);
+    }
+
+    #[tokio::test]
+    async fn test_delete_files_async_is_send() {
+        let server = mockito::Server::new_async().await;
+        let client = get_mock_client(&server);
+        let future = client
+            .delete_files_async()
+            .file_ids(vec!["file-1".to_string()])
+            .call();
+        assert_send(&future);
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_all_async_is_send() {
+        let server = mockito::Server::new_async().await;
+        let client = get_mock_client(&server);
+        let future = client.disconnect_all_async().call();
+        assert_send(&future);
+    }
+
+    #[tokio::test]
+    async fn test_get_devices_all_orgs_async_is_send() {
+        let server = mockito::Server::new_async().await;
+        let client = get_mock_client(&server);
+        let future = client
+            .get_devices_all_orgs_async()
+            .org_ids(vec!["org-1".to_string()])
+            .call();
+        assert_send(&future);
+    }
+
+    #[tokio::test]
+    async fn test_get_organization_members_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+            "data": {
+                "login": {
+                    "account": {
+                        "organization": {
+                            "id": "org-1",
+                            "name": "ACME",
+                            "members": [
+                                {
+                                    "user": { "id": "user-1", "email": "alice@example.com" },
+                                            "customRole": { "id": "role-1", "name": "Admin" },
+                                    "license": "LICENSED",
+                                    "created": "2024-01-01T00:00:00.000Z"
+                                },
+                                {
+                                    "user": { "id": "user-2", "email": "bob@example.com" },
+                                            "customRole": { "id": "role-2", "name": "Member" },
+                                    "license": "UNLICENSED",
+                                    "created": "2024-02-01T00:00:00.000Z"
+                                }
+                            ]
+                        }
+                    }
+                }
+            }
+        }"#,
+            )
+            .create_async()
+            .await;
+
+        let response = get_mock_client(&server)
+            .get_organization_members_async()
+            .call()
+            .await
+            .unwrap();
+
+        let members = response
+            .data
+            .unwrap()
+            .login
+            .unwrap()
+            .account
+            .unwrap()
+            .organization
+            .unwrap()
+            .members;
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[0].user.id, "user-1");
+        assert_eq!(members[0].custom_role.name, "Admin");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_remove_org_member_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "emails": ["bob@example.com"] }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "removeMember": true } }"#)
+            .create_async()
+            .await;
+
+        let response = get_mock_client(&server)
+            .remove_org_member_async()
+            .email("bob@example.com".to_string())
+            .call()
+            .await
+            .unwrap();
+
+        assert!(response.data.unwrap().remove_member);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_self_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "data": {
+                        "login": {
+                            "id": "user-1",
+                            "email": "alice@example.com",
+                            "created": "2024-01-01T00:00:00.000Z",
+                            "lastLogin": "2024-08-30T12:00:00.000Z",
+                            "language": "en",
+                            "timezone": null
+                        }
+                    }
+                }"#,
+            )
+            .create_async()
+            .await;
+
+        let response = get_mock_client(&server)
+            .get_self_async()
+            .call()
+            .await
+            .unwrap();
+
+        let login = response.data.unwrap().login.unwrap();
+        assert_eq!(login.id, "user-1");
+        assert_eq!(login.language.as_deref(), Some("en"));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_online_devices_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "state": "active" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "devices": { "hasMore": false, "total": 0, "items": [] } } } } }"#)
+            .create_async()
+            .await;
+
+        get_mock_client(&server)
+            .get_online_devices_async()
+            .call()
+            .await
+            .unwrap();
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_search_devices_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "name": "raspberry" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "devices": { "hasMore": false, "total": 0, "items": [] } } } } }"#)
+            .create_async()
+            .await;
+
+        get_mock_client(&server)
+            .search_devices_async()
+            .query("raspberry".to_string())
+            .call()
+            .await
+            .unwrap();
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_remove_device_tag_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "deviceIds": ["device-1"], "tags": ["production"] }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "removeTag": true } }"#)
+            .create_async()
+            .await;
+
+        let response = get_mock_client(&server)
+            .remove_device_tag_async()
+            .device_id("device-1".to_string())
+            .tag("production".to_string())
+            .call()
+            .await
+            .unwrap();
+
+        assert_eq!(response.data.unwrap().remove_tag, Some(true));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_add_device_tag_empty_async() {
+        let server = mockito::Server::new_async().await;
+        let error = get_mock_client(&server)
+            .add_device_tag_async()
+            .device_id("device-1".to_string())
+            .tag("  ".to_string())
+            .call()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_device_to_organization_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "TransferDevice",
+                "variables": { "deviceId": "device-1", "email": "owner@example.com" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "transfer": true } }"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let response = get_mock_client(&server)
+            .transfer_device_async()
+            .device_id("device-1".to_string())
+            .target(TransferTarget::Organization {
+                owner_email: "owner@example.com".to_string(),
+            })
+            .call()
+            .await
+            .unwrap();
+
+        assert!(response.data.unwrap().transfer);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_job_not_found_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "jobs": { "items": [] } } } } }"#)
+            .create_async()
+            .await;
+
+        let job = get_mock_client(&server)
+            .get_job_async()
+            .job_id("unknown".to_string())
+            .call()
+            .await
+            .unwrap();
+
+        assert!(job.is_none());
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_delete_files_async_preserves_order() {
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        for (file_id, body) in [
+            ("file-1", r#"{ "data": { "deleteFile": true } }"#),
+            (
+                "file-2",
+                r#"{ "data": null, "errors": [{ "message": "File not found" }] }"#,
+            ),
+            ("file-3", r#"{ "data": { "deleteFile": false } }"#),
+        ] {
+            mocks.push(
+                server
+                    .mock("POST", GRAPHQL_PATH)
+                    .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                        "variables": { "fileId": file_id }
+                    })))
+                    .with_header("content-type", "application/json")
+                    .with_body(body)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        let results = get_mock_client(&server)
+            .delete_files_async()
+            .file_ids(vec![
+                "file-1".to_string(),
+                "file-2".to_string(),
+                "file-3".to_string(),
+            ])
+            .concurrency(3)
+            .call()
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].as_ref().unwrap());
+        assert!(results[1].is_err());
+        assert!(!results[2].as_ref().unwrap());
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_no_content_response_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let response = get_mock_client(&server)
+            .delete_file_async()
+            .file_id("file-1".to_string())
+            .call()
+            .await
+            .unwrap();
+
+        assert!(response.data.is_none());
+        assert!(response.errors.is_none());
+        mock.assert_async().await;
+    }
+
+    fn running_job_body() -> &'static str {
+        r#"{
+            "data": {
+                "login": {
+                    "account": {
+                        "jobs": {
+                            "items": [{
+                                "id": "job-1",
+                                "owner": { "email": "alice@example.com" },
+                                "user": { "email": "alice@example.com" },
+                                "created": "2024-08-30T12:00:00.000Z",
+                                "updated": "2024-08-30T12:01:00.000Z",
+                                "status": "RUNNING",
+                                "fileVersion": { "id": "version-1", "file": { "id": "file-1", "name": "reboot.sh" } },
+                                "jobDevices": [],
+                                "arguments": []
+                            }]
+                        }
+                    }
+                }
+            }
+        }"#
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_job_with_cancel_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(running_job_body())
+            .expect_at_least(2)
+            .create_async()
+            .await;
+
+        let client = get_mock_client(&server);
+        let cancel_token = CancellationToken::new();
+        let waiting = client
+            .wait_for_job_with_cancel_async()
+            .job_id("job-1".to_string())
+            .poll_interval(Duration::from_millis(10))
+            .cancel_token(cancel_token.clone())
+            .call();
+
+        let cancel = async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            cancel_token.cancel();
+        };
+        let (result, ()) = tokio::join!(waiting, cancel);
+
+        let error = result.unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::Cancelled)
+        ));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_device_events_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "deviceId": "device-1", "limit": 2, "offset": 0 }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "login": { "device": [{ "events": {
+                    "total": 5,
+                    "hasMore": true,
+                    "items": [
+                        { "__typename": "DeviceConnectEvent", "id": "event-2", "type": "DEVICE_CONNECT", "timestamp": "2024-08-30T13:00:00.000Z", "state": null, "action": "disconnected", "actor": { "email": "alice@example.com" } },
+                        { "__typename": "DeviceStateEvent", "id": "event-1", "type": "DEVICE_STATE", "timestamp": "2024-08-30T12:00:00.000Z", "state": "active", "action": null, "actor": null }
+                    ]
+                } }] } } }"#,
+            )
+            .create_async()
+            .await;
+
+        let events = get_mock_client(&server)
+            .get_device_events_async()
+            .device_id("device-1".to_string())
+            .limit(2)
+            .offset(0)
+            .call()
+            .await
+            .unwrap();
+
+        assert_eq!(events.total, 5);
+        assert!(events.has_more);
+        let ids: Vec<&str> = events.items.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, vec!["event-1", "event-2"]);
+        assert_eq!(events.items[0].event_type, "DEVICE_STATE");
+        assert_eq!(events.items[0].state.as_deref(), Some("active"));
+        assert_eq!(events.items[1].actor.as_deref(), Some("alice@example.com"));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_get_device_shares_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "deviceId": "device-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "login": { "device": [{ "access": [
+                    { "created": "2024-08-30T12:00:00.000Z", "scripting": true, "user": { "id": "user-2", "email": "bob@example.com" } },
+                    { "created": null, "scripting": null, "user": { "id": "user-3", "email": null } }
+                ] }] } } }"#,
+            )
+            .create_async()
+            .await;
+
+        let shares = get_mock_client(&server)
+            .get_device_shares_async()
+            .device_id("device-1".to_string())
+            .call()
+            .await
+            .unwrap();
+
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[0].user_id, "user-2");
+        assert_eq!(shares[0].email.as_deref(), Some("bob@example.com"));
+        assert_eq!(
+            shares[0].permissions,
+            BTreeSet::from([SharePermission::Connect, SharePermission::Scripting])
+        );
+        assert!(shares[0].created.is_some());
+        assert_eq!(shares[1].email, None);
+        assert_eq!(
+            shares[1].permissions,
+            BTreeSet::from([SharePermission::Connect])
+        );
+        mock.assert_async().await;
+    }
+
+    fn devices_body(ids: &[&str]) -> String {
+        let items: Vec<serde_json::Value> = ids
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "id": id, "name": id, "online": true, "created": null, "state": "active",
+                    "lastReported": null, "platform": 1072, "version": null, "hardwareId": null,
+                    "access": [], "tags": [], "attributes": {}, "services": [],
+                    "categoryA": null, "categoryB": null, "categoryC": null, "categoryD": null, "categoryE": null
+                })
+            })
+            .collect();
+        serde_json::json!({ "data": { "login": { "account": { "devices": {
+            "hasMore": false, "total": ids.len(), "items": items
+        } } } } })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_get_devices_all_orgs_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mut mocks = Vec::new();
+        let orgs = [
+            ("org-1", vec!["device-1", "device-2"]),
+            ("org-2", vec!["device-3"]),
+        ];
+        for (org_id, ids) in orgs {
+            let mock = server
+                .mock("POST", GRAPHQL_PATH)
+                .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                    "operationName": "GetDevices",
+                    "variables": { "orgId": org_id }
+                })))
+                .with_header("content-type", "application/json")
+                .with_body(devices_body(&ids))
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+
+        let devices = get_mock_client(&server)
+            .get_devices_all_orgs_async()
+            .org_ids(vec!["org-1".to_string(), "org-2".to_string()])
+            .concurrency(2)
+            .call()
+            .await
+            .unwrap();
+
+        let tagged: Vec<(&str, &str)> = devices
+            .iter()
+            .map(|d| (d.org_id.as_str(), d.device.id.as_str()))
+            .collect();
+        assert_eq!(
+            tagged,
+            vec![
+                ("org-1", "device-1"),
+                ("org-1", "device-2"),
+                ("org-2", "device-3")
+            ]
+        );
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_for_each_device_async() {
+        let mut server = mockito::Server::new_async().await;
+        let device_ids = ["device-1", "device-2", "device-3", "device-4", "missing"];
+        let mut mocks = Vec::new();
+        for device_id in device_ids {
+            let devices = if device_id == "missing" {
+                serde_json::json!([])
+            } else {
+                serde_json::json!([{ "id": device_id, "online": true, "lastReported": null }])
+            };
+            let mock = server
+                .mock("POST", GRAPHQL_PATH)
+                .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                    "operationName": "GetDeviceReachability",
+                    "variables": { "deviceId": device_id }
+                })))
+                .with_header("content-type", "application/json")
+                .with_body(
+                    serde_json::json!({ "data": { "login": { "device": devices } } }).to_string(),
+                )
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+
+        let in_flight = std::sync::atomic::AtomicUsize::new(0);
+        let max_in_flight = std::sync::atomic::AtomicUsize::new(0);
+        let client = get_mock_client(&server);
+        let mut results = client
+            .for_each_device_async()
+            .device_ids(device_ids.map(String::from).to_vec())
+            .concurrency(2)
+            .f(|client, device_id| {
+                let (in_flight, max_in_flight) = (&in_flight, &max_in_flight);
+                async move {
+                    let running = in_flight.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(running, std::sync::atomic::Ordering::SeqCst);
+                    let result = client
+                        .is_device_reachable_async()
+                        .device_id(device_id)
+                        .call()
+                        .await;
+                    in_flight.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+                    result
+                }
+            })
+            .call()
+            .await
+            .unwrap();
+
+        results.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let ids: Vec<&str> = results.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, device_ids);
+        for (device_id, result) in &results[..4] {
+            assert_eq!(&result.as_ref().unwrap().device_id, device_id);
+        }
+        assert!(matches!(
+            results[4].1.as_ref().unwrap_err().downcast_ref::<R3Error>(),
+            Some(R3Error::DeviceNotFound(_))
+        ));
+        assert!(max_in_flight.load(std::sync::atomic::Ordering::SeqCst) <= 2);
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_devices_all_orgs_zero_concurrency_async() {
+        let server = mockito::Server::new_async().await;
+        let result = get_mock_client(&server)
+            .get_devices_all_orgs_async()
+            .org_ids(vec!["org-1".to_string()])
+            .concurrency(0)
+            .call()
+            .await;
+        assert!(matches!(
+            result.unwrap_err().downcast_ref::<R3Error>(),
+            Some(R3Error::InvalidArgument(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_remoteit_graphql_request_raw_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "id": "user-1" } } }"#)
+            .create_async()
+            .await;
+
+        let request_body = GetSelf::build_query(get_self::Variables {});
+        let response = get_mock_client(&server)
+            .send_remoteit_graphql_request_raw_async(&request_body)
+            .await
+            .unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.text().await.unwrap(),
+            r#"{ "data": { "login": { "id": "user-1" } } }"#
+        );
+        mock.assert_async().await;
+    }
+
+    fn jobs_body(ids: &[&str], last: Option<&str>, has_more: bool) -> String {
+        let items: Vec<serde_json::Value> = ids
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "id": id, "owner": { "email": "alice@example.com" }, "user": { "email": "alice@example.com" },
+                    "created": "2024-08-30T12:00:00.000Z", "updated": "2024-08-30T12:01:00.000Z",
+                    "status": "SUCCESS", "tags": [], "tagOperator": "ANY", "fileVersion": null,
+                    "jobDevices": [], "arguments": []
+                })
+            })
+            .collect();
+        serde_json::json!({ "data": { "login": { "account": { "jobs": {
+            "hasMore": has_more, "total": ids.len(), "last": last, "items": items
+        } } } } })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_get_jobs_stream_async() {
+        let mut server = mockito::Server::new_async().await;
+        let pages = [
+            (
+                serde_json::Value::Null,
+                jobs_body(&["job-1", "job-2"], Some("cursor-2"), true),
+            ),
+            (
+                serde_json::json!("cursor-2"),
+                jobs_body(&["job-3"], None, false),
+            ),
+        ];
+        let mut mocks = Vec::new();
+        for (after, body) in pages {
+            let mock = server
+                .mock("POST", GRAPHQL_PATH)
+                .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                    "operationName": "GetJobs",
+                    "variables": { "limit": 2, "after": after, "jobIds": ["job-1", "job-2", "job-3"] }
+                })))
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .expect(2)
+                .create_async()
+                .await;
+            mocks.push(mock);
+        }
+        let client = get_mock_client(&server);
+        let job_ids = vec![
+            "job-1".to_string(),
+            "job-2".to_string(),
+            "job-3".to_string(),
+        ];
+
+        let streamed: Vec<String> = client
+            .get_jobs_stream()
+            .page_size(2)
+            .job_id_filter(job_ids.clone())
+            .call()
+            .map_ok(|job| job.id)
+            .try_collect()
+            .await
+            .unwrap();
+        let collected = client
+            .get_all_jobs_async()
+            .page_size(2)
+            .job_id_filter(job_ids)
+            .call()
+            .await
+            .unwrap();
+
+        assert_eq!(streamed, ["job-1", "job-2", "job-3"]);
+        assert_eq!(collected.len(), 3);
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_and_rebuild_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "devices": { "total": 3 } } } } }"#)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = get_mock_client(&server);
+        assert_eq!(client.get_device_count_async().call().await.unwrap(), 3);
+        client.shutdown_async().await;
+
+        let client = get_mock_client(&server);
+        assert_eq!(client.get_device_count_async().call().await.unwrap(), 3);
+        client.shutdown_async().await;
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_with_timeout_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_chunked_body(|writer| {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+                writer.write_all(
+                    br#"{ "data": { "login": { "account": { "devices": { "total": 1 } } } } }"#,
+                )
+            })
+            .expect(2)
+            .create_async()
+            .await;
+        let client = get_mock_client(&server);
+
+        let error = client
+            .with_timeout(std::time::Duration::from_millis(100))
+            .get_device_count_async()
+            .call()
+            .await
+            .unwrap_err();
+        assert!(error
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(reqwest::Error::is_timeout));
+        assert_eq!(client.get_device_count_async().call().await.unwrap(), 1);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_shared_client_spawned_tasks_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(
+                serde_json::json!({ "operationName": "GetDeviceCount" }),
+            ))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "devices": { "total": 3 } } } } }"#)
+            .expect(8)
+            .create_async()
+            .await;
+        // The fan-out helpers only need to run to completion in a spawned task, so every other request fails.
+        let helpers_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_status(500)
+            .expect_at_least(4)
+            .create_async()
+            .await;
+        let client = get_mock_client(&server).into_shared();
+
+        let tasks: Vec<_> = (0..8)
+            .map(|_| {
+                let client = Arc::clone(&client);
+                tokio::spawn(async move {
+                    client
+                        .get_device_count_async()
+                        .call()
+                        .await
+                        .map_err(|e| e.to_string())
+                })
+            })
+            .collect();
+        for task in tasks {
+            assert_eq!(task.await.unwrap(), Ok(3));
+        }
+
+        let helpers = [
+            tokio::spawn({
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .start_job_bulk_async()
+                        .file_id("file-1".to_string())
+                        .device_ids(vec!["a".to_string(), "b".to_string()])
+                        .chunk_size(1)
+                        .call()
+                        .await
+                        .map(|results| results.len())
+                        .map_err(|e| e.to_string())
+                }
+            }),
+            tokio::spawn({
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .delete_files_async()
+                        .file_ids(vec!["file-1".to_string()])
+                        .call()
+                        .await
+                        .map(|results| results.len())
+                        .map_err(|e| e.to_string())
+                }
+            }),
+            tokio::spawn({
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .disconnect_all_async()
+                        .call()
+                        .await
+                        .map(|results| results.len())
+                        .map_err(|e| e.to_string())
+                }
+            }),
+            tokio::spawn({
+                let client = Arc::clone(&client);
+                async move {
+                    client
+                        .get_devices_all_orgs_async()
+                        .org_ids(vec!["org-1".to_string()])
+                        .call()
+                        .await
+                        .map(|devices| devices.len())
+                        .map_err(|e| e.to_string())
+                }
+            }),
+        ];
+        for task in helpers {
+            task.await.unwrap().ok();
+        }
+
+        assert_eq!(Arc::strong_count(&client), 1);
+        mock.assert_async().await;
+        helpers_mock.assert_async().await;
+    }
 }
@@ -4,14 +4,41 @@
 //!
 //! Please see [`R3Client`] for the actual functions you can call.
 
-use crate::auth::{build_auth_header, get_date};
-use crate::operations::{cancel_job, delete_file, delete_file_version, get_application_types, get_devices, get_files, get_jobs, get_organization_self_membership, get_owned_organization, start_job, CancelJob, DeleteFile, DeleteFileVersion, DeviceState, GetApplicationTypes, GetDevices, GetFiles, GetJobs, GetOrganizationSelfMembership, GetOwnedOrganization, StartJob};
-use crate::{R3Client, BASE_URL, GRAPHQL_PATH};
+use crate::auth::{build_auth_header, create_digest};
+use crate::operations::{
+    add_device_tag, add_service_to_network, cancel_job, create_connection, create_network,
+    create_service, delete_file, delete_file_version, delete_network, get_application_types,
+    get_audit_log, get_connections, get_device_count, get_device_events, get_device_reachability,
+    get_device_shares, get_device_tags, get_devices, get_file_versions, get_files, get_job,
+    get_job_results, get_job_summary, get_jobs, get_networks, get_organization_members,
+    get_organization_self_membership, get_owned_organization, get_plan, get_self,
+    invite_organization_member, remove_connection, remove_device_tag, remove_organization_member,
+    remove_service, remove_service_from_network, set_service_enabled, share_device, start_job,
+    transfer_device, unique_device_id, unshare_device, AccountPlan, ActiveConnection, AddDeviceTag,
+    AddServiceToNetwork, AuditLogEntry, CancelJob, Connection, ConnectionOptions, CreateConnection,
+    CreateNetwork, CreateService, DeleteFile, DeleteFileVersion, DeleteNetwork, Device,
+    DeviceEvent, DeviceReachability, DeviceShare, DeviceState, FileVersion, GetApplicationTypes,
+    GetAuditLog, GetConnections, GetDeviceCount, GetDeviceEvents, GetDeviceReachability,
+    GetDeviceShares, GetDeviceTags, GetDevices, GetFileVersions, GetFiles, GetJob, GetJobResults,
+    GetJobSummary, GetJobs, GetNetworks, GetOrganizationMembers, GetOrganizationSelfMembership,
+    GetOwnedOrganization, GetPlan, GetSelf, InviteOrganizationMember, Job, JobDeviceResult,
+    Network, NetworkId, OrgDevice, OrganizationRole, RemoveConnection, RemoveDeviceTag,
+    RemoveOrganizationMember, RemoveService, RemoveServiceFromNetwork, ServiceId, ServiceState,
+    SetServiceEnabled, ShareDevice, SharePermission, StartJob, TransferDevice, TransferTarget,
+    UnshareDevice,
+};
+use crate::paginator::{jobs_page, Page};
+use crate::retry::{is_retryable_error, is_retryable_status};
+use crate::{
+    batch_query, generate_idempotency_key, into_send_error, paginate, parse_graphql_response,
+    response_data, typed_response, DeleteFileResult, DisconnectResult, PageCursor, PagedResult,
+    Paginator, R3Client, R3Error, ResponseMeta, StartJobResult, IDEMPOTENCY_KEY_HEADER,
+};
 use bon::bon;
 use graphql_client::{GraphQLQuery, QueryBody, Response};
-use reqwest::blocking::Client;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::error::Error;
 
 /// Impl block for blocking API calls.
@@ -21,45 +48,476 @@ impl R3Client {
     ///
     /// You probably don't want to use this function directly, but rather use the other functions in this module like [`R3Client::get_files()`].
     ///
+    /// # Custom queries
+    /// This takes any [`QueryBody`], not only the ones of the queries in [`crate::operations`].
+    /// So you can define your own queries with [`graphql_client`], and use this crate to sign and send them:
+    /// ```
+    /// use graphql_client::{GraphQLQuery, QueryBody};
+    /// use serde::{Deserialize, Serialize};
+    /// # use remoteit_api::{Credentials, R3Client};
+    ///
+    /// // Usually generated with `#[derive(GraphQLQuery)]` from a `.graphql` file and the schema.
+    /// // Written by hand here, to keep the example self-contained.
+    /// struct GetDeviceName;
+    ///
+    /// #[derive(Serialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct Variables {
+    ///     device_id: String,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct ResponseData {
+    ///     login: Login,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Login {
+    ///     device: Vec<Device>,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Device {
+    ///     name: String,
+    /// }
+    ///
+    /// impl GraphQLQuery for GetDeviceName {
+    ///     type Variables = Variables;
+    ///     type ResponseData = ResponseData;
+    ///
+    ///     fn build_query(variables: Variables) -> QueryBody<Variables> {
+    ///         QueryBody {
+    ///             variables,
+    ///             query: "query GetDeviceName($deviceId: String!) { login { device(id: [$deviceId]) { name } } }",
+    ///             operation_name: "GetDeviceName",
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// # let mut server = mockito::Server::new();
+    /// # let mock = server
+    /// #     .mock("POST", remoteit_api::GRAPHQL_PATH)
+    /// #     .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+    /// #         "operationName": "GetDeviceName",
+    /// #         "variables": { "deviceId": "80:00:00:00:01:2A:3B:4C" }
+    /// #     })))
+    /// #     .with_body(r#"{ "data": { "login": { "device": [{ "name": "raspberrypi" }] } } }"#)
+    /// #     .create();
+    /// # let credentials = Credentials::builder()
+    /// #     .r3_access_key_id("foo")
+    /// #     .r3_secret_access_key("YmFy")
+    /// #     .build()?;
+    /// # let client = R3Client::builder().credentials(credentials).base_url(server.url()).build();
+    /// let request_body = GetDeviceName::build_query(Variables {
+    ///     device_id: "80:00:00:00:01:2A:3B:4C".to_string(),
+    /// });
+    /// let response = client.send_remoteit_graphql_request::<_, ResponseData>(&request_body)?;
+    /// assert_eq!(response.data.unwrap().login.device[0].name, "raspberrypi");
+    /// # mock.assert();
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
     /// # Errors
     /// - Any error that occurs during the request.
     /// - Any error that occurs during deserialization of the response.
+    ///   An empty body on a success status is not an error, but a response without data.
+    /// - [`R3Error::GraphQl`](crate::R3Error::GraphQl) if the response contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
     pub fn send_remoteit_graphql_request<V: Serialize, R: for<'a> Deserialize<'a>>(
         &self,
         query_body: &QueryBody<V>,
     ) -> Result<Response<R>, Box<dyn Error>> {
-        let date = get_date();
+        let (response, _) = self.send_remoteit_graphql_request_inner(query_body, None)?;
+        Ok(response)
+    }
+
+    /// Same as [`R3Client::send_remoteit_graphql_request()`], but also returns the [`ResponseMeta`] of the response.
+    /// Use this to read the request ID and the rate limit headers, e.g. to implement client-side throttling.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request.
+    /// - Any error that occurs during deserialization of the response.
+    /// - [`R3Error::GraphQl`](crate::R3Error::GraphQl) if the response contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+    pub fn send_remoteit_graphql_request_with_meta<V: Serialize, R: for<'a> Deserialize<'a>>(
+        &self,
+        query_body: &QueryBody<V>,
+    ) -> Result<(Response<R>, ResponseMeta), Box<dyn Error>> {
+        self.send_remoteit_graphql_request_inner(query_body, None)
+    }
+
+    /// Same as [`R3Client::send_remoteit_graphql_request()`], but returns the data as untyped JSON, regardless of the query.
+    /// Use [`ResponseExt::to_pretty_json`](crate::ResponseExt::to_pretty_json) to dump the response, e.g. for logging or as a test fixture.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request.
+    /// - Any error that occurs during deserialization of the response.
+    /// - [`R3Error::GraphQl`](crate::R3Error::GraphQl) if the response contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+    pub fn send_remoteit_graphql_request_value<V: Serialize>(
+        &self,
+        query_body: &QueryBody<V>,
+    ) -> Result<Response<serde_json::Value>, Box<dyn Error>> {
+        self.send_remoteit_graphql_request(query_body)
+    }
+
+    /// Sends a signed GraphQL request and returns the raw HTTP response, without deserializing it.
+    /// Use this if you need more control over the response than [`R3Client::send_remoteit_graphql_request()`] gives you,
+    /// e.g. to stream the body or to deserialize it into your own types.
+    ///
+    /// The caller is responsible for checking the status and for reading and parsing the body.
+    /// Like the other requests, transient failures are retried according to the [`RetryPolicy`](crate::RetryPolicy) of the client.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request. A response with an error status is not an error.
+    pub fn send_remoteit_graphql_request_raw<V: Serialize>(
+        &self,
+        query_body: &QueryBody<V>,
+    ) -> Result<reqwest::blocking::Response, Box<dyn Error>> {
+        self.post_graphql_with_retry(query_body, None)
+    }
+
+    /// Sends a signed GraphQL request, optionally with an idempotency key in the [`IDEMPOTENCY_KEY_HEADER`] header.
+    pub(crate) fn send_remoteit_graphql_request_inner<
+        B: Serialize + ?Sized,
+        R: for<'a> Deserialize<'a>,
+    >(
+        &self,
+        query_body: &B,
+        idempotency_key: Option<String>,
+    ) -> Result<(Response<R>, ResponseMeta), Box<dyn Error>> {
+        let response = self.post_graphql_with_retry(query_body, idempotency_key)?;
+        let meta = ResponseMeta::new(response.status(), response.headers());
+        let body = response.bytes()?;
+        let response = self.check_graphql_errors(parse_graphql_response(meta.status, &body)?)?;
+        Ok((response, meta))
+    }
+
+    /// Sends multiple GraphQL operations in a single HTTP request, using GraphQL request batching.
+    /// Use [`batch_query`](crate::batch_query) to turn the typed queries of [`crate::operations`] into untyped ones.
+    ///
+    /// Only batch queries, which read data (e.g. [`GetDevices`], [`GetJobs`], [`GetFiles`]).
+    /// Mutations (e.g. [`StartJob`], [`DeleteFile`]) should be sent on their own:
+    /// if the batch fails, you can't tell which of them were applied, and no idempotency key is sent for them.
+    ///
+    /// This requires the API gateway to accept a JSON array of operations. If it doesn't, an error is returned.
+    ///
+    /// # Returns
+    /// One response per operation, in the same order as `queries`. No request is sent, if `queries` is empty.
+    ///
+    /// Like the other requests, transient failures are retried according to the [`RetryPolicy`](crate::RetryPolicy) of the client.
+    ///
+    /// # Errors
+    /// - [`R3Error::BatchSizeMismatch`] if the API returns a different number of responses than operations were sent.
+    /// - [`R3Error::GraphQl`] if any of the responses contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+    /// - Any error that occurs during the request, including an error status of the response,
+    ///   e.g. if the API gateway doesn't accept batches.
+    /// - Any error that occurs during deserialization of the response.
+    pub fn send_batch(
+        &self,
+        queries: &[QueryBody<serde_json::Value>],
+    ) -> Result<Vec<Response<serde_json::Value>>, Box<dyn Error>> {
+        if queries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let response = self
+            .post_graphql_with_retry(queries, None)?
+            .error_for_status()?;
+        let body = response.bytes()?;
+        let responses: Vec<Response<serde_json::Value>> = serde_json::from_slice(&body)?;
+        if responses.len() != queries.len() {
+            return Err(R3Error::BatchSizeMismatch {
+                expected: queries.len(),
+                actual: responses.len(),
+            }
+            .into());
+        }
+        Ok(responses
+            .into_iter()
+            .map(|response| self.check_graphql_errors(response))
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Sends an arbitrary GraphQL query and returns the response as untyped JSON.
+    ///
+    /// This is an escape hatch for fields, which the queries in [`crate::operations`] don't select (yet).
+    /// For queries you send regularly, prefer a typed query with [`R3Client::send_remoteit_graphql_request()`].
+    ///
+    /// Like the other requests, transient failures are retried according to the [`RetryPolicy`](crate::RetryPolicy) of the client.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request.
+    /// - Any error that occurs during deserialization of the response.
+    /// - [`R3Error::GraphQl`] if the response contains GraphQL errors
+    ///   and the client was built with [`error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+    #[builder]
+    pub fn execute_query(
+        &self,
+        /// The GraphQL query document.
+        query: &str,
+        /// Optional variables for the query.
+        variables: Option<serde_json::Value>,
+        /// Optional name of the operation to execute, if `query` contains multiple operations.
+        operation_name: Option<&str>,
+    ) -> Result<Response<serde_json::Value>, Box<dyn Error>> {
+        let body = serde_json::json!({
+            "query": query,
+            "variables": variables.unwrap_or_else(|| serde_json::json!({})),
+            "operationName": operation_name,
+        });
+        self.send_remoteit_graphql_request_inner(&body, None)
+            .map(|(response, _)| response)
+    }
+
+    /// Get the devices and the jobs in a single HTTP request. See [`R3Client::send_batch()`].
+    ///
+    /// # Errors
+    /// - Any error that [`R3Client::send_batch()`] can return.
+    /// - Any error that occurs during deserialization of the individual responses.
+    #[builder]
+    pub fn get_devices_and_jobs(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        device_limit: Option<i64>,
+        /// Optional limit for the number of jobs to return.
+        job_limit: Option<i64>,
+    ) -> Result<
+        (
+            Response<get_devices::ResponseData>,
+            Response<get_jobs::ResponseData>,
+        ),
+        Box<dyn Error>,
+    > {
+        let devices = GetDevices::build_query(get_devices::Variables {
+            org_id: org_id.clone(),
+            limit: device_limit,
+            offset: None,
+            state: None,
+            name: None,
+        });
+        let jobs = GetJobs::build_query(get_jobs::Variables {
+            org_id,
+            limit: job_limit,
+            after: None,
+            job_ids: None,
+            statuses: None,
+        });
+        let mut responses = self
+            .send_batch(&[batch_query(&devices)?, batch_query(&jobs)?])?
+            .into_iter();
+        let (Some(devices), Some(jobs)) = (responses.next(), responses.next()) else {
+            unreachable!("send_batch returns exactly one response per query");
+        };
+        Ok((typed_response(devices)?, typed_response(jobs)?))
+    }
+
+    /// Measures the difference between the local clock and the clock of the remote.it API,
+    /// using the `Date` header of a lightweight request.
+    ///
+    /// The API rejects requests, if the signed `Date` header is too far off, which results in a cryptic `401`.
+    /// After calling this, all requests of this client and its clones are signed with the corrected time.
+    /// Call this once, e.g. on startup of a long-running process.
+    ///
+    /// # Returns
+    /// The measured clock skew (API time minus local time).
+    ///
+    /// # Errors
+    /// - [`R3Error::NoServerDate`] if the response has no valid `Date` header.
+    /// - Any error that occurs during the request.
+    pub fn check_clock_skew(&self) -> Result<chrono::Duration, Box<dyn Error>> {
+        let response =
+            self.post_graphql(&serde_json::json!({ "query": "{ __typename }" }), None)?;
+        Ok(self
+            .apply_server_date(response.headers())
+            .ok_or(R3Error::NoServerDate)?)
+    }
+
+    /// Signs and sends a JSON body to the GraphQL endpoint.
+    /// Same as `post_graphql`, but retries transient failures according to the [`RetryPolicy`](crate::RetryPolicy) of the client.
+    #[allow(clippy::needless_pass_by_value)]
+    fn post_graphql_with_retry<B: Serialize + ?Sized>(
+        &self,
+        body: &B,
+        idempotency_key: Option<String>,
+    ) -> Result<reqwest::blocking::Response, Box<dyn Error>> {
+        let mut retry = 0;
+        loop {
+            let result = self.post_graphql(body, idempotency_key.clone());
+            let retryable = match &result {
+                Ok(response) => {
+                    self.record_rate_limit(response.status(), response.headers());
+                    is_retryable_status(response.status())
+                }
+                Err(error) => is_retryable_error(error.as_ref()),
+            };
+            if !retryable || retry >= self.retry_policy.max_retries {
+                return result;
+            }
+            std::thread::sleep(self.retry_policy.backoff.delay(retry));
+            retry += 1;
+        }
+    }
+
+    /// Builds a signed request to the remote.it API, which is ready to be sent.
+    /// Use this to call parts of the API, that this crate doesn't wrap (yet).
+    ///
+    /// The `Date`, `Content-Type` and `Authorization` headers are set and signed the same way as for the requests of this crate.
+    /// If the client signs body digests (see [`R3Client::sign_body_digest`]) and there is a body, the `Digest` header is set and signed as well.
+    /// Don't change these headers, the method, the path or the body afterwards, or the signature becomes invalid.
+    ///
+    /// # Returns
+    /// A [`reqwest::blocking::RequestBuilder`] for the base URL of the client plus `path`.
+    #[builder]
+    pub fn signed_request(
+        &self,
+        /// The HTTP method of the request.
+        method: Method,
+        /// The path of the request, e.g. [`GRAPHQL_PATH`](crate::GRAPHQL_PATH). It is appended to the base URL and is part of the signature.
+        path: &str,
+        /// Optional body of the request.
+        body: Option<Vec<u8>>,
+        /// The content type of the request. It is signed even if there is no body. Defaults to `application/json`.
+        #[builder(default = "application/json")]
+        content_type: &str,
+    ) -> reqwest::blocking::RequestBuilder {
+        let digest = body
+            .as_deref()
+            .filter(|_| self.sign_body_digest)
+            .map(create_digest);
+        let date = self.signing_date();
         let auth_header = build_auth_header()
             .key_id(&self.credentials.r3_access_key_id)
-            .key(&self.credentials.key)
-            .content_type("application/json")
-            .method(&Method::POST)
-            .path(GRAPHQL_PATH)
+            .key(self.credentials.signing_key())
+            .content_type(content_type)
+            .method(&method)
+            .path(path)
             .date(&date)
-            .call();
-        let client = Client::new();
-        let response = client
-            .post(format!("{BASE_URL}{GRAPHQL_PATH}"))
+            .host(self.environment.host());
+        let auth_header = match &digest {
+            Some(digest) => auth_header.digest(digest).call(),
+            None => auth_header.call(),
+        };
+        let mut request = self
+            .blocking_http_client()
+            .request(method, format!("{}{}", self.base_url, path))
             .header("Date", date)
-            .header("Content-Type", "application/json")
-            .header("Authorization", auth_header)
-            .json(&query_body)
-            .send()?;
-        let response: Response<R> = response.json()?;
-        Ok(response)
+            .header("Content-Type", content_type)
+            .header("Authorization", auth_header);
+        if let Some(digest) = digest {
+            request = request.header("Digest", digest);
+        }
+        if let Some(body) = body {
+            request = request.body(body);
+        }
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        request
+    }
+
+    fn post_graphql<B: Serialize + ?Sized>(
+        &self,
+        body: &B,
+        idempotency_key: Option<String>,
+    ) -> Result<reqwest::blocking::Response, Box<dyn Error>> {
+        let mut request = self
+            .signed_request()
+            .method(Method::POST)
+            .path(&self.graphql_path)
+            .body(serde_json::to_vec(body)?)
+            .call();
+        if let Some(idempotency_key) = idempotency_key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, idempotency_key);
+        }
+        Ok(request.send()?)
     }
 
     // region Scripting
 
     /// Get a list of files that were uploaded to remote.it.
+    ///
+    /// Set `executable` to only get executable scripts (`true`), or only assets (`false`),
+    /// and `owner_id` to only get the files owned by the user with that ID.
+    /// The API supports neither filter, so all files are still fetched and then filtered on the client side.
     #[builder]
     pub fn get_files(
         &self,
         /// Optional organization ID for org context.
         org_id: Option<String>,
+        /// Optional filter for executable scripts or assets.
+        executable: Option<bool>,
+        /// Optional ID of the user, whose files to get.
+        owner_id: Option<String>,
     ) -> Result<Response<get_files::ResponseData>, Box<dyn Error>> {
         let request_body = GetFiles::build_query(get_files::Variables { org_id });
-        self.send_remoteit_graphql_request(&request_body)
+        let mut response: Response<get_files::ResponseData> =
+            self.send_remoteit_graphql_request(&request_body)?;
+        if let (Some(executable), Some(data)) = (executable, response.data.as_mut()) {
+            data.retain_executable(executable);
+        }
+        if let (Some(owner_id), Some(data)) = (owner_id, response.data.as_mut()) {
+            data.retain_owner(&owner_id);
+        }
+        Ok(response)
+    }
+
+    /// Get the files owned by the user with the given ID, e.g. to hide the files of other users in a shared organization.
+    /// Like the `owner_id` filter of [`R3Client::get_files()`], this filters on the client side.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn my_files(
+        &self,
+        /// The ID of the user, whose files to get.
+        user_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<get_files::GetFilesLoginAccountFiles>, Box<dyn Error>> {
+        let response = self
+            .get_files()
+            .maybe_org_id(org_id)
+            .owner_id(user_id)
+            .call()?;
+        Ok(response_data::<get_files::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| account.files)
+            .unwrap_or_default())
+    }
+
+    /// Get the versions of a single file.
+    ///
+    /// # Returns
+    /// The versions of the file, ordered from oldest to newest. Empty, if there is no file with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_file_versions(
+        &self,
+        /// The ID of the file.
+        /// You can get this from the response of [`R3Client::get_files()`].
+        file_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<FileVersion>, Box<dyn Error>> {
+        let request_body =
+            GetFileVersions::build_query(get_file_versions::Variables { file_id, org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let mut versions = response_data::<get_file_versions::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .and_then(|account| account.files.into_iter().next())
+            .map(|file| file.versions)
+            .unwrap_or_default();
+        versions.sort_by(|a, b| a.version.total_cmp(&b.version));
+        Ok(versions)
     }
 
     /// Delete a file from remote.it. Deletes all versions of the file.
@@ -69,9 +527,41 @@ impl R3Client {
         /// The ID of the file to delete.
         /// You can get this from the response of [`R3Client::get_files()`].
         file_id: String,
+        /// Optional idempotency key, sent in the [`IDEMPOTENCY_KEY_HEADER`] header.
+        /// Use the same key when retrying the request, so the API doesn't apply the mutation twice.
+        idempotency_key: Option<String>,
     ) -> Result<Response<delete_file::ResponseData>, Box<dyn Error>> {
         let request_body = DeleteFile::build_query(delete_file::Variables { file_id });
-        self.send_remoteit_graphql_request(&request_body)
+        self.send_remoteit_graphql_request_inner(&request_body, idempotency_key)
+            .map(|(response, _)| response)
+    }
+
+    /// Delete multiple files from remote.it. (All versions)
+    ///
+    /// The files are deleted one after another.
+    ///
+    /// # Returns
+    /// One result per file, in the same order as `file_ids`.
+    /// A failing delete does not stop the remaining files from being deleted.
+    /// Every delete is sent with a newly generated idempotency key.
+    #[builder]
+    pub fn delete_files(
+        &self,
+        /// The IDs of the files to delete.
+        /// You can get these from the response of [`R3Client::get_files()`].
+        file_ids: Vec<String>,
+    ) -> Vec<DeleteFileResult> {
+        file_ids
+            .into_iter()
+            .map(|file_id| {
+                self.delete_file()
+                    .file_id(file_id)
+                    .idempotency_key(generate_idempotency_key())
+                    .call()
+                    .and_then(|response| Ok(response_data(response)?.delete_file))
+                    .map_err(into_send_error)
+            })
+            .collect()
     }
 
     /// Delete a version of a file from remote.it. (Not the whole file)
@@ -81,13 +571,21 @@ impl R3Client {
         /// The ID of the file version to delete.
         /// You can get this from the response of [`R3Client::get_files()`].
         file_version_id: String,
+        /// Optional idempotency key, sent in the [`IDEMPOTENCY_KEY_HEADER`] header.
+        /// Use the same key when retrying the request, so the API doesn't apply the mutation twice.
+        idempotency_key: Option<String>,
     ) -> Result<Response<delete_file_version::ResponseData>, Box<dyn Error>> {
         let request_body =
             DeleteFileVersion::build_query(delete_file_version::Variables { file_version_id });
-        self.send_remoteit_graphql_request(&request_body)
+        self.send_remoteit_graphql_request_inner(&request_body, idempotency_key)
+            .map(|(response, _)| response)
     }
 
     /// Start scripting jobs on one or more devices.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `device_ids` is empty. No request is sent in this case.
+    /// - Any error that occurs during the request.
     #[builder]
     pub fn start_job(
         &self,
@@ -101,15 +599,82 @@ impl R3Client {
         /// Arguments to pass to the script.
         /// These are optional.
         /// For more information on script arguments please consult the remote.it API documentation.
+        /// Use [`start_job::ArgumentInput::from_pairs`] to create them from name/value pairs.
         #[builder(default)]
         arguments: Vec<start_job::ArgumentInput>,
+        /// Optional idempotency key, sent in the [`IDEMPOTENCY_KEY_HEADER`] header.
+        /// Use the same key when retrying the request, so the API doesn't apply the mutation twice.
+        idempotency_key: Option<String>,
     ) -> Result<Response<start_job::ResponseData>, Box<dyn Error>> {
+        if device_ids.is_empty() {
+            return Err(R3Error::InvalidArgument(
+                "`device_ids` must contain at least one device ID".to_string(),
+            )
+            .into());
+        }
         let request_body = StartJob::build_query(start_job::Variables {
             file_id,
             device_ids,
             arguments,
         });
-        self.send_remoteit_graphql_request(&request_body)
+        self.send_remoteit_graphql_request_inner(&request_body, idempotency_key)
+            .map(|(response, _)| response)
+    }
+
+    /// Start scripting jobs on many devices, by splitting the devices into chunks and starting one job per chunk.
+    ///
+    /// This is useful, when the number of devices is too large for a single [`R3Client::start_job()`] request.
+    ///
+    /// # Returns
+    /// One result per chunk, in the order of the chunks. Each successful result contains the ID of the started job.
+    /// A failing chunk does not stop the remaining chunks from being started.
+    /// Every chunk is sent with a newly generated idempotency key.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `device_ids` is empty or `chunk_size` is zero. No request is sent in this case.
+    #[allow(clippy::needless_pass_by_value)]
+    #[builder]
+    pub fn start_job_bulk(
+        &self,
+        /// The ID of the script file to run.
+        /// Note that this needs to be an executable file.
+        /// Get a list of files using [`R3Client::get_files()`].
+        file_id: String,
+        /// The IDs of the devices to run the script on.
+        /// Get a list of devices using [`R3Client::get_devices()`].
+        device_ids: Vec<String>,
+        /// The maximum number of devices per job.
+        chunk_size: usize,
+        /// Arguments to pass to the script. The same arguments are used for every chunk.
+        /// Use [`start_job::ArgumentInput::from_pairs`] to create them from name/value pairs.
+        #[builder(default)]
+        arguments: Vec<start_job::ArgumentInput>,
+    ) -> Result<Vec<StartJobResult>, Box<dyn Error>> {
+        if device_ids.is_empty() {
+            return Err(R3Error::InvalidArgument(
+                "`device_ids` must contain at least one device ID".to_string(),
+            )
+            .into());
+        }
+        if chunk_size == 0 {
+            return Err(
+                R3Error::InvalidArgument("`chunk_size` must not be zero".to_string()).into(),
+            );
+        }
+        let results = device_ids
+            .chunks(chunk_size)
+            .map(|chunk| {
+                self.start_job()
+                    .file_id(file_id.clone())
+                    .device_ids(chunk.to_vec())
+                    .arguments(arguments.clone())
+                    .idempotency_key(generate_idempotency_key())
+                    .call()
+                    .and_then(|response| Ok(response_data(response)?.start_job))
+                    .map_err(into_send_error)
+            })
+            .collect();
+        Ok(results)
     }
 
     /// Cancel a job. See remote.it docs on more information on when jobs can be cancelled.
@@ -119,9 +684,13 @@ impl R3Client {
         /// The ID of the job to cancel.
         /// You get this after starting a job using [`R3Client::start_job()`].
         job_id: String,
+        /// Optional idempotency key, sent in the [`IDEMPOTENCY_KEY_HEADER`] header.
+        /// Use the same key when retrying the request, so the API doesn't apply the mutation twice.
+        idempotency_key: Option<String>,
     ) -> Result<Response<cancel_job::ResponseData>, Box<dyn Error>> {
         let request_body = CancelJob::build_query(cancel_job::Variables { job_id });
-        self.send_remoteit_graphql_request(&request_body)
+        self.send_remoteit_graphql_request_inner(&request_body, idempotency_key)
+            .map(|(response, _)| response)
     }
 
     /// Get a list of jobs that were started on remote.it.
@@ -140,12 +709,172 @@ impl R3Client {
         let request_body = GetJobs::build_query(get_jobs::Variables {
             org_id,
             limit,
+            after: None,
             job_ids: job_id_filter,
             statuses: status_filter,
         });
         self.send_remoteit_graphql_request(&request_body)
     }
 
+    /// Pages through the jobs matching `variables`. The `after` cursor of `variables` is overwritten for each page.
+    fn paginate_jobs(
+        &self,
+        mut variables: get_jobs::Variables,
+    ) -> Paginator<'_, get_jobs::GetJobsLoginAccountJobsItems> {
+        Paginator::new(move |cursor| {
+            variables.after = match cursor {
+                Some(PageCursor::After(after)) => Some(after.clone()),
+                _ => None,
+            };
+            let request_body = GetJobs::build_query(variables.clone());
+            let response = self.send_remoteit_graphql_request(&request_body)?;
+            Ok(jobs_page(response_data(response)?))
+        })
+    }
+
+    /// Page through the jobs, see [`Paginator`]. The jobs are paged by cursor.
+    /// No request is sent until [`Paginator::next_page`] is called.
+    #[builder]
+    pub fn jobs_paginator(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// How many jobs to fetch per page.
+        #[builder(default = 100)]
+        page_size: i64,
+        /// Optional list of job IDs to filter by.
+        job_id_filter: Option<Vec<String>>,
+        /// Optional list of job statuses to filter by.
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Paginator<'_, get_jobs::GetJobsLoginAccountJobsItems> {
+        self.paginate_jobs(get_jobs::Variables {
+            org_id,
+            limit: Some(page_size),
+            after: None,
+            job_ids: job_id_filter,
+            statuses: status_filter,
+        })
+    }
+
+    /// Get all jobs, fetching page after page until there are no more.
+    /// Takes the same filters as [`R3Client::get_jobs()`]. Use [`R3Client::jobs_paginator()`] to process the jobs page by page instead.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the requests. The first error stops the remaining requests.
+    #[builder]
+    pub fn get_all_jobs(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// How many jobs to fetch per request.
+        #[builder(default = 100)]
+        page_size: i64,
+        /// Optional list of job IDs to filter by.
+        job_id_filter: Option<Vec<String>>,
+        /// Optional list of job statuses to filter by.
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Result<Vec<get_jobs::GetJobsLoginAccountJobsItems>, Box<dyn Error>> {
+        let mut paginator = self.paginate_jobs(get_jobs::Variables {
+            org_id,
+            limit: Some(page_size),
+            after: None,
+            job_ids: job_id_filter,
+            statuses: status_filter,
+        });
+        let mut jobs = Vec::new();
+        while let Some(page) = paginator.next_page()? {
+            jobs.extend(page);
+        }
+        Ok(jobs)
+    }
+
+    /// Count the jobs per status, e.g. to show "3 running, 10 succeeded, 1 failed" on a dashboard.
+    /// The jobs themselves are not fetched, the API only returns the number of jobs for each status.
+    ///
+    /// # Returns
+    /// The number of jobs for each status in [`all_job_statuses`](crate::operations::all_job_statuses).
+    /// Empty, if there is no account with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_job_summary(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<HashMap<get_jobs::JobStatusEnum, u64>, Box<dyn Error>> {
+        let request_body = GetJobSummary::build_query(get_job_summary::Variables { org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(response_data::<get_job_summary::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(get_job_summary::GetJobSummaryLoginAccount::into_counts)
+            .unwrap_or_default())
+    }
+
+    /// Get a single job, including the status of the job on each device.
+    ///
+    /// # Returns
+    /// - [`None`] if there is no job with the given ID.
+    /// - [`Some`] containing the job, if it exists.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_job(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job()`].
+        job_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Option<Job>, Box<dyn Error>> {
+        let request_body = GetJob::build_query(get_job::Variables { job_id, org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let job = response_data::<get_job::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .and_then(|account| account.jobs.items.into_iter().next());
+        Ok(job)
+    }
+
+    /// Get the result of a job on each device it ran on, including the exit code and output of the script.
+    ///
+    /// # Returns
+    /// One [`JobDeviceResult`] per device. Empty, if there is no job with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_job_results(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job()`].
+        job_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<JobDeviceResult>, Box<dyn Error>> {
+        let request_body =
+            GetJobResults::build_query(get_job_results::Variables { job_id, org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let results = response_data::<get_job_results::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .and_then(|account| account.jobs.items.into_iter().next())
+            .map(|job| {
+                job.job_devices
+                    .into_iter()
+                    .map(JobDeviceResult::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(results)
+    }
+
     // endregion
     // region Organizations
     /// Get data on your own organization, which belongs to the current user.
@@ -174,101 +903,4147 @@ impl R3Client {
         );
         self.send_remoteit_graphql_request(&request_body)
     }
-    // endregion
-    // region Devices and Services
+    /// Get the members of an organization.
+    ///
+    /// The remote.it API returns all members at once, so `limit` and `offset` are applied client-side.
+    ///
+    /// # Returns
+    /// The organization with its members. Each member contains the user ID, email and role.
+    #[builder]
+    pub fn get_organization_members(
+        &self,
+        /// Optional organization ID. If omitted, your own organization is used.
+        org_id: Option<String>,
+        /// Optional limit for the number of members to return.
+        limit: Option<i64>,
+        /// Optional offset for the members. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_organization_members::ResponseData>, Box<dyn Error>> {
+        let request_body =
+            GetOrganizationMembers::build_query(get_organization_members::Variables { org_id });
+        let mut response: Response<get_organization_members::ResponseData> =
+            self.send_remoteit_graphql_request(&request_body)?;
+        if let Some(organization) = response
+            .data
+            .as_mut()
+            .and_then(|data| data.login.as_mut())
+            .and_then(|login| login.account.as_mut())
+            .and_then(|account| account.organization.as_mut())
+        {
+            paginate(&mut organization.members, limit, offset);
+        }
+        Ok(response)
+    }
 
-    /// Get a list of application types that are available on remote.it.
+    /// Invite a user to an organization, or change the role of an existing member.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `role` is [`OrganizationRole::Unknown`]. No request is sent in this case.
+    /// - Any error that occurs during the request.
     #[builder]
-    pub fn get_application_types(
+    pub fn invite_org_member(
         &self,
-    ) -> Result<Response<get_application_types::ResponseData>, Box<dyn Error>> {
-        let request_body = GetApplicationTypes::build_query(get_application_types::Variables {});
+        /// Optional organization ID. If omitted, your own organization is used.
+        org_id: Option<String>,
+        /// The email address of the user to invite.
+        email: String,
+        /// The role of the user within the organization.
+        role: OrganizationRole,
+    ) -> Result<Response<invite_organization_member::ResponseData>, Box<dyn Error>> {
+        let role = invite_organization_member::OrganizationRole::try_from(role)?;
+        let request_body =
+            InviteOrganizationMember::build_query(invite_organization_member::Variables {
+                org_id,
+                emails: vec![email],
+                role: Some(role),
+            });
         self.send_remoteit_graphql_request(&request_body)
     }
 
-    /// Get a list of devices.
+    /// Remove a member from an organization.
+    /// The remote.it API identifies members by their email address.
     #[builder]
-    pub fn get_devices(
+    pub fn remove_org_member(
         &self,
-        /// Optional organization ID for org context.
+        /// Optional organization ID. If omitted, your own organization is used.
         org_id: Option<String>,
-        /// Optional limit for the number of devices to return.
-        limit: Option<i64>,
-        /// Optional offset for the devices. Useful for pagination.
-        offset: Option<i64>,
-        /// Optional state to filter by.
-        state: Option<DeviceState>
-    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
-        let request_body = GetDevices::build_query(get_devices::Variables {
+        /// The email address of the member to remove.
+        email: String,
+    ) -> Result<Response<remove_organization_member::ResponseData>, Box<dyn Error>> {
+        let request_body =
+            RemoveOrganizationMember::build_query(remove_organization_member::Variables {
+                org_id,
+                emails: vec![email],
+            });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    // endregion
+    // region Account
+
+    /// Get the account of the currently authenticated user, i.e. the owner of the credentials.
+    ///
+    /// # Returns
+    /// The ID, email and basic profile fields of your account.
+    #[builder]
+    pub fn get_self(&self) -> Result<Response<get_self::ResponseData>, Box<dyn Error>> {
+        let request_body = GetSelf::build_query(get_self::Variables {});
+        self.send_remoteit_graphql_request(&request_body)
+    }
+    /// Get the audit log of your account, or the organization with the given ID.
+    ///
+    /// # Returns
+    /// A [`PagedResult`] with the entries on the requested page, ordered from oldest to newest.
+    /// The API pages from the newest entry, so `offset` 0 is the page with the most recent entries.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_audit_log(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of entries to return.
+        limit: Option<i64>,
+        /// Optional offset for the entries. Useful for pagination.
+        offset: Option<i64>,
+        /// Optionally only return entries from this point in time on.
+        since: Option<chrono::DateTime<chrono::Local>>,
+        /// Optionally only return entries up to this point in time.
+        until: Option<chrono::DateTime<chrono::Local>>,
+    ) -> Result<PagedResult<AuditLogEntry>, Box<dyn Error>> {
+        let request_body = GetAuditLog::build_query(get_audit_log::Variables {
+            org_id,
+            limit,
+            offset,
+            min_date: since,
+            max_date: until,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let events = response_data::<get_audit_log::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| account.events);
+        Ok(match events {
+            Some(events) => {
+                let mut items: Vec<AuditLogEntry> =
+                    events.items.into_iter().map(AuditLogEntry::from).collect();
+                items.sort_by_key(|entry| entry.timestamp);
+                PagedResult {
+                    items,
+                    total: events.total,
+                    has_more: events.has_more,
+                    offset,
+                    limit,
+                }
+            }
+            None => PagedResult {
+                items: Vec::new(),
+                total: 0,
+                has_more: false,
+                offset,
+                limit,
+            },
+        })
+    }
+
+    /// Get the plan of your account, or the organization with the given ID, and how many devices it uses.
+    ///
+    /// # Returns
+    /// The [`AccountPlan`], or `None` if there is no account with the given ID.
+    /// Free accounts have no license, so their plan ID and name are `None`.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_plan(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Option<AccountPlan>, Box<dyn Error>> {
+        let request_body = GetPlan::build_query(get_plan::Variables { org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(response_data::<get_plan::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(AccountPlan::from))
+    }
+
+    // endregion
+    // region Devices and Services
+
+    /// Get a list of application types that are available on remote.it.
+    #[builder]
+    pub fn get_application_types(
+        &self,
+    ) -> Result<Response<get_application_types::ResponseData>, Box<dyn Error>> {
+        let request_body = GetApplicationTypes::build_query(get_application_types::Variables {});
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Get a list of devices.
+    #[builder]
+    pub fn get_devices(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        /// The filter is applied server-side. The remote.it API returns devices whose name contains the given text.
+        name: Option<String>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
             org_id,
             limit,
             offset,
             state: state.map(|s| s.to_string()),
+            name,
+        });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Get the number of devices, without transferring the device data.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_device_count(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<u64, Box<dyn Error>> {
+        let request_body = GetDeviceCount::build_query(get_device_count::Variables { org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let total = response_data::<get_device_count::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map_or(0, |account| account.devices.total);
+        Ok(u64::try_from(total).unwrap_or(0))
+    }
+
+    /// Get a page of devices, together with the total number of devices matching the filters.
+    /// Takes the same filters as [`R3Client::get_devices()`].
+    ///
+    /// # Returns
+    /// A [`PagedResult`] with the devices on the requested page. Use [`PagedResult::total`] to show e.g. "showing 20 of 340".
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_devices_paged(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        name: Option<String>,
+    ) -> Result<PagedResult<get_devices::GetDevicesLoginAccountDevicesItems>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: state.map(|s| s.to_string()),
+            name,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let devices = response_data::<get_devices::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| account.devices);
+        Ok(match devices {
+            Some(devices) => PagedResult {
+                items: devices.items,
+                total: devices.total,
+                has_more: devices.has_more,
+                offset,
+                limit,
+            },
+            None => PagedResult {
+                items: Vec::new(),
+                total: 0,
+                has_more: false,
+                offset,
+                limit,
+            },
+        })
+    }
+
+    /// Get a list of devices as flat [`Device`] structs, instead of the nested generated response.
+    /// Takes the same filters as [`R3Client::get_devices()`].
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_devices_typed(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        name: Option<String>,
+    ) -> Result<Vec<Device>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: state.map(|s| s.to_string()),
+            name,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let devices = response_data::<get_devices::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| {
+                account
+                    .devices
+                    .items
+                    .into_iter()
+                    .map(Device::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(devices)
+    }
+
+    /// Page through the devices as flat [`Device`] structs, see [`Paginator`]. The devices are paged by offset.
+    /// No request is sent until [`Paginator::next_page`] is called.
+    #[builder]
+    pub fn devices_paginator(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// How many devices to fetch per page.
+        #[builder(default = 100)]
+        page_size: i64,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+    ) -> Paginator<'_, Device> {
+        Paginator::new(move |cursor| {
+            let offset = match cursor {
+                Some(PageCursor::Offset(offset)) => *offset,
+                _ => 0,
+            };
+            let request_body = GetDevices::build_query(get_devices::Variables {
+                org_id: org_id.clone(),
+                limit: Some(page_size),
+                offset: Some(offset),
+                state: state.map(|s| s.to_string()),
+                name: None,
+            });
+            let response = self.send_remoteit_graphql_request(&request_body)?;
+            let devices = response_data::<get_devices::ResponseData>(response)?
+                .login
+                .and_then(|login| login.account)
+                .map(|account| account.devices);
+            Ok(match devices {
+                Some(devices) => Page {
+                    next: (devices.has_more && !devices.items.is_empty()).then(|| {
+                        let fetched = i64::try_from(devices.items.len()).unwrap_or(i64::MAX);
+                        PageCursor::Offset(offset.saturating_add(fetched))
+                    }),
+                    items: devices.items.into_iter().map(Device::from).collect(),
+                },
+                None => Page {
+                    items: Vec::new(),
+                    next: None,
+                },
+            })
+        })
+    }
+
+    /// Get the devices of multiple organizations as one list.
+    /// The organizations are queried one after another, see [`R3Client::get_devices_all_orgs_async()`] for concurrent requests.
+    ///
+    /// # Returns
+    /// The devices of all organizations, in the order of `org_ids`, each tagged with the organization it was listed in.
+    /// A device shared with multiple of the organizations is listed once per organization.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors for any of the organizations.
+    /// - Any error that occurs during the requests. The first error stops the remaining requests.
+    #[builder]
+    pub fn get_devices_all_orgs(
+        &self,
+        /// The IDs of the organizations to list the devices of.
+        org_ids: Vec<String>,
+    ) -> Result<Vec<OrgDevice>, Box<dyn Error>> {
+        let mut devices = Vec::new();
+        for org_id in org_ids {
+            let org_devices = self.get_devices_typed().org_id(org_id.clone()).call()?;
+            devices.extend(org_devices.into_iter().map(|device| OrgDevice {
+                org_id: org_id.clone(),
+                device,
+            }));
+        }
+        Ok(devices)
+    }
+
+    /// Check whether a device is online, without fetching the rest of the device.
+    ///
+    /// # Returns
+    /// The [`DeviceReachability`] of the device, including when it last reported to remote.it.
+    ///
+    /// # Errors
+    /// - [`R3Error::DeviceNotFound`] if there is no device with the given ID.
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn is_device_reachable(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+    ) -> Result<DeviceReachability, Box<dyn Error>> {
+        let request_body = GetDeviceReachability::build_query(get_device_reachability::Variables {
+            device_id: device_id.clone(),
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let device = response_data::<get_device_reachability::ResponseData>(response)?
+            .login
+            .and_then(|login| login.device)
+            .and_then(|devices| devices.into_iter().next())
+            .ok_or(R3Error::DeviceNotFound(device_id))?;
+        Ok(DeviceReachability::from(device))
+    }
+
+    /// Make sure a device is online, e.g. as a guard before [`R3Client::start_job()`].
+    ///
+    /// # Returns
+    /// The [`DeviceReachability`] of the device, if it is online.
+    ///
+    /// # Errors
+    /// - [`R3Error::DeviceOffline`] if the device is offline.
+    /// - Any error of [`R3Client::is_device_reachable()`].
+    #[builder]
+    pub fn require_online(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+    ) -> Result<DeviceReachability, Box<dyn Error>> {
+        let reachability = self.is_device_reachable().device_id(device_id).call()?;
+        if !reachability.online {
+            return Err(R3Error::DeviceOffline(reachability.device_id).into());
+        }
+        Ok(reachability)
+    }
+
+    /// Get a list of online devices.
+    /// This is a shorthand for [`R3Client::get_devices()`] with the state [`DeviceState::Active`].
+    #[builder]
+    pub fn get_online_devices(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: Some(DeviceState::Active.to_string()),
+            name: None,
+        });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Get a list of offline devices.
+    /// This is a shorthand for [`R3Client::get_devices()`] with the state [`DeviceState::Inactive`].
+    #[builder]
+    pub fn get_offline_devices(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: Some(DeviceState::Inactive.to_string()),
+            name: None,
+        });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Search for devices by name.
+    /// This is a shorthand for [`R3Client::get_devices()`] with the `name` filter.
+    ///
+    /// The search is done server-side. The remote.it API returns devices whose name contains `query`, so this is a substring search.
+    #[builder]
+    pub fn search_devices(
+        &self,
+        /// The text to search for in the device names.
+        query: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        let request_body = GetDevices::build_query(get_devices::Variables {
+            org_id,
+            limit,
+            offset,
+            state: None,
+            name: Some(query),
+        });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Find the ID of the device with the given name.
+    ///
+    /// The name has to match exactly. Devices, whose name only contains `name`, are ignored.
+    ///
+    /// # Errors
+    /// - [`R3Error::DeviceNameNotFound`] if there is no device with the given name.
+    /// - [`R3Error::AmbiguousDeviceName`] if there are multiple devices with the given name.
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[allow(clippy::needless_pass_by_value)]
+    #[builder]
+    pub fn resolve_device_id(
+        &self,
+        /// The name of the device.
+        name: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<String, Box<dyn Error>> {
+        let search = self.get_devices_typed().name(name.clone());
+        let devices = match org_id {
+            Some(org_id) => search.org_id(org_id).call()?,
+            None => search.call()?,
+        };
+        Ok(unique_device_id(&name, devices)?)
+    }
+
+    /// Same as [`R3Client::resolve_device_id()`], but for multiple names.
+    ///
+    /// # Returns
+    /// The IDs of the devices, in the same order as `names`.
+    ///
+    /// # Errors
+    /// The first error of [`R3Client::resolve_device_id()`] for any of the names.
+    #[allow(clippy::needless_pass_by_value)]
+    #[builder]
+    pub fn resolve_device_ids(
+        &self,
+        /// The names of the devices.
+        names: Vec<String>,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut device_ids = Vec::with_capacity(names.len());
+        for name in names {
+            let resolve = self.resolve_device_id().name(name);
+            let device_id = match &org_id {
+                Some(org_id) => resolve.org_id(org_id.clone()).call()?,
+                None => resolve.call()?,
+            };
+            device_ids.push(device_id);
+        }
+        Ok(device_ids)
+    }
+
+    /// Get the tags of a device.
+    #[builder]
+    pub fn get_device_tags(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Response<get_device_tags::ResponseData>, Box<dyn Error>> {
+        let request_body =
+            GetDeviceTags::build_query(get_device_tags::Variables { device_id, org_id });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Add a tag to a device.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `tag` is empty. No request is sent in this case.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn add_device_tag(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The tag to add. Tags, that don't exist yet, are created.
+        tag: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Response<add_device_tag::ResponseData>, Box<dyn Error>> {
+        if tag.trim().is_empty() {
+            return Err(R3Error::InvalidArgument("`tag` must not be empty".to_string()).into());
+        }
+        let request_body = AddDeviceTag::build_query(add_device_tag::Variables {
+            device_ids: vec![device_id],
+            tags: vec![tag],
+            org_id,
+        });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Remove a tag from a device.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `tag` is empty. No request is sent in this case.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn remove_device_tag(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The tag to remove.
+        tag: String,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Response<remove_device_tag::ResponseData>, Box<dyn Error>> {
+        if tag.trim().is_empty() {
+            return Err(R3Error::InvalidArgument("`tag` must not be empty".to_string()).into());
+        }
+        let request_body = RemoveDeviceTag::build_query(remove_device_tag::Variables {
+            device_ids: vec![device_id],
+            tags: vec![tag],
+            org_id,
+        });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Transfer a device to another user or organization.
+    ///
+    /// **This can not be undone.** After the transfer you lose access to the device, unless the new owner shares it with you.
+    ///
+    /// # Errors
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn transfer_device(
+        &self,
+        /// The ID of the device to transfer.
+        device_id: String,
+        /// The new owner of the device.
+        target: TransferTarget,
+    ) -> Result<Response<transfer_device::ResponseData>, Box<dyn Error>> {
+        let email = match target {
+            TransferTarget::User { email } => email,
+            TransferTarget::Organization { owner_email } => owner_email,
+        };
+        let request_body =
+            TransferDevice::build_query(transfer_device::Variables { device_id, email });
+        self.send_remoteit_graphql_request(&request_body)
+    }
+
+    /// Enable or disable a service. A disabled service can't be connected to.
+    ///
+    /// # Returns
+    /// The state of the service after the change.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the service does not exist.
+    /// - [`R3Error::NoData`] if the API did not return the service.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn set_service_enabled(
+        &self,
+        /// The ID of the service.
+        service_id: ServiceId,
+        /// Whether the service should be enabled.
+        enabled: bool,
+    ) -> Result<ServiceState, Box<dyn Error>> {
+        let request_body = SetServiceEnabled::build_query(set_service_enabled::Variables {
+            service_id: service_id.0,
+            enabled,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(
+            response_data::<set_service_enabled::ResponseData>(response)?
+                .update_service
+                .ok_or(R3Error::NoData)?,
+        )
+    }
+
+    /// Add a service to a device. The service is enabled right away.
+    ///
+    /// # Returns
+    /// The ID of the new service.
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if there is no application type with the given ID.
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the device does not exist.
+    /// - [`R3Error::NoData`] if the API did not return the new service.
+    /// - Any error that occurs during the requests.
+    #[builder]
+    pub fn create_service(
+        &self,
+        /// The ID of the device to add the service to.
+        device_id: String,
+        /// The ID of the application type of the service, e.g. `28` for SSH.
+        /// Use [`R3Client::get_application_types()`] to get the available application types.
+        application_type_id: i64,
+        /// The port of the service on the device.
+        port: u16,
+        /// Optional name of the service.
+        name: Option<String>,
+    ) -> Result<ServiceId, Box<dyn Error>> {
+        let application_types = self.get_application_types().call()?;
+        let known = response_data(application_types)?
+            .application_types
+            .iter()
+            .any(|application_type| application_type.id == application_type_id);
+        if !known {
+            return Err(R3Error::InvalidArgument(format!(
+                "There is no application type with the ID `{application_type_id}`"
+            ))
+            .into());
+        }
+
+        let request_body = CreateService::build_query(create_service::Variables {
+            device_id,
+            application: application_type_id,
+            port: i64::from(port),
+            name,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let service = response_data::<create_service::ResponseData>(response)?
+            .add_service
+            .ok_or(R3Error::NoData)?;
+        Ok(ServiceId(service.id))
+    }
+
+    /// Remove a service from its device.
+    ///
+    /// **This can not be undone.**
+    ///
+    /// # Returns
+    /// Whether the service was removed.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the service does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn remove_service(
+        &self,
+        /// The ID of the service to remove.
+        service_id: ServiceId,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body = RemoveService::build_query(remove_service::Variables {
+            service_id: service_id.0,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(response_data::<remove_service::ResponseData>(response)?.remove_service)
+    }
+
+    /// Create a proxy connection to a service.
+    ///
+    /// # Returns
+    /// The connection, including the host and port or URL to connect to.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the service does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn create_connection(
+        &self,
+        /// The ID of the service to connect to.
+        service_id: ServiceId,
+        /// Options for the connection.
+        options: ConnectionOptions,
+    ) -> Result<Connection, Box<dyn Error>> {
+        let request_body = CreateConnection::build_query(create_connection::Variables {
+            service_id: service_id.0,
+            host_ip: options.host_ip,
+            proxy_type: options.proxy_type,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(response_data::<create_connection::ResponseData>(response)?.connect)
+    }
+
+    /// Get the active connections to services of your account, or the organization with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_connections(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<ActiveConnection>, Box<dyn Error>> {
+        let request_body = GetConnections::build_query(get_connections::Variables { org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let sessions = response_data::<get_connections::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .and_then(|account| account.sessions)
+            .unwrap_or_default();
+        Ok(sessions.into_iter().map(ActiveConnection::from).collect())
+    }
+
+    /// Close a connection to a service.
+    ///
+    /// # Returns
+    /// Whether the API reported the connection as closed.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn disconnect(
+        &self,
+        /// The ID of the connection.
+        /// You get this from [`R3Client::get_connections()`] or [`R3Client::create_connection()`].
+        connection_id: String,
+        /// Optional ID of the service the connection goes to.
+        service_id: Option<ServiceId>,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body = RemoveConnection::build_query(remove_connection::Variables {
+            connection_id,
+            service_id: service_id.map(|service_id| service_id.0),
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(response_data::<remove_connection::ResponseData>(response)?.disconnect)
+    }
+
+    /// Close all active connections, one after another. See [`R3Client::get_connections()`] and [`R3Client::disconnect()`].
+    ///
+    /// # Returns
+    /// The ID of each connection together with the result of closing it.
+    /// A failing disconnect does not stop the remaining connections from being closed.
+    ///
+    /// # Errors
+    /// - Any error that occurs while listing the connections. No connection is closed in this case.
+    #[builder]
+    pub fn disconnect_all(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<(String, DisconnectResult)>, Box<dyn Error>> {
+        let connections = match org_id {
+            Some(org_id) => self.get_connections().org_id(org_id).call()?,
+            None => self.get_connections().call()?,
+        };
+        let mut results = Vec::with_capacity(connections.len());
+        for connection in connections {
+            let result = match connection.service_id {
+                Some(service_id) => self
+                    .disconnect()
+                    .connection_id(connection.id.clone())
+                    .service_id(service_id)
+                    .call(),
+                None => self
+                    .disconnect()
+                    .connection_id(connection.id.clone())
+                    .call(),
+            };
+            results.push((connection.id, result.map_err(into_send_error)));
+        }
+        Ok(results)
+    }
+
+    /// Get the event history of a device, like connects, disconnects and state changes.
+    ///
+    /// # Returns
+    /// A [`PagedResult`] with the events on the requested page, ordered from oldest to newest.
+    /// The API pages from the newest event, so `offset` 0 is the page with the most recent events.
+    /// Empty, if there is no device with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_device_events(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// Optional limit for the number of events to return.
+        limit: Option<i64>,
+        /// Optional offset for the events. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<PagedResult<DeviceEvent>, Box<dyn Error>> {
+        let request_body = GetDeviceEvents::build_query(get_device_events::Variables {
+            device_id,
+            limit,
+            offset,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let events = response_data::<get_device_events::ResponseData>(response)?
+            .login
+            .and_then(|login| login.device)
+            .and_then(|devices| devices.into_iter().next())
+            .map(|device| device.events);
+        Ok(match events {
+            Some(events) => {
+                let mut items: Vec<DeviceEvent> =
+                    events.items.into_iter().map(DeviceEvent::from).collect();
+                items.sort_by_key(|event| event.timestamp);
+                PagedResult {
+                    items,
+                    total: events.total,
+                    has_more: events.has_more,
+                    offset,
+                    limit,
+                }
+            }
+            None => PagedResult {
+                items: Vec::new(),
+                total: 0,
+                has_more: false,
+                offset,
+                limit,
+            },
+        })
+    }
+
+    /// Share a device with another user.
+    ///
+    /// # Returns
+    /// All users the device is shared with after the change.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the device does not exist.
+    /// - Any error that occurs during the requests.
+    #[allow(clippy::needless_pass_by_value)]
+    #[builder]
+    pub fn share_device(
+        &self,
+        /// The ID of the device to share.
+        device_id: String,
+        /// The email of the user to share the device with.
+        email: String,
+        /// The permissions to grant. [`SharePermission::Connect`] is always granted.
+        #[builder(default)]
+        permissions: BTreeSet<SharePermission>,
+        /// Whether remote.it should notify the user by email.
+        #[builder(default)]
+        notify: bool,
+    ) -> Result<Vec<DeviceShare>, Box<dyn Error>> {
+        let request_body = ShareDevice::build_query(share_device::Variables {
+            device_id: device_id.clone(),
+            emails: vec![email],
+            scripting: permissions.contains(&SharePermission::Scripting),
+            notify,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        response_data::<share_device::ResponseData>(response)?;
+        self.get_device_shares().device_id(device_id).call()
+    }
+
+    /// Revoke the access of another user to a device.
+    ///
+    /// The API identifies users by their email. You can look it up using [`R3Client::get_device_shares()`].
+    ///
+    /// # Returns
+    /// All users the device is shared with after the change.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the device does not exist.
+    /// - Any error that occurs during the requests.
+    #[builder]
+    pub fn unshare_device(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The email of the user to revoke the access of.
+        email: String,
+    ) -> Result<Vec<DeviceShare>, Box<dyn Error>> {
+        let request_body = UnshareDevice::build_query(unshare_device::Variables {
+            device_id: device_id.clone(),
+            emails: vec![email],
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        response_data::<unshare_device::ResponseData>(response)?;
+        self.get_device_shares().device_id(device_id).call()
+    }
+
+    /// Get the users a device is shared with, and their permissions on the device.
+    ///
+    /// # Returns
+    /// One [`DeviceShare`] per user. Empty, if the device is not shared or there is no device with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_device_shares(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+    ) -> Result<Vec<DeviceShare>, Box<dyn Error>> {
+        let request_body = GetDeviceShares::build_query(get_device_shares::Variables { device_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let access = response_data::<get_device_shares::ResponseData>(response)?
+            .login
+            .and_then(|login| login.device)
+            .and_then(|devices| devices.into_iter().next())
+            .map(|device| device.access)
+            .unwrap_or_default();
+        Ok(access.into_iter().map(DeviceShare::from).collect())
+    }
+
+    // endregion
+    // region Networks
+
+    /// Get the networks of your account, or the organization with the given ID.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn get_networks(
+        &self,
+        /// Optional organization ID for org context.
+        org_id: Option<String>,
+    ) -> Result<Vec<Network>, Box<dyn Error>> {
+        let request_body = GetNetworks::build_query(get_networks::Variables { org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let networks = response_data::<get_networks::ResponseData>(response)?
+            .login
+            .and_then(|login| login.account)
+            .map(|account| account.networks)
+            .unwrap_or_default();
+        Ok(networks.into_iter().map(Network::from).collect())
+    }
+
+    /// Create a new, empty network.
+    ///
+    /// # Returns
+    /// The ID of the new network.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors.
+    /// - [`R3Error::NoData`] if the API did not return the new network.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn create_network(
+        &self,
+        /// The name of the network.
+        name: String,
+        /// Optional organization ID. If set, the network is created in the organization instead of your own account.
+        org_id: Option<String>,
+    ) -> Result<NetworkId, Box<dyn Error>> {
+        let request_body = CreateNetwork::build_query(create_network::Variables { name, org_id });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        let network = response_data::<create_network::ResponseData>(response)?
+            .create_network
+            .ok_or(R3Error::NoData)?;
+        Ok(NetworkId(network.id))
+    }
+
+    /// Delete a network. The services in the network are not affected.
+    ///
+    /// # Returns
+    /// Whether the network was deleted.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the network does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn delete_network(
+        &self,
+        /// The ID of the network.
+        network_id: NetworkId,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body = DeleteNetwork::build_query(delete_network::Variables {
+            network_id: network_id.0,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(response_data::<delete_network::ResponseData>(response)?.delete_network)
+    }
+
+    /// Add a service to a network.
+    ///
+    /// # Returns
+    /// Whether the service was added.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the network or service does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn add_service_to_network(
+        &self,
+        /// The ID of the network.
+        network_id: NetworkId,
+        /// The ID of the service to add.
+        service_id: ServiceId,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body = AddServiceToNetwork::build_query(add_service_to_network::Variables {
+            network_id: network_id.0,
+            service_id: service_id.0,
+        });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(
+            response_data::<add_service_to_network::ResponseData>(response)?
+                .add_network_connection
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Remove a service from a network. The service itself is not affected.
+    ///
+    /// # Returns
+    /// Whether the service was removed.
+    ///
+    /// # Errors
+    /// - [`R3Error::GraphQl`] if the API returns errors, e.g. if the network or service does not exist.
+    /// - Any error that occurs during the request.
+    #[builder]
+    pub fn remove_service_from_network(
+        &self,
+        /// The ID of the network.
+        network_id: NetworkId,
+        /// The ID of the service to remove.
+        service_id: ServiceId,
+    ) -> Result<bool, Box<dyn Error>> {
+        let request_body =
+            RemoveServiceFromNetwork::build_query(remove_service_from_network::Variables {
+                network_id: network_id.0,
+                service_id: service_id.0,
+            });
+        let response = self.send_remoteit_graphql_request(&request_body)?;
+        Ok(
+            response_data::<remove_service_from_network::ResponseData>(response)?
+                .remove_network_connection
+                .unwrap_or_default(),
+        )
+    }
+
+    // endregion
+}
+
+/// The most common blocking API calls, as a trait.
+///
+/// [`R3Client`] implements this trait, so your application can depend on `&dyn R3Api` (or a generic `impl R3Api`)
+/// instead of the concrete client, and use a fake implementation in tests.
+/// The trait is object safe, so it can also be mocked with crates like `mockall`.
+///
+/// The methods take all parameters directly instead of using builders.
+/// On [`R3Client`] itself, the builder methods of the same name take precedence. Call e.g. `R3Api::get_devices(&client, ...)` to use the trait method.
+pub trait R3Api {
+    /// See [`R3Client::get_files()`].
+    ///
+    /// # Errors
+    /// Any error that occurs during the request.
+    fn get_files(
+        &self,
+        org_id: Option<String>,
+    ) -> Result<Response<get_files::ResponseData>, Box<dyn Error>>;
+
+    /// See [`R3Client::start_job()`].
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if `device_ids` is empty.
+    /// - Any error that occurs during the request.
+    fn start_job(
+        &self,
+        file_id: String,
+        device_ids: Vec<String>,
+        arguments: Vec<start_job::ArgumentInput>,
+    ) -> Result<Response<start_job::ResponseData>, Box<dyn Error>>;
+
+    /// See [`R3Client::cancel_job()`].
+    ///
+    /// # Errors
+    /// Any error that occurs during the request.
+    fn cancel_job(
+        &self,
+        job_id: String,
+    ) -> Result<Response<cancel_job::ResponseData>, Box<dyn Error>>;
+
+    /// See [`R3Client::get_jobs()`].
+    ///
+    /// # Errors
+    /// Any error that occurs during the request.
+    fn get_jobs(
+        &self,
+        org_id: Option<String>,
+        limit: Option<i64>,
+        job_id_filter: Option<Vec<String>>,
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Result<Response<get_jobs::ResponseData>, Box<dyn Error>>;
+
+    /// See [`R3Client::get_devices()`].
+    ///
+    /// # Errors
+    /// Any error that occurs during the request.
+    fn get_devices(
+        &self,
+        org_id: Option<String>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        state: Option<DeviceState>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>>;
+}
+
+impl R3Api for R3Client {
+    fn get_files(
+        &self,
+        org_id: Option<String>,
+    ) -> Result<Response<get_files::ResponseData>, Box<dyn Error>> {
+        self.get_files().maybe_org_id(org_id).call()
+    }
+
+    fn start_job(
+        &self,
+        file_id: String,
+        device_ids: Vec<String>,
+        arguments: Vec<start_job::ArgumentInput>,
+    ) -> Result<Response<start_job::ResponseData>, Box<dyn Error>> {
+        self.start_job()
+            .file_id(file_id)
+            .device_ids(device_ids)
+            .arguments(arguments)
+            .call()
+    }
+
+    fn cancel_job(
+        &self,
+        job_id: String,
+    ) -> Result<Response<cancel_job::ResponseData>, Box<dyn Error>> {
+        self.cancel_job().job_id(job_id).call()
+    }
+
+    fn get_jobs(
+        &self,
+        org_id: Option<String>,
+        limit: Option<i64>,
+        job_id_filter: Option<Vec<String>>,
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Result<Response<get_jobs::ResponseData>, Box<dyn Error>> {
+        self.get_jobs()
+            .maybe_org_id(org_id)
+            .maybe_limit(limit)
+            .maybe_job_id_filter(job_id_filter)
+            .maybe_status_filter(status_filter)
+            .call()
+    }
+
+    fn get_devices(
+        &self,
+        org_id: Option<String>,
+        limit: Option<i64>,
+        offset: Option<i64>,
+        state: Option<DeviceState>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.get_devices()
+            .maybe_org_id(org_id)
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .maybe_state(state)
+            .call()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::Credentials;
+    use crate::GRAPHQL_PATH;
+    use crate::{
+        BackoffStrategy, Environment, RateLimitInfo, ResponseExt, RetryPolicy, BASE_URL,
+        STAGING_BASE_URL,
+    };
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn get_credentials() -> Credentials {
+        Credentials::load_from_disk()
+            .custom_credentials_path(PathBuf::from(".env.remoteit"))
+            .call()
+            .unwrap()
+            .take_profile("default")
+            .unwrap()
+            .unwrap()
+    }
+
+    fn get_client() -> R3Client {
+        R3Client::builder().credentials(get_credentials()).build()
+    }
+
+    fn get_mock_client(server: &mockito::Server) -> R3Client {
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        R3Client::builder()
+            .credentials(credentials)
+            .base_url(server.url())
+            .build()
+    }
+
+    #[test]
+    fn test_get_files() {
+        let response = get_client().get_files().call().unwrap();
+        assert!(response.data.is_some());
+        assert!(response.errors.is_none());
+    }
+
+    #[test]
+    fn test_get_jobs() {
+        let response = get_client().get_jobs().limit(1).call().unwrap();
+        assert!(response.data.is_some());
+        assert!(response.errors.is_none());
+    }
+
+    #[test]
+    fn test_get_jobs_with_filters() {
+        let response = get_client()
+            .get_jobs()
+            .job_id_filter(vec!["foobar".to_string()])
+            .status_filter(vec![get_jobs::JobStatusEnum::SUCCESS])
+            .call()
+            .unwrap();
+        assert!(response.data.is_some());
+        assert!(response.errors.is_none());
+    }
+
+    #[test]
+    fn test_get_application_types() {
+        let response = get_client().get_application_types().call().unwrap();
+        dbg!(&response);
+        assert!(response.data.is_some());
+        assert!(response.errors.is_none());
+    }
+
+    #[test]
+    fn test_get_devices() {
+        let response = get_client().get_devices().call().unwrap();
+        assert!(response.data.is_some());
+        assert!(response.errors.is_none());
+    }
+
+    #[test]
+    fn test_start_job_without_devices() {
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        let client = R3Client::builder().credentials(credentials).build();
+        let error = client
+            .start_job()
+            .file_id("file".to_string())
+            .device_ids(vec![])
+            .call()
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_start_job_bulk() {
+        let mut server = mockito::Server::new();
+        let mocks: Vec<_> = [
+            (vec!["a", "b"], "job-1"),
+            (vec!["c", "d"], "job-2"),
+            (vec!["e"], "job-3"),
+        ]
+        .into_iter()
+        .map(|(device_ids, job_id)| {
+            server
+                .mock("POST", GRAPHQL_PATH)
+                .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                    "variables": { "deviceIds": device_ids }
+                })))
+                .with_header("content-type", "application/json")
+                .with_body(serde_json::json!({ "data": { "startJob": job_id } }).to_string())
+                .create()
+        })
+        .collect();
+
+        let results = get_mock_client(&server)
+            .start_job_bulk()
+            .file_id("file".to_string())
+            .device_ids(["a", "b", "c", "d", "e"].map(String::from).to_vec())
+            .chunk_size(2)
+            .call()
+            .unwrap();
+
+        let job_ids: Vec<String> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(job_ids, ["job-1", "job-2", "job-3"]);
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_get_organization_members() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+            "data": {
+                "login": {
+                    "account": {
+                        "organization": {
+                            "id": "org-1",
+                            "name": "ACME",
+                            "members": [
+                                {
+                                    "user": { "id": "user-1", "email": "alice@example.com" },
+                                            "customRole": { "id": "role-1", "name": "Admin" },
+                                    "license": "LICENSED",
+                                    "created": "2024-01-01T00:00:00.000Z"
+                                },
+                                {
+                                    "user": { "id": "user-2", "email": "bob@example.com" },
+                                            "customRole": { "id": "role-2", "name": "Member" },
+                                    "license": "UNLICENSED",
+                                    "created": "2024-02-01T00:00:00.000Z"
+                                }
+                            ]
+                        }
+                    }
+                }
+            }
+        }"#,
+            )
+            .create();
+
+        let response = get_mock_client(&server)
+            .get_organization_members()
+            .org_id("org-1".to_string())
+            .offset(1)
+            .limit(1)
+            .call()
+            .unwrap();
+
+        let members = response
+            .data
+            .unwrap()
+            .login
+            .unwrap()
+            .account
+            .unwrap()
+            .organization
+            .unwrap()
+            .members;
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].user.id, "user-2");
+        assert_eq!(members[0].user.email.as_deref(), Some("bob@example.com"));
+        assert_eq!(members[0].custom_role.name, "Member");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_invite_org_member() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "orgId": "org-1", "emails": ["bob@example.com"], "role": "ADMIN" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "setMember": true } }"#)
+            .create();
+
+        let response = get_mock_client(&server)
+            .invite_org_member()
+            .org_id("org-1".to_string())
+            .email("bob@example.com".to_string())
+            .role(OrganizationRole::Admin)
+            .call()
+            .unwrap();
+
+        assert!(response.data.unwrap().set_member);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_invite_org_member_invalid_role() {
+        let server = mockito::Server::new();
+        let error = get_mock_client(&server)
+            .invite_org_member()
+            .email("bob@example.com".to_string())
+            .role(OrganizationRole::Unknown("OWNER".to_string()))
+            .call()
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_self() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "data": {
+                        "login": {
+                            "id": "user-1",
+                            "email": "alice@example.com",
+                            "created": "2024-01-01T00:00:00.000Z",
+                            "lastLogin": "2024-08-30T12:00:00.000Z",
+                            "language": "en",
+                            "timezone": null
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let response = get_mock_client(&server).get_self().call().unwrap();
+
+        let login = response.data.unwrap().login.unwrap();
+        assert_eq!(login.id, "user-1");
+        assert_eq!(login.email.as_deref(), Some("alice@example.com"));
+        assert!(login.last_login.is_some());
+        assert!(login.timezone.is_none());
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_devices_state_filter() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "state": "inactive" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "devices": { "hasMore": false, "total": 0, "items": [] } } } } }"#)
+            .expect(2)
+            .create();
+
+        let client = get_mock_client(&server);
+        client
+            .get_devices()
+            .state(DeviceState::Inactive)
+            .call()
+            .unwrap();
+        client.get_offline_devices().call().unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_devices_typed_state_filter() {
+        let mut server = mockito::Server::new();
+        let mut mocks = Vec::new();
+        for (state, online, ids) in [
+            ("active", true, ["device-1", "device-2"]),
+            ("inactive", false, ["device-3", "device-4"]),
+        ] {
+            let mut body: serde_json::Value = serde_json::from_str(&devices_body(&ids)).unwrap();
+            for device in body["data"]["login"]["account"]["devices"]["items"]
+                .as_array_mut()
+                .unwrap()
+            {
+                device["state"] = state.into();
+                device["online"] = online.into();
+            }
+            mocks.push(mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "state": state }),
+                &body.to_string(),
+            ));
+        }
+        let client = get_mock_client(&server);
+
+        for (state, ids) in [
+            (DeviceState::Active, ["device-1", "device-2"]),
+            (DeviceState::Inactive, ["device-3", "device-4"]),
+        ] {
+            let devices = client.get_devices_typed().state(state).call().unwrap();
+            assert_eq!(
+                devices.iter().map(|d| d.id.as_str()).collect::<Vec<_>>(),
+                ids
+            );
+            assert!(devices.iter().all(
+                |d| d.state == state.to_string() && d.online == (state == DeviceState::Active)
+            ));
+        }
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_search_devices() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "name": "raspberry" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "devices": { "hasMore": false, "total": 0, "items": [] } } } } }"#)
+            .expect(2)
+            .create();
+
+        let client = get_mock_client(&server);
+        client
+            .get_devices()
+            .name("raspberry".to_string())
+            .call()
+            .unwrap();
+        client
+            .search_devices()
+            .query("raspberry".to_string())
+            .call()
+            .unwrap();
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_device_tags() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "deviceId": "device-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "data": {
+                        "login": {
+                            "device": [{
+                                "id": "device-1",
+                                "tags": [{ "name": "production", "color": 3, "created": "2024-01-01T00:00:00.000Z" }]
+                            }]
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let response = get_mock_client(&server)
+            .get_device_tags()
+            .device_id("device-1".to_string())
+            .call()
+            .unwrap();
+
+        let devices = response.data.unwrap().login.unwrap().device.unwrap();
+        assert_eq!(devices[0].tags[0].name, "production");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_add_device_tag() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "deviceIds": ["device-1"], "tags": ["production"] }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "addTag": true } }"#)
+            .create();
+
+        let response = get_mock_client(&server)
+            .add_device_tag()
+            .device_id("device-1".to_string())
+            .tag("production".to_string())
+            .call()
+            .unwrap();
+
+        assert_eq!(response.data.unwrap().add_tag, Some(true));
+        mock.assert();
+    }
+
+    #[test]
+    fn test_transfer_device_to_user() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "TransferDevice",
+                "variables": { "deviceId": "device-1", "email": "bob@example.com" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "transfer": true } }"#)
+            .expect(1)
+            .create();
+
+        let response = get_mock_client(&server)
+            .transfer_device()
+            .device_id("device-1".to_string())
+            .target(TransferTarget::User {
+                email: "bob@example.com".to_string(),
+            })
+            .call()
+            .unwrap();
+
+        assert!(response.data.unwrap().transfer);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_transfer_device_to_organization() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "TransferDevice",
+                "variables": { "deviceId": "device-1", "email": "owner@example.com" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "transfer": true } }"#)
+            .expect(1)
+            .create();
+
+        let response = get_mock_client(&server)
+            .transfer_device()
+            .device_id("device-1".to_string())
+            .target(TransferTarget::Organization {
+                owner_email: "owner@example.com".to_string(),
+            })
+            .call()
+            .unwrap();
+
+        assert!(response.data.unwrap().transfer);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_job() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "jobId": "job-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "data": {
+                        "login": {
+                            "account": {
+                                "jobs": {
+                                    "items": [{
+                                    "id": "job-1",
+                                    "owner": { "email": "alice@example.com" },
+                                    "user": { "email": "alice@example.com" },
+                                    "created": "2024-08-30T12:00:00.000Z",
+                                    "updated": "2024-08-30T12:01:00.000Z",
+                                    "status": "SUCCESS",
+                                    "fileVersion": { "id": "version-1", "file": { "id": "file-1", "name": "reboot.sh" } },
+                                    "jobDevices": [{
+                                        "id": "job-device-1",
+                                        "device": { "id": "device-1", "name": "Pi" },
+                                        "status": "SUCCESS",
+                                        "created": "2024-08-30T12:00:00.000Z",
+                                        "updated": "2024-08-30T12:01:00.000Z",
+                                        "attributes": []
+                                    }],
+                                    "arguments": [{ "name": "delay", "value": "10" }]
+                                }]
+                                }
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let job = get_mock_client(&server)
+            .get_job()
+            .job_id("job-1".to_string())
+            .call()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(job.id, "job-1");
+        assert!(matches!(job.status, get_job::JobStatusEnum::SUCCESS));
+        assert_eq!(job.job_devices.len(), 1);
+        assert_eq!(job.job_devices[0].device.id, "device-1");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_job_not_found() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "jobs": { "items": [] } } } } }"#)
+            .create();
+
+        let job = get_mock_client(&server)
+            .get_job()
+            .job_id("unknown".to_string())
+            .call()
+            .unwrap();
+
+        assert!(job.is_none());
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_job_results() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "jobId": "job-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "data": {
+                        "login": {
+                            "account": {
+                                "jobs": {
+                                    "items": [{
+                                        "jobDevices": [
+                                            {
+                                                "device": { "id": "device-1" },
+                                                "status": "SUCCESS",
+                                                "attributes": [
+                                                    { "key": "exitCode", "value": "0" },
+                                                    { "key": "output", "value": "rebooting in 10s\n" }
+                                                ]
+                                            },
+                                            {
+                                                "device": { "id": "device-2" },
+                                                "status": "FAILED",
+                                                "attributes": [
+                                                    { "key": "exitCode", "value": "127" },
+                                                    { "key": "output", "value": "reboot: command not found\n" }
+                                                ]
+                                            },
+                                            {
+                                                "device": { "id": "device-3" },
+                                                "status": "WAITING",
+                                                "attributes": null
+                                            }
+                                        ]
+                                    }]
+                                }
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let results = get_mock_client(&server)
+            .get_job_results()
+            .job_id("job-1".to_string())
+            .call()
+            .unwrap();
+
+        assert_eq!(
+            results,
+            vec![
+                JobDeviceResult {
+                    device_id: "device-1".to_string(),
+                    status: get_job_results::JobStatusEnum::SUCCESS,
+                    exit_code: Some(0),
+                    output: "rebooting in 10s\n".to_string(),
+                },
+                JobDeviceResult {
+                    device_id: "device-2".to_string(),
+                    status: get_job_results::JobStatusEnum::FAILED,
+                    exit_code: Some(127),
+                    output: "reboot: command not found\n".to_string(),
+                },
+                JobDeviceResult {
+                    device_id: "device-3".to_string(),
+                    status: get_job_results::JobStatusEnum::WAITING,
+                    exit_code: None,
+                    output: String::new(),
+                },
+            ]
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_delete_files_reports_partial_failure() {
+        let mut server = mockito::Server::new();
+        let ok_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "fileId": "file-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "deleteFile": true } }"#)
+            .create();
+        let error_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "fileId": "file-2" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": null, "errors": [{ "message": "File not found" }] }"#)
+            .create();
+        let last_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "fileId": "file-3" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "deleteFile": true } }"#)
+            .create();
+
+        let results = get_mock_client(&server)
+            .delete_files()
+            .file_ids(vec![
+                "file-1".to_string(),
+                "file-2".to_string(),
+                "file-3".to_string(),
+            ])
+            .call();
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].as_ref().unwrap());
+        assert!(results[1].is_err());
+        assert!(results[2].as_ref().unwrap());
+        ok_mock.assert();
+        error_mock.assert();
+        last_mock.assert();
+    }
+
+    #[test]
+    fn test_get_file_versions() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "fileId": "file-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "data": {
+                        "login": {
+                            "account": {
+                                "files": [{
+                                    "versions": [
+                                        { "id": "version-3", "version": 3, "created": "2024-09-01T12:00:00.000Z" },
+                                        { "id": "version-1", "version": 1, "created": "2024-08-01T12:00:00.000Z" },
+                                        { "id": "version-2", "version": 2, "created": "2024-08-15T12:00:00.000Z" }
+                                    ]
+                                }]
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let versions = get_mock_client(&server)
+            .get_file_versions()
+            .file_id("file-1".to_string())
+            .call()
+            .unwrap();
+
+        let ids: Vec<&str> = versions.iter().map(|version| version.id.as_str()).collect();
+        assert_eq!(ids, vec!["version-1", "version-2", "version-3"]);
+        assert!(versions
+            .windows(2)
+            .all(|pair| pair[0].created < pair[1].created));
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_devices_paged() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "limit": 1, "offset": 20 }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "data": {
+                        "login": {
+                            "account": {
+                                "devices": {
+                                    "hasMore": true,
+                                    "total": 340,
+                                    "items": [{
+                                        "id": "device-21",
+                                        "name": "Pi",
+                                        "online": true,
+                                        "created": "2024-08-30T12:00:00.000Z",
+                                        "state": "active",
+                                        "lastReported": "2024-08-30T12:00:00.000Z",
+                                        "access": [],
+                                        "tags": [],
+                                        "attributes": {},
+                                        "services": [],
+                                        "categoryA": null,
+                                        "categoryB": null,
+                                        "categoryC": null,
+                                        "categoryD": null,
+                                        "categoryE": null
+                                    }]
+                                }
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let page = get_mock_client(&server)
+            .get_devices_paged()
+            .limit(1)
+            .offset(20)
+            .call()
+            .unwrap();
+
+        assert_eq!(page.total, 340);
+        assert!(page.has_more);
+        assert_eq!(page.offset, Some(20));
+        assert_eq!(page.limit, Some(1));
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].id, "device-21");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_empty_response_body() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_status(200)
+            .with_body("")
+            .create();
+
+        let response = get_mock_client(&server)
+            .cancel_job()
+            .job_id("job-1".to_string())
+            .call()
+            .unwrap();
+
+        assert!(response.data.is_none());
+        assert!(response.errors.is_none());
+        mock.assert();
+    }
+
+    #[test]
+    fn test_idempotency_key_is_stable_across_retry() {
+        let mut server = mockito::Server::new();
+        let unavailable_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header(crate::IDEMPOTENCY_KEY_HEADER, "start-reboot-1")
+            .with_status(503)
+            .expect(1)
+            .create();
+        let ok_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header(crate::IDEMPOTENCY_KEY_HEADER, "start-reboot-1")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "startJob": "job-1" } }"#)
+            .expect(1)
+            .create();
+        let client = R3Client::builder()
+            .credentials(get_mock_client(&server).credentials().clone())
+            .base_url(server.url())
+            .retry_policy(RetryPolicy {
+                max_retries: 1,
+                backoff: BackoffStrategy::Fixed(Duration::ZERO),
+            })
+            .build();
+
+        client
+            .start_job()
+            .file_id("file-1".to_string())
+            .device_ids(vec!["device-1".to_string()])
+            .idempotency_key("start-reboot-1".to_string())
+            .call()
+            .unwrap();
+
+        unavailable_mock.assert();
+        ok_mock.assert();
+    }
+
+    #[test]
+    fn test_no_idempotency_key_by_default() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header(crate::IDEMPOTENCY_KEY_HEADER, mockito::Matcher::Missing)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "cancelJob": true } }"#)
+            .create();
+
+        get_mock_client(&server)
+            .cancel_job()
+            .job_id("job-1".to_string())
+            .call()
+            .unwrap();
+
+        mock.assert();
+    }
+
+    /// A hand-written fake, like an application would use in its own tests.
+    struct FakeApi {
+        started_jobs: std::cell::RefCell<Vec<Vec<String>>>,
+    }
+
+    impl R3Api for FakeApi {
+        fn get_files(
+            &self,
+            _org_id: Option<String>,
+        ) -> Result<Response<get_files::ResponseData>, Box<dyn Error>> {
+            Err("not used by this fake".into())
+        }
+
+        fn start_job(
+            &self,
+            _file_id: String,
+            device_ids: Vec<String>,
+            _arguments: Vec<start_job::ArgumentInput>,
+        ) -> Result<Response<start_job::ResponseData>, Box<dyn Error>> {
+            self.started_jobs.borrow_mut().push(device_ids);
+            Ok(Response {
+                data: Some(start_job::ResponseData {
+                    start_job: "fake-job".to_string(),
+                }),
+                errors: None,
+                extensions: None,
+            })
+        }
+
+        fn cancel_job(
+            &self,
+            _job_id: String,
+        ) -> Result<Response<cancel_job::ResponseData>, Box<dyn Error>> {
+            Err("not used by this fake".into())
+        }
+
+        fn get_jobs(
+            &self,
+            _org_id: Option<String>,
+            _limit: Option<i64>,
+            _job_id_filter: Option<Vec<String>>,
+            _status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+        ) -> Result<Response<get_jobs::ResponseData>, Box<dyn Error>> {
+            Err("not used by this fake".into())
+        }
+
+        fn get_devices(
+            &self,
+            _org_id: Option<String>,
+            _limit: Option<i64>,
+            _offset: Option<i64>,
+            _state: Option<DeviceState>,
+        ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+            Err("not used by this fake".into())
+        }
+    }
+
+    /// Application logic, that only depends on the trait.
+    fn reboot_all(api: &dyn R3Api, device_ids: Vec<String>) -> Result<String, Box<dyn Error>> {
+        let response = api.start_job("reboot-script".to_string(), device_ids, Vec::new())?;
+        Ok(response_data(response)?.start_job)
+    }
+
+    #[test]
+    fn test_r3_api_fake() {
+        let fake = FakeApi {
+            started_jobs: std::cell::RefCell::new(Vec::new()),
+        };
+
+        let job_id = reboot_all(&fake, vec!["device-1".to_string()]).unwrap();
+
+        assert_eq!(job_id, "fake-job");
+        assert_eq!(
+            *fake.started_jobs.borrow(),
+            vec![vec!["device-1".to_string()]]
+        );
+    }
+
+    #[test]
+    fn test_r3_api_client() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "startJob": "job-1" } }"#)
+            .create();
+
+        let client = get_mock_client(&server);
+        let job_id = reboot_all(&client, vec!["device-1".to_string()]).unwrap();
+
+        assert_eq!(job_id, "job-1");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_send_request_with_meta() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_header("x-request-id", "req-123")
+            .with_header("ratelimit-limit", "100")
+            .with_header("ratelimit-remaining", "42")
+            .with_header("ratelimit-reset", "30")
+            .with_body(r#"{ "data": { "startJob": "job-1" } }"#)
+            .create();
+
+        let request_body = StartJob::build_query(start_job::Variables {
+            file_id: "file-1".to_string(),
+            device_ids: vec!["device-1".to_string()],
+            arguments: Vec::new(),
+        });
+        let (response, meta) = get_mock_client(&server)
+            .send_remoteit_graphql_request_with_meta::<_, start_job::ResponseData>(&request_body)
+            .unwrap();
+
+        assert_eq!(response.data.unwrap().start_job, "job-1");
+        assert_eq!(meta.status, reqwest::StatusCode::OK);
+        assert_eq!(meta.request_id.as_deref(), Some("req-123"));
+        assert_eq!(meta.rate_limit.len(), 3);
+        assert_eq!(meta.rate_limit["ratelimit-limit"], "100");
+        assert_eq!(meta.rate_limit_remaining(), Some(42));
+        mock.assert();
+    }
+
+    #[test]
+    fn test_send_request_value_to_pretty_json() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":{"login":{"account":{"devices":{"total":2}}}}}"#)
+            .create();
+        let request_body =
+            GetDeviceCount::build_query(get_device_count::Variables { org_id: None });
+
+        let response = get_mock_client(&server)
+            .send_remoteit_graphql_request_value(&request_body)
+            .unwrap();
+
+        assert_eq!(
+            response.data.as_ref().unwrap()["login"]["account"]["devices"]["total"],
+            2
+        );
+        assert_eq!(
+            response.to_pretty_json().unwrap(),
+            r#"{
+  "data": {
+    "login": {
+      "account": {
+        "devices": {
+          "total": 2
+        }
+      }
+    }
+  }
+}"#
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_chunked_body(|writer| {
+                std::thread::sleep(Duration::from_millis(500));
+                writer.write_all(
+                    br#"{ "data": { "login": { "account": { "devices": { "total": 1 } } } } }"#,
+                )
+            })
+            .expect(4)
+            .create();
+        let client = get_mock_client(&server);
+
+        let error = client
+            .with_timeout(Duration::from_millis(100))
+            .get_device_count()
+            .call()
+            .unwrap_err();
+        assert!(error
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(reqwest::Error::is_timeout));
+        assert_eq!(
+            client
+                .with_timeout(Duration::from_millis(100))
+                .request_timeout(),
+            Some(Duration::from_millis(100))
+        );
+        // The override only applies to the clone.
+        assert_eq!(client.request_timeout(), None);
+        assert_eq!(client.get_device_count().call().unwrap(), 1);
+
+        // A longer timeout takes precedence over a shorter client-wide timeout.
+        let client = R3Client::builder()
+            .credentials(client.credentials().clone())
+            .base_url(server.url())
+            .timeout(Duration::from_millis(100))
+            .build();
+        assert!(client.get_device_count().call().is_err());
+        assert_eq!(
+            client
+                .with_timeout(Duration::from_secs(5))
+                .get_device_count()
+                .call()
+                .unwrap(),
+            1
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_last_rate_limit() {
+        let mut server = mockito::Server::new();
+        let body = r#"{ "data": { "login": { "account": { "devices": { "total": 1 } } } } }"#;
+        let mut mock = |rate_limit_headers: &[(&str, &str)]| {
+            let mut mock = server
+                .mock("POST", GRAPHQL_PATH)
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .expect(1);
+            for (name, value) in rate_limit_headers {
+                mock = mock.with_header(*name, value);
+            }
+            mock.create()
+        };
+        let mocks = [
+            mock(&[]),
+            mock(&[
+                ("x-ratelimit-limit", "100"),
+                ("x-ratelimit-remaining", "99"),
+                ("x-ratelimit-reset", "1893456000"),
+            ]),
+            mock(&[]),
+        ];
+        let client = get_mock_client(&server);
+        let clone = client.clone();
+        let expected = RateLimitInfo {
+            limit: Some(100),
+            remaining: Some(99),
+            reset: chrono::DateTime::from_timestamp(1_893_456_000, 0),
+        };
+
+        client.get_device_count().call().unwrap();
+        assert_eq!(client.last_rate_limit(), None);
+        client.get_device_count().call().unwrap();
+        assert_eq!(client.last_rate_limit(), Some(expected.clone()));
+        assert_eq!(clone.last_rate_limit(), Some(expected.clone()));
+        // A response without rate limit headers keeps the last known state.
+        clone.get_device_count().call().unwrap();
+        assert_eq!(client.last_rate_limit(), Some(expected));
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_info_relative_reset() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("ratelimit-remaining", "5".parse().unwrap());
+        headers.insert("ratelimit-reset", "30".parse().unwrap());
+        let before = chrono::Utc::now();
+
+        let info = ResponseMeta::new(reqwest::StatusCode::OK, &headers)
+            .rate_limit_info()
+            .unwrap();
+
+        assert_eq!(info.limit, None);
+        assert_eq!(info.remaining, Some(5));
+        let reset = info.reset.unwrap();
+        assert!(reset >= before + chrono::Duration::seconds(30));
+        assert!(reset <= chrono::Utc::now() + chrono::Duration::seconds(30));
+        assert_eq!(
+            ResponseMeta::new(reqwest::StatusCode::OK, &reqwest::header::HeaderMap::new())
+                .rate_limit_info(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_client_with_pool_options() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "cancelJob": true } }"#)
+            .create();
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        let client = R3Client::builder()
+            .credentials(credentials)
+            .base_url(server.url())
+            .pool_max_idle_per_host(1)
+            .pool_idle_timeout(std::time::Duration::from_secs(5))
+            .build();
+
+        let response = client
+            .cancel_job()
+            .job_id("job-1".to_string())
+            .call()
+            .unwrap();
+
+        assert!(response.data.unwrap().cancel_job);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_device_count() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "variables": { "orgId": "org-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "devices": { "total": 340 } } } } }"#)
+            .create();
+
+        let count = get_mock_client(&server)
+            .get_device_count()
+            .org_id("org-1".to_string())
+            .call()
+            .unwrap();
+
+        assert_eq!(count, 340);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_devices_typed() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{
+                    "data": {
+                        "login": {
+                            "account": {
+                                "devices": {
+                                    "hasMore": false,
+                                    "total": 1,
+                                    "items": [{
+                                        "id": "80:00:00:00:01:2A:3B:4C",
+                                        "name": "Raspberry Pi",
+                                        "online": true,
+                                        "created": "2024-01-10T08:00:00.000Z",
+                                        "state": "active",
+                                        "lastReported": "2024-08-30T12:00:00.000Z",
+                                        "platform": 1072,
+                                        "version": "4.18.4",
+                                        "hardwareId": "b8:27:eb:12:34:56",
+                                        "access": [],
+                                        "tags": [{ "name": "prod" }, { "name": "berlin" }],
+                                        "attributes": {},
+                                        "services": [
+                                            {
+                                                "id": "80:00:00:00:01:2A:3B:4D",
+                                                "name": "SSH",
+                                                "created": "2024-01-10T08:00:00.000Z",
+                                                "access": [],
+                                                "application": 28,
+                                                "attributes": {},
+                                                "enabled": true
+                                            },
+                                            {
+                                                "id": "80:00:00:00:01:2A:3B:4E",
+                                                "name": null,
+                                                "created": null,
+                                                "access": [],
+                                                "application": null,
+                                                "attributes": {},
+                                                "enabled": false
+                                            }
+                                        ],
+                                        "categoryA": null,
+                                        "categoryB": null,
+                                        "categoryC": null,
+                                        "categoryD": null,
+                                        "categoryE": null
+                                    }]
+                                }
+                            }
+                        }
+                    }
+                }"#,
+            )
+            .create();
+
+        let devices = get_mock_client(&server).get_devices_typed().call().unwrap();
+
+        assert_eq!(devices.len(), 1);
+        let device = &devices[0];
+        assert_eq!(device.id, "80:00:00:00:01:2A:3B:4C");
+        assert_eq!(device.name, "Raspberry Pi");
+        assert_eq!(device.state, "active");
+        assert!(device.online);
+        assert_eq!(device.platform, Some(1072));
+        assert_eq!(device.agent_version.as_deref(), Some("4.18.4"));
+        assert_eq!(device.hardware_id.as_deref(), Some("b8:27:eb:12:34:56"));
+        assert!(device.last_reported.is_some());
+        assert_eq!(device.tags, vec!["prod", "berlin"]);
+        assert_eq!(
+            device.services,
+            vec![
+                crate::operations::DeviceService {
+                    id: "80:00:00:00:01:2A:3B:4D".to_string(),
+                    name: "SSH".to_string(),
+                    application: Some(28),
+                    enabled: true,
+                },
+                crate::operations::DeviceService {
+                    id: "80:00:00:00:01:2A:3B:4E".to_string(),
+                    name: String::new(),
+                    application: None,
+                    enabled: false,
+                },
+            ]
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_send_batch() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!([
+                { "operationName": "GetDevices", "variables": { "limit": 1 } },
+                { "operationName": "GetJobs", "variables": { "limit": 2 } }
+            ])))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"[
+                    {
+                        "data": {
+                            "login": {
+                                "account": {
+                                    "devices": { "hasMore": true, "total": 5, "items": [] }
+                                }
+                            }
+                        }
+                    },
+                    {
+                        "data": null,
+                        "errors": [{ "message": "Not authorized" }]
+                    }
+                ]"#,
+            )
+            .create();
+
+        let (devices, jobs) = get_mock_client(&server)
+            .get_devices_and_jobs()
+            .device_limit(1)
+            .job_limit(2)
+            .call()
+            .unwrap();
+
+        let devices = devices
+            .data
+            .unwrap()
+            .login
+            .unwrap()
+            .account
+            .unwrap()
+            .devices;
+        assert_eq!(devices.total, 5);
+        assert!(jobs.data.is_none());
+        assert_eq!(jobs.errors.unwrap()[0].message, "Not authorized");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_send_batch_size_mismatch() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"[{ "data": { "cancelJob": true } }]"#)
+            .create();
+        let queries = [
+            crate::batch_query(&CancelJob::build_query(cancel_job::Variables {
+                job_id: "job-1".to_string(),
+            }))
+            .unwrap(),
+            crate::batch_query(&CancelJob::build_query(cancel_job::Variables {
+                job_id: "job-2".to_string(),
+            }))
+            .unwrap(),
+        ];
+
+        let error = get_mock_client(&server).send_batch(&queries).unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::BatchSizeMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+        mock.assert();
+    }
+
+    #[test]
+    fn test_send_batch_error_status() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "message": "Batching is not supported" }"#)
+            .create();
+        let queries = [
+            crate::batch_query(&CancelJob::build_query(cancel_job::Variables {
+                job_id: "job-1".to_string(),
+            }))
+            .unwrap(),
+        ];
+
+        let error = get_mock_client(&server).send_batch(&queries).unwrap_err();
+
+        assert_eq!(
+            error
+                .downcast_ref::<reqwest::Error>()
+                .and_then(reqwest::Error::status),
+            Some(reqwest::StatusCode::BAD_REQUEST)
+        );
+        mock.assert();
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_response() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(
+                br#"{ "data": { "login": { "account": { "devices": { "total": 340 } } } } }"#,
+            )
+            .unwrap();
+        let body = encoder.finish().unwrap();
+
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header(
+                "accept-encoding",
+                mockito::Matcher::Regex("gzip".to_string()),
+            )
+            .with_header("content-type", "application/json")
+            .with_header("content-encoding", "gzip")
+            .with_body(body)
+            .create();
+
+        let count = get_mock_client(&server).get_device_count().call().unwrap();
+
+        assert_eq!(count, 340);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_custom_graphql_path() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", "/graphql/v2")
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "cancelJob": true } }"#)
+            .create();
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        let client = R3Client::builder()
+            .credentials(credentials)
+            .base_url(server.url())
+            .graphql_path("/graphql/v2".to_string())
+            .build();
+
+        let response = client
+            .cancel_job()
+            .job_id("job-1".to_string())
+            .call()
+            .unwrap();
+
+        assert!(response.data.unwrap().cancel_job);
+        assert_eq!(client.graphql_path(), "/graphql/v2");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_check_clock_skew() {
+        let server_date = chrono::Utc::now() + chrono::Duration::minutes(10);
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header(
+                "date",
+                &server_date.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            )
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "__typename": "Query" } }"#)
+            .create();
+        let client = get_mock_client(&server);
+        let clone = client.clone();
+
+        let skew = client.check_clock_skew().unwrap();
+
+        assert!((skew - chrono::Duration::minutes(10)).num_seconds().abs() <= 2);
+        assert_eq!(clone.clock_skew(), skew);
+        let signing_date = chrono::DateTime::parse_from_rfc2822(&clone.signing_date()).unwrap();
+        assert!(
+            (signing_date.with_timezone(&chrono::Utc) - server_date)
+                .num_seconds()
+                .abs()
+                <= 2
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_with_clock_skew() {
+        let server = mockito::Server::new();
+        let client = get_mock_client(&server);
+
+        let skewed = client
+            .clone()
+            .with_clock_skew(chrono::Duration::minutes(-5));
+
+        assert_eq!(skewed.clock_skew(), chrono::Duration::minutes(-5));
+        assert_eq!(client.clock_skew(), chrono::Duration::zero());
+    }
+
+    #[test]
+    fn test_sign_body_digest() {
+        let request_body = CancelJob::build_query(cancel_job::Variables {
+            job_id: "job-1".to_string(),
+        });
+        let expected_digest =
+            crate::auth::create_digest(&serde_json::to_vec(&request_body).unwrap());
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header("digest", expected_digest.as_str())
+            .match_header(
+                "authorization",
+                mockito::Matcher::Regex(
+                    r#"headers="\(request-target\) host date content-type digest""#.to_string(),
+                ),
+            )
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "cancelJob": true } }"#)
+            .create();
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        let client = R3Client::builder()
+            .credentials(credentials)
+            .base_url(server.url())
+            .sign_body_digest(true)
+            .build();
+
+        client
+            .send_remoteit_graphql_request::<_, cancel_job::ResponseData>(&request_body)
+            .unwrap();
+
+        mock.assert();
+    }
+
+    #[test]
+    fn test_error_on_graphql_errors() {
+        let request_body = CancelJob::build_query(cancel_job::Variables {
+            job_id: "job-1".to_string(),
+        });
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": null, "errors": [{ "message": "Job not found" }] }"#)
+            .expect(2)
+            .create();
+        let client = |error_on_graphql_errors| {
+            let credentials = Credentials::builder()
+                .r3_access_key_id("foo")
+                .r3_secret_access_key("YmFy")
+                .build()
+                .unwrap();
+            R3Client::builder()
+                .credentials(credentials)
+                .base_url(server.url())
+                .error_on_graphql_errors(error_on_graphql_errors)
+                .build()
+        };
+
+        // Off by default, so the errors are part of an `Ok` response.
+        let response = client(false)
+            .send_remoteit_graphql_request::<_, cancel_job::ResponseData>(&request_body)
+            .unwrap();
+        assert!(response.data.is_none());
+        assert_eq!(response.errors.unwrap()[0].message, "Job not found");
+
+        let error = client(true)
+            .send_remoteit_graphql_request::<_, cancel_job::ResponseData>(&request_body)
+            .unwrap_err();
+        let Some(R3Error::GraphQl(errors)) = error.downcast_ref::<R3Error>() else {
+            panic!("Expected R3Error::GraphQl, got {error:?}");
+        };
+        assert_eq!(errors[0].0.message, "Job not found");
+
+        mock.assert();
+    }
+
+    #[test]
+    fn test_set_credentials() {
+        let mut server = mockito::Server::new();
+        let old_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header(
+                "authorization",
+                mockito::Matcher::Regex(r#"keyId="foo""#.to_string()),
+            )
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "__typename": "Query" } }"#)
+            .expect(1)
+            .create();
+        let new_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_header(
+                "authorization",
+                mockito::Matcher::Regex(r#"keyId="rotated""#.to_string()),
+            )
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "__typename": "Query" } }"#)
+            .expect(2)
+            .create();
+        let query = graphql_client::QueryBody {
+            variables: serde_json::json!({}),
+            query: "{ __typename }",
+            operation_name: "",
+        };
+
+        let mut client = get_mock_client(&server);
+        let mut clone = client.clone();
+        client.set_credentials(
+            Credentials::builder()
+                .r3_access_key_id("rotated")
+                .r3_secret_access_key("YmF6")
+                .build()
+                .unwrap(),
+        );
+        client
+            .send_remoteit_graphql_request::<_, serde_json::Value>(&query)
+            .unwrap();
+        clone
+            .send_remoteit_graphql_request::<_, serde_json::Value>(&query)
+            .unwrap();
+
+        *clone.credentials_mut() = client.credentials().clone();
+        clone
+            .send_remoteit_graphql_request::<_, serde_json::Value>(&query)
+            .unwrap();
+        assert_eq!(client.credentials().access_key_id(), "rotated");
+
+        // Only the clone used the old key, until its credentials were replaced too.
+        old_mock.assert();
+        new_mock.assert();
+    }
+
+    #[test]
+    fn test_get_files_executable_filter() {
+        let mut server = mockito::Server::new();
+        let file = |id: &str, executable: bool| {
+            serde_json::json!({
+                "owner": { "email": "alice@example.com", "id": "user-1" },
+                "id": id,
+                "name": format!("{id}.sh"),
+                "shortDesc": null,
+                "longDesc": null,
+                "executable": executable,
+                "created": "2024-08-30T12:00:00.000Z",
+                "updated": "2024-08-30T12:00:00.000Z",
+                "versions": []
+            })
+        };
+        let body = serde_json::json!({
+            "data": { "login": { "account": { "files": [
+                file("script-1", true),
+                file("asset-1", false),
+                file("script-2", true),
+            ] } } }
         });
-        self.send_remoteit_graphql_request(&request_body)
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .expect(3)
+            .create();
+        let client = get_mock_client(&server);
+        let file_ids = |executable: Option<bool>| {
+            let response = match executable {
+                Some(executable) => client.get_files().executable(executable).call(),
+                None => client.get_files().call(),
+            };
+            response
+                .unwrap()
+                .data
+                .unwrap()
+                .login
+                .unwrap()
+                .account
+                .unwrap()
+                .files
+                .into_iter()
+                .map(|file| file.id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(file_ids(Some(true)), vec!["script-1", "script-2"]);
+        assert_eq!(file_ids(Some(false)), vec!["asset-1"]);
+        assert_eq!(file_ids(None), vec!["script-1", "asset-1", "script-2"]);
+        mock.assert();
     }
 
-    // endregion
-}
+    #[test]
+    fn test_get_files_owner_filter() {
+        let mut server = mockito::Server::new();
+        let file = |id: &str, owner_id: &str| {
+            serde_json::json!({
+                "owner": { "email": format!("{owner_id}@example.com"), "id": owner_id },
+                "id": id,
+                "name": format!("{id}.sh"),
+                "shortDesc": null,
+                "longDesc": null,
+                "executable": true,
+                "created": "2024-08-30T12:00:00.000Z",
+                "updated": "2024-08-30T12:00:00.000Z",
+                "versions": []
+            })
+        };
+        let body = serde_json::json!({
+            "data": { "login": { "account": { "files": [
+                file("alice-1", "alice"),
+                file("bob-1", "bob"),
+                file("alice-2", "alice"),
+            ] } } }
+        });
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(body.to_string())
+            .expect(3)
+            .create();
+        let client = get_mock_client(&server);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::credentials::Credentials;
-    use std::path::PathBuf;
+        let owned_by = |owner_id: &str| {
+            client
+                .get_files()
+                .owner_id(owner_id.to_string())
+                .call()
+                .unwrap()
+                .data
+                .unwrap()
+                .login
+                .unwrap()
+                .account
+                .unwrap()
+                .files
+                .into_iter()
+                .map(|file| file.id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(owned_by("alice"), vec!["alice-1", "alice-2"]);
+        assert!(owned_by("carol").is_empty());
 
-    fn get_credentials() -> Credentials {
-        Credentials::load_from_disk()
-            .custom_credentials_path(PathBuf::from(".env.remoteit"))
+        let my_files = client.my_files().user_id("bob".to_string()).call().unwrap();
+        assert_eq!(
+            my_files.into_iter().map(|file| file.id).collect::<Vec<_>>(),
+            vec!["bob-1"]
+        );
+        mock.assert();
+    }
+
+    #[test]
+    fn test_execute_query() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "query": "query Device($id: String!) { login { device(id: $id) { id firmware } } }",
+                "variables": { "id": "device-1" },
+                "operationName": "Device"
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "login": { "device": [{ "id": "device-1", "firmware": "1.2.3" }] } } }"#,
+            )
+            .create();
+
+        let response = get_mock_client(&server)
+            .execute_query()
+            .query("query Device($id: String!) { login { device(id: $id) { id firmware } } }")
+            .variables(serde_json::json!({ "id": "device-1" }))
+            .operation_name("Device")
             .call()
+            .unwrap();
+
+        let device = &response.data.unwrap()["login"]["device"][0];
+        assert_eq!(device["id"], "device-1");
+        assert_eq!(device["firmware"], "1.2.3");
+        mock.assert();
+    }
+
+    #[test]
+    fn test_execute_query_retries_and_checks_errors() {
+        let mut server = mockito::Server::new();
+        let unavailable_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_status(503)
+            .expect(1)
+            .create();
+        let error_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": null, "errors": [{ "message": "Not authorized" }] }"#)
+            .create();
+        let client = R3Client::builder()
+            .credentials(get_mock_client(&server).credentials().clone())
+            .base_url(server.url())
+            .retry_policy(RetryPolicy {
+                max_retries: 1,
+                backoff: BackoffStrategy::Fixed(Duration::ZERO),
+            })
+            .error_on_graphql_errors(true)
+            .build();
+
+        let error = client
+            .execute_query()
+            .query("{ login { id } }")
+            .call()
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::GraphQl(_))
+        ));
+        unavailable_mock.assert();
+        error_mock.assert();
+    }
+
+    #[test]
+    fn test_set_service_enabled() {
+        let mut server = mockito::Server::new();
+        let mocks = [true, false].map(|enabled| {
+            server
+                .mock("POST", GRAPHQL_PATH)
+                .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                    "variables": { "serviceId": "service-1", "enabled": enabled }
+                })))
+                .with_header("content-type", "application/json")
+                .with_body(
+                    serde_json::json!({
+                        "data": { "updateService": {
+                            "id": "service-1",
+                            "name": "SSH",
+                            "enabled": enabled,
+                            "state": if enabled { "active" } else { "inactive" }
+                        } }
+                    })
+                    .to_string(),
+                )
+                .create()
+        });
+        let client = get_mock_client(&server);
+
+        let enabled = client
+            .set_service_enabled()
+            .service_id("service-1".into())
+            .enabled(true)
+            .call()
+            .unwrap();
+        assert!(enabled.enabled);
+        assert_eq!(enabled.state, "active");
+
+        let disabled = client
+            .set_service_enabled()
+            .service_id(ServiceId::from("service-1"))
+            .enabled(false)
+            .call()
+            .unwrap();
+        assert!(!disabled.enabled);
+        assert_eq!(disabled.name.as_deref(), Some("SSH"));
+
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    fn mock_application_types(server: &mut mockito::Server) -> mockito::Mock {
+        server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "GetApplicationTypes"
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "applicationTypes": [
+                    { "id": 28, "name": "SSH", "description": "Secure Shell", "port": 22, "protocol": "TCP", "proxy": false }
+                ] } }"#,
+            )
+            .create()
+    }
+
+    #[test]
+    fn test_create_service() {
+        let mut server = mockito::Server::new();
+        let application_types_mock = mock_application_types(&mut server);
+        let create_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "CreateService",
+                "variables": { "deviceId": "device-1", "application": 28, "port": 22, "name": "SSH" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "addService": { "id": "service-1" } } }"#)
+            .create();
+
+        let service_id = get_mock_client(&server)
+            .create_service()
+            .device_id("device-1".to_string())
+            .application_type_id(28)
+            .port(22)
+            .name("SSH".to_string())
+            .call()
+            .unwrap();
+
+        assert_eq!(service_id, ServiceId::from("service-1"));
+        application_types_mock.assert();
+        create_mock.assert();
+    }
+
+    #[test]
+    fn test_create_service_unknown_application_type() {
+        let mut server = mockito::Server::new();
+        let application_types_mock = mock_application_types(&mut server);
+
+        let error = get_mock_client(&server)
+            .create_service()
+            .device_id("device-1".to_string())
+            .application_type_id(9999)
+            .port(22)
+            .call()
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::InvalidArgument(_))
+        ));
+        application_types_mock.assert();
+    }
+
+    #[test]
+    fn test_remove_service() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "RemoveService",
+                "variables": { "serviceId": "service-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "removeService": true } }"#)
+            .create();
+
+        let removed = get_mock_client(&server)
+            .remove_service()
+            .service_id("service-1".into())
+            .call()
+            .unwrap();
+
+        assert!(removed);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_create_connection() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "CreateConnection",
+                "variables": { "serviceId": "service-1", "hostIp": "203.0.113.7", "proxyType": "PORT_PROXY" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "connect": {
+                    "id": "connection-1",
+                    "reverseProxy": false,
+                    "host": "proxy.remote.it",
+                    "port": 33001,
+                    "url": "proxy.remote.it:33001",
+                    "created": "2024-08-30T12:00:00.000Z",
+                    "timeout": 900
+                } } }"#,
+            )
+            .create();
+
+        let connection = get_mock_client(&server)
+            .create_connection()
+            .service_id("service-1".into())
+            .options(
+                ConnectionOptions::builder()
+                    .host_ip("203.0.113.7".to_string())
+                    .proxy_type(create_connection::ProxyType::PORT_PROXY)
+                    .build(),
+            )
+            .call()
+            .unwrap();
+
+        assert_eq!(connection.id, "connection-1");
+        assert!(!connection.reverse_proxy);
+        assert_eq!(connection.host, "proxy.remote.it");
+        assert_eq!(connection.port, Some(33001));
+        assert_eq!(connection.timeout, 900);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_connections_and_disconnect_all() {
+        let mut server = mockito::Server::new();
+        let list_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "GetConnections"
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "login": { "account": { "sessions": [
+                    { "id": "connection-1", "timestamp": "2024-08-30T12:00:00.000Z", "state": "ACTIVE", "connected": true, "target": { "id": "service-1" } },
+                    { "id": "connection-2", "timestamp": "2024-08-30T13:00:00.000Z", "state": "IDLE", "connected": false, "target": null }
+                ] } } } }"#,
+            )
+            .expect(2)
+            .create();
+        let disconnect_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "RemoveConnection",
+                "variables": { "connectionId": "connection-1", "serviceId": "service-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "disconnect": true } }"#)
+            .create();
+        let failing_disconnect_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "RemoveConnection",
+                "variables": { "connectionId": "connection-2", "serviceId": null }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": null, "errors": [{ "message": "Connection not found" }] }"#)
+            .create();
+        let client = get_mock_client(&server);
+
+        let connections = client.get_connections().call().unwrap();
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].id, "connection-1");
+        assert_eq!(
+            connections[0].service_id,
+            Some(ServiceId::from("service-1"))
+        );
+        assert_eq!(connections[0].status, "ACTIVE");
+        assert!(connections[0].connected);
+        assert_eq!(connections[1].service_id, None);
+
+        let results = client.disconnect_all().call().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, "connection-1");
+        assert!(results[0].1.as_ref().unwrap());
+        assert_eq!(results[1].0, "connection-2");
+        assert!(results[1].1.is_err());
+
+        list_mock.assert();
+        disconnect_mock.assert();
+        failing_disconnect_mock.assert();
+    }
+
+    #[test]
+    fn test_get_audit_log() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "GetAuditLog",
+                "variables": {
+                    "orgId": "org-1",
+                    "limit": 2,
+                    "offset": 4
+                }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "login": { "account": { "events": {
+                    "total": 7,
+                    "hasMore": true,
+                    "items": [
+                        { "__typename": "DeviceShareEvent", "id": "event-2", "type": "DEVICE_SHARE", "timestamp": "2024-08-30T13:00:00.000Z", "action": "add", "actor": { "email": "alice@example.com" }, "target": [{ "id": "device-1" }, { "id": "device-2" }] },
+                        { "__typename": "GenericEvent", "id": "event-1", "type": "AUTH_LOGIN", "timestamp": "2024-08-30T12:00:00.000Z", "action": null, "actor": { "email": "bob@example.com" }, "target": null }
+                    ]
+                } } } } }"#,
+            )
+            .create();
+
+        let since = chrono::DateTime::parse_from_rfc3339("2024-08-01T00:00:00Z")
             .unwrap()
-            .take_profile("default")
-            .unwrap()
+            .with_timezone(&chrono::Utc)
+            .with_timezone(&chrono::Local);
+        let log = get_mock_client(&server)
+            .get_audit_log()
+            .org_id("org-1".to_string())
+            .limit(2)
+            .offset(4)
+            .since(since)
+            .call()
+            .unwrap();
+
+        assert_eq!(log.total, 7);
+        assert!(log.has_more);
+        assert_eq!(log.offset, Some(4));
+        assert_eq!(log.items[0].event_type, "AUTH_LOGIN");
+        assert_eq!(log.items[0].actor.as_deref(), Some("bob@example.com"));
+        assert!(log.items[0].targets.is_empty());
+        assert_eq!(log.items[1].action.as_deref(), Some("add"));
+        assert_eq!(log.items[1].targets, vec!["device-1", "device-2"]);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_networks() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "login": { "account": { "networks": [
+                    { "id": "network-1", "name": "Office", "connections": [
+                        { "service": { "id": "service-1" } },
+                        { "service": { "id": "service-2" } }
+                    ] },
+                    { "id": "network-2", "name": "Lab", "connections": [] }
+                ] } } } }"#,
+            )
+            .create();
+
+        let networks = get_mock_client(&server).get_networks().call().unwrap();
+
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].id, NetworkId::from("network-1"));
+        assert_eq!(networks[0].name, "Office");
+        assert_eq!(
+            networks[0].service_ids,
+            vec![ServiceId::from("service-1"), ServiceId::from("service-2")]
+        );
+        assert!(networks[1].service_ids.is_empty());
+        mock.assert();
+    }
+
+    fn mock_operation(
+        server: &mut mockito::Server,
+        operation_name: &str,
+        variables: &serde_json::Value,
+        body: &str,
+    ) -> mockito::Mock {
+        server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": operation_name,
+                "variables": variables
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create()
+    }
+
+    #[test]
+    fn test_network_round_trip() {
+        let mut server = mockito::Server::new();
+        let mocks = [
+            mock_operation(
+                &mut server,
+                "CreateNetwork",
+                &serde_json::json!({ "name": "Office" }),
+                r#"{ "data": { "createNetwork": { "id": "network-1" } } }"#,
+            ),
+            mock_operation(
+                &mut server,
+                "AddServiceToNetwork",
+                &serde_json::json!({ "networkId": "network-1", "serviceId": "service-1" }),
+                r#"{ "data": { "addNetworkConnection": true } }"#,
+            ),
+            mock_operation(
+                &mut server,
+                "RemoveServiceFromNetwork",
+                &serde_json::json!({ "networkId": "network-1", "serviceId": "service-1" }),
+                r#"{ "data": { "removeNetworkConnection": true } }"#,
+            ),
+            mock_operation(
+                &mut server,
+                "DeleteNetwork",
+                &serde_json::json!({ "networkId": "network-1" }),
+                r#"{ "data": { "deleteNetwork": true } }"#,
+            ),
+        ];
+        let client = get_mock_client(&server);
+
+        let network_id = client
+            .create_network()
+            .name("Office".to_string())
+            .call()
+            .unwrap();
+        assert_eq!(network_id, NetworkId::from("network-1"));
+        assert!(client
+            .add_service_to_network()
+            .network_id(network_id.clone())
+            .service_id("service-1".into())
+            .call()
+            .unwrap());
+        assert!(client
+            .remove_service_from_network()
+            .network_id(network_id.clone())
+            .service_id("service-1".into())
+            .call()
+            .unwrap());
+        assert!(client
+            .delete_network()
+            .network_id(network_id)
+            .call()
+            .unwrap());
+
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_share_and_unshare_device() {
+        let mut server = mockito::Server::new();
+        let share_mock = mock_operation(
+            &mut server,
+            "ShareDevice",
+            &serde_json::json!({
+                "deviceId": "device-1",
+                "emails": ["bob@example.com"],
+                "scripting": true,
+                "notify": false
+            }),
+            r#"{ "data": { "share": true } }"#,
+        );
+        let unshare_mock = mock_operation(
+            &mut server,
+            "UnshareDevice",
+            &serde_json::json!({ "deviceId": "device-1", "emails": ["bob@example.com"] }),
+            r#"{ "data": { "share": true } }"#,
+        );
+        let get_shares_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "GetDeviceShares"
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "login": { "device": [{ "access": [
+                    { "created": "2024-08-30T12:00:00.000Z", "scripting": true, "user": { "id": "user-2", "email": "bob@example.com" } }
+                ] }] } } }"#,
+            )
+            .expect(2)
+            .create();
+        let client = get_mock_client(&server);
+
+        let shares = client
+            .share_device()
+            .device_id("device-1".to_string())
+            .email("bob@example.com".to_string())
+            .permissions(BTreeSet::from([SharePermission::Scripting]))
+            .call()
+            .unwrap();
+        assert_eq!(shares.len(), 1);
+        assert_eq!(shares[0].user_id, "user-2");
+        assert_eq!(
+            shares[0].permissions,
+            BTreeSet::from([SharePermission::Connect, SharePermission::Scripting])
+        );
+
+        client
+            .unshare_device()
+            .device_id("device-1".to_string())
+            .email("bob@example.com".to_string())
+            .call()
+            .unwrap();
+
+        share_mock.assert();
+        unshare_mock.assert();
+        get_shares_mock.assert();
+    }
+
+    #[test]
+    fn test_get_plan_free() {
+        let mut server = mockito::Server::new();
+        let mock = mock_operation(
+            &mut server,
+            "GetPlan",
+            &serde_json::json!({ "orgId": null }),
+            r#"{ "data": { "login": { "account": {
+                "licenses": [],
+                "limits": [
+                    { "name": "devices", "value": 5, "actual": 2 },
+                    { "name": "scripting", "value": null, "actual": null }
+                ],
+                "info": { "devices": { "total": 2, "online": 1, "licensed": 0 } }
+            } } } }"#,
+        );
+
+        let plan = get_mock_client(&server).get_plan().call().unwrap().unwrap();
+
+        assert_eq!(plan.plan_id, None);
+        assert_eq!(plan.plan_name, None);
+        assert!(!plan.commercial);
+        assert_eq!(plan.expiration, None);
+        assert_eq!(plan.device_limit, Some(5));
+        assert_eq!(plan.devices_total, 2);
+        assert_eq!(plan.devices_online, 1);
+        assert_eq!(plan.devices_licensed, 0);
+        assert_eq!(plan.limits[1].value, None);
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_plan_paid() {
+        let mut server = mockito::Server::new();
+        let mock = mock_operation(
+            &mut server,
+            "GetPlan",
+            &serde_json::json!({ "orgId": "org-1" }),
+            r#"{ "data": { "login": { "account": {
+                "licenses": [
+                    { "expiration": "2023-01-01T00:00:00.000Z", "valid": false, "plan": { "id": "plan-old", "name": "PERSONAL", "commercial": false } },
+                    { "expiration": null, "valid": true, "plan": { "id": "plan-1", "name": "BUSINESS", "commercial": true } }
+                ],
+                "limits": [{ "name": "devices", "value": "250", "actual": 40 }],
+                "info": { "devices": { "total": 40, "online": 38, "licensed": 40 } }
+            } } } }"#,
+        );
+
+        let plan = get_mock_client(&server)
+            .get_plan()
+            .org_id("org-1".to_string())
+            .call()
             .unwrap()
+            .unwrap();
+
+        assert_eq!(plan.plan_id.as_deref(), Some("plan-1"));
+        assert_eq!(plan.plan_name.as_deref(), Some("BUSINESS"));
+        assert!(plan.commercial);
+        assert_eq!(plan.expiration, None);
+        assert_eq!(plan.device_limit, Some(250));
+        assert_eq!(plan.devices_total, 40);
+        assert_eq!(plan.limits[0].actual, Some(serde_json::json!(40)));
+        mock.assert();
     }
 
-    fn get_client() -> R3Client {
-        R3Client::builder().credentials(get_credentials()).build()
+    fn devices_body(ids: &[&str]) -> String {
+        let items: Vec<serde_json::Value> = ids
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "id": id, "name": id, "online": true, "created": null, "state": "active",
+                    "lastReported": null, "platform": 1072, "version": null, "hardwareId": null,
+                    "access": [], "tags": [], "attributes": {}, "services": [],
+                    "categoryA": null, "categoryB": null, "categoryC": null, "categoryD": null, "categoryE": null
+                })
+            })
+            .collect();
+        serde_json::json!({ "data": { "login": { "account": { "devices": {
+            "hasMore": false, "total": ids.len(), "items": items
+        } } } } })
+        .to_string()
     }
 
     #[test]
-    fn test_get_files() {
-        let response = get_client().get_files().call().unwrap();
-        assert!(response.data.is_some());
-        assert!(response.errors.is_none());
+    fn test_get_devices_all_orgs() {
+        let mut server = mockito::Server::new();
+        let mocks = [
+            mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "orgId": "org-1" }),
+                &devices_body(&["device-1"]),
+            ),
+            mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "orgId": "org-2" }),
+                &devices_body(&["device-2", "device-3"]),
+            ),
+        ];
+
+        let devices = get_mock_client(&server)
+            .get_devices_all_orgs()
+            .org_ids(vec!["org-1".to_string(), "org-2".to_string()])
+            .call()
+            .unwrap();
+
+        let tagged: Vec<(&str, &str)> = devices
+            .iter()
+            .map(|d| (d.org_id.as_str(), d.device.id.as_str()))
+            .collect();
+        assert_eq!(
+            tagged,
+            vec![
+                ("org-1", "device-1"),
+                ("org-2", "device-2"),
+                ("org-2", "device-3")
+            ]
+        );
+        for mock in mocks {
+            mock.assert();
+        }
     }
 
     #[test]
-    fn test_get_jobs() {
-        let response = get_client().get_jobs().limit(1).call().unwrap();
-        assert!(response.data.is_some());
-        assert!(response.errors.is_none());
+    fn test_require_online() {
+        let mut server = mockito::Server::new();
+        let online_mock = mock_operation(
+            &mut server,
+            "GetDeviceReachability",
+            &serde_json::json!({ "deviceId": "device-1" }),
+            r#"{ "data": { "login": { "device": [
+                { "id": "device-1", "online": true, "lastReported": "2024-08-30T12:00:00.000Z" }
+            ] } } }"#,
+        );
+        let offline_mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": "GetDeviceReachability",
+                "variables": { "deviceId": "device-2" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "data": { "login": { "device": [
+                    { "id": "device-2", "online": false, "lastReported": null }
+                ] } } }"#,
+            )
+            .expect(2)
+            .create();
+        let client = get_mock_client(&server);
+
+        let online = client
+            .require_online()
+            .device_id("device-1".to_string())
+            .call()
+            .unwrap();
+        assert!(online.online);
+        assert!(online.last_reported_age().unwrap() > chrono::Duration::zero());
+
+        let offline = client
+            .is_device_reachable()
+            .device_id("device-2".to_string())
+            .call()
+            .unwrap();
+        assert!(!offline.online);
+        assert_eq!(offline.last_reported_age(), None);
+        let error = client
+            .require_online()
+            .device_id("device-2".to_string())
+            .call()
+            .unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::DeviceOffline(id)) if id == "device-2"
+        ));
+
+        online_mock.assert();
+        offline_mock.assert();
     }
 
     #[test]
-    fn test_get_jobs_with_filters() {
-        let response = get_client()
-            .get_jobs()
-            .job_id_filter(vec!["foobar".to_string()])
+    fn test_is_device_reachable_not_found() {
+        let mut server = mockito::Server::new();
+        let mock = mock_operation(
+            &mut server,
+            "GetDeviceReachability",
+            &serde_json::json!({ "deviceId": "device-1" }),
+            r#"{ "data": { "login": { "device": [] } } }"#,
+        );
+
+        let error = get_mock_client(&server)
+            .is_device_reachable()
+            .device_id("device-1".to_string())
+            .call()
+            .unwrap_err();
+
+        assert!(matches!(
+            error.downcast_ref::<R3Error>(),
+            Some(R3Error::DeviceNotFound(_))
+        ));
+        mock.assert();
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        // 503 is retried, 500 is not.
+        for (status, expected_requests) in [(503, 3), (500, 1)] {
+            let mut server = mockito::Server::new();
+            let mock = server
+                .mock("POST", GRAPHQL_PATH)
+                .with_status(status)
+                .expect(expected_requests)
+                .create();
+            let client = R3Client::builder()
+                .credentials(get_mock_client(&server).credentials().clone())
+                .base_url(server.url())
+                .retry_policy(RetryPolicy {
+                    max_retries: 2,
+                    backoff: BackoffStrategy::Fixed(std::time::Duration::ZERO),
+                })
+                .build();
+
+            assert!(client.get_self().call().is_err());
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_send_remoteit_graphql_request_raw() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_header("x-request-id", "request-1")
+            .with_body(r#"{ "data": { "login": { "id": "user-1" } } }"#)
+            .create();
+
+        let request_body = GetSelf::build_query(get_self::Variables {});
+        let response = get_mock_client(&server)
+            .send_remoteit_graphql_request_raw(&request_body)
+            .unwrap();
+
+        assert!(response.status().is_success());
+        assert_eq!(response.headers()["x-request-id"], "request-1");
+        assert_eq!(
+            response.text().unwrap(),
+            r#"{ "data": { "login": { "id": "user-1" } } }"#
+        );
+        mock.assert();
+    }
+
+    /// Checks the `Authorization` header of the request against the signed headers it lists.
+    fn assert_valid_signature(request: &reqwest::blocking::Request, client: &R3Client) {
+        let header = |name: &str| request.headers()[name].to_str().unwrap().to_string();
+        let authorization = header("Authorization");
+        let field = |name: &str| {
+            let start = authorization.find(&format!("{name}=\"")).unwrap() + name.len() + 2;
+            let end = start + authorization[start..].find('"').unwrap();
+            authorization[start..end].to_string()
+        };
+        let signature_params = field("headers")
+            .split(' ')
+            .map(|name| match name {
+                "(request-target)" => format!(
+                    "(request-target): {} {}",
+                    request.method().as_str().to_lowercase(),
+                    request.url().path()
+                ),
+                "host" => format!("host: {}", client.environment().host()),
+                name => format!("{name}: {}", header(name)),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert!(crate::auth::verify_signature(
+            client.credentials().signing_key(),
+            &signature_params,
+            &field("signature")
+        ));
+    }
+
+    #[test]
+    fn test_signed_request() {
+        let server = mockito::Server::new();
+        let client = get_mock_client(&server);
+
+        let get = client
+            .signed_request()
+            .method(Method::GET)
+            .path("/apv/v27/user/login")
+            .call()
+            .build()
+            .unwrap();
+        assert_eq!(
+            get.url().as_str(),
+            format!("{}/apv/v27/user/login", server.url())
+        );
+        assert!(get.body().is_none());
+        assert_valid_signature(&get, &client);
+
+        let post = client
+            .signed_request()
+            .method(Method::POST)
+            .path(GRAPHQL_PATH)
+            .body(br#"{"query":"{ login { id } }"}"#.to_vec())
+            .call()
+            .build()
+            .unwrap();
+        assert_eq!(post.headers()["Content-Type"], "application/json");
+        assert!(post.body().is_some());
+        assert_valid_signature(&post, &client);
+    }
+
+    #[test]
+    fn test_environment() {
+        let credentials = || {
+            Credentials::builder()
+                .r3_access_key_id("foo")
+                .r3_secret_access_key("YmFy")
+                .build()
+                .unwrap()
+        };
+        let production = R3Client::builder().credentials(credentials()).build();
+        assert_eq!(production.base_url(), BASE_URL);
+        let staging = R3Client::builder()
+            .credentials(credentials())
+            .environment(Environment::Staging)
+            .build();
+        assert_eq!(staging.base_url(), STAGING_BASE_URL);
+
+        let server = mockito::Server::new();
+        for environment in [
+            Environment::Production,
+            Environment::Staging,
+            Environment::Custom(server.url()),
+        ] {
+            let client = R3Client::builder()
+                .credentials(credentials())
+                .environment(environment.clone())
+                .base_url(server.url())
+                .build();
+            let request = client
+                .signed_request()
+                .method(Method::POST)
+                .path(GRAPHQL_PATH)
+                .call()
+                .build()
+                .unwrap();
+            assert_eq!(
+                request.url().as_str(),
+                format!("{}{GRAPHQL_PATH}", server.url())
+            );
+            assert_valid_signature(&request, &client);
+            assert!(request.headers()["Authorization"]
+                .to_str()
+                .unwrap()
+                .contains(r#"headers="(request-target) host date content-type""#));
+        }
+    }
+
+    #[test]
+    fn test_resolve_device_id() {
+        let mut server = mockito::Server::new();
+        let mocks = [
+            mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "name": "pi" }),
+                &devices_body(&["pi", "pi-2"]),
+            ),
+            mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "name": "nas" }),
+                &devices_body(&["nas-backup"]),
+            ),
+            mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "name": "router" }),
+                &devices_body(&["router", "router"]),
+            ),
+            mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "name": "pi-2" }),
+                &devices_body(&["pi-2"]),
+            ),
+        ];
+        let client = get_mock_client(&server);
+        let resolve = |name: &str| {
+            client
+                .resolve_device_id()
+                .name(name.to_string())
+                .call()
+                .map_err(|e| e.downcast::<R3Error>().unwrap())
+        };
+
+        // `devices_body` uses the name as the ID as well.
+        assert_eq!(resolve("pi").unwrap(), "pi");
+        assert!(matches!(
+            resolve("nas"),
+            Err(error) if matches!(*error, R3Error::DeviceNameNotFound(ref name) if name == "nas")
+        ));
+        assert!(matches!(
+            resolve("router"),
+            Err(error) if matches!(*error, R3Error::AmbiguousDeviceName { ref device_ids, .. } if device_ids.len() == 2)
+        ));
+        assert_eq!(
+            client
+                .resolve_device_ids()
+                .names(vec!["pi-2".to_string()])
+                .call()
+                .unwrap(),
+            vec!["pi-2"]
+        );
+
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    fn jobs_body(ids: &[&str], last: Option<&str>, has_more: bool) -> String {
+        let items: Vec<serde_json::Value> = ids
+            .iter()
+            .map(|id| {
+                serde_json::json!({
+                    "id": id, "owner": { "email": "alice@example.com" }, "user": { "email": "alice@example.com" },
+                    "created": "2024-08-30T12:00:00.000Z", "updated": "2024-08-30T12:01:00.000Z",
+                    "status": "SUCCESS", "tags": [], "tagOperator": "ANY", "fileVersion": null,
+                    "jobDevices": [], "arguments": []
+                })
+            })
+            .collect();
+        serde_json::json!({ "data": { "login": { "account": { "jobs": {
+            "hasMore": has_more, "total": ids.len(), "last": last, "items": items
+        } } } } })
+        .to_string()
+    }
+
+    #[test]
+    fn test_devices_paginator() {
+        let mut server = mockito::Server::new();
+        let mut first_page: serde_json::Value =
+            serde_json::from_str(&devices_body(&["device-1", "device-2"])).unwrap();
+        first_page["data"]["login"]["account"]["devices"]["hasMore"] = true.into();
+        let mocks = [
+            mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "limit": 2, "offset": 0 }),
+                &first_page.to_string(),
+            ),
+            mock_operation(
+                &mut server,
+                "GetDevices",
+                &serde_json::json!({ "limit": 2, "offset": 2 }),
+                &devices_body(&["device-3"]),
+            ),
+        ];
+        let client = get_mock_client(&server);
+        let mut paginator = client.devices_paginator().page_size(2).call();
+
+        let ids = |page: Vec<Device>| page.into_iter().map(|d| d.id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(paginator.next_page().unwrap().unwrap()),
+            ["device-1", "device-2"]
+        );
+        assert_eq!(paginator.cursor(), Some(&PageCursor::Offset(2)));
+        assert_eq!(ids(paginator.next_page().unwrap().unwrap()), ["device-3"]);
+        assert!(paginator.is_exhausted());
+        assert!(paginator.next_page().unwrap().is_none());
+
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_devices_paginator_stops_on_empty_page() {
+        let mut server = mockito::Server::new();
+        let mut empty_page: serde_json::Value = serde_json::from_str(&devices_body(&[])).unwrap();
+        empty_page["data"]["login"]["account"]["devices"]["hasMore"] = true.into();
+        let mock = mock_operation(
+            &mut server,
+            "GetDevices",
+            &serde_json::json!({ "limit": 2, "offset": 0 }),
+            &empty_page.to_string(),
+        );
+        let client = get_mock_client(&server);
+        let mut paginator = client.devices_paginator().page_size(2).call();
+
+        assert!(paginator.next_page().unwrap().unwrap().is_empty());
+        assert!(paginator.is_exhausted());
+        assert!(paginator.next_page().unwrap().is_none());
+        mock.assert();
+    }
+
+    #[test]
+    fn test_jobs_paginator() {
+        let mut server = mockito::Server::new();
+        let mocks = [
+            mock_operation(
+                &mut server,
+                "GetJobs",
+                &serde_json::json!({ "limit": 2, "after": null }),
+                &jobs_body(&["job-1", "job-2"], Some("cursor-2"), true),
+            ),
+            mock_operation(
+                &mut server,
+                "GetJobs",
+                &serde_json::json!({ "limit": 2, "after": "cursor-2" }),
+                &jobs_body(&["job-3"], Some("cursor-3"), false),
+            ),
+        ];
+        let client = get_mock_client(&server);
+        let mut paginator = client.jobs_paginator().page_size(2).call();
+
+        let mut ids = Vec::new();
+        while let Some(page) = paginator.next_page().unwrap() {
+            ids.extend(page.into_iter().map(|job| job.id));
+        }
+        assert_eq!(ids, ["job-1", "job-2", "job-3"]);
+
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_jobs_paginator_stops_on_empty_page() {
+        let mut server = mockito::Server::new();
+        let mock = mock_operation(
+            &mut server,
+            "GetJobs",
+            &serde_json::json!({ "limit": 2, "after": null }),
+            &jobs_body(&[], Some("cursor-0"), true),
+        );
+        let client = get_mock_client(&server);
+        let mut paginator = client.jobs_paginator().page_size(2).call();
+
+        assert!(paginator.next_page().unwrap().unwrap().is_empty());
+        assert!(paginator.is_exhausted());
+        assert!(paginator.next_page().unwrap().is_none());
+        mock.assert();
+    }
+
+    #[test]
+    fn test_get_all_jobs() {
+        let mut server = mockito::Server::new();
+        let mocks = [
+            mock_operation(
+                &mut server,
+                "GetJobs",
+                &serde_json::json!({ "limit": 2, "after": null, "statuses": ["SUCCESS"] }),
+                &jobs_body(&["job-1", "job-2"], Some("cursor-2"), true),
+            ),
+            mock_operation(
+                &mut server,
+                "GetJobs",
+                &serde_json::json!({ "limit": 2, "after": "cursor-2", "statuses": ["SUCCESS"] }),
+                &jobs_body(&["job-3"], None, false),
+            ),
+        ];
+
+        let jobs = get_mock_client(&server)
+            .get_all_jobs()
+            .page_size(2)
             .status_filter(vec![get_jobs::JobStatusEnum::SUCCESS])
             .call()
             .unwrap();
-        assert!(response.data.is_some());
-        assert!(response.errors.is_none());
+
+        let ids: Vec<&str> = jobs.iter().map(|job| job.id.as_str()).collect();
+        assert_eq!(ids, ["job-1", "job-2", "job-3"]);
+        for mock in mocks {
+            mock.assert();
+        }
     }
 
     #[test]
-    fn test_get_application_types() {
-        let response = get_client().get_application_types().call().unwrap();
-        dbg!(&response);
-        assert!(response.data.is_some());
-        assert!(response.errors.is_none());
+    fn test_get_job_summary() {
+        let mut server = mockito::Server::new();
+        let total = |total: i64| serde_json::json!({ "total": total });
+        let body = serde_json::json!({ "data": { "login": { "account": {
+            "waiting": total(0), "running": total(3), "failed": total(1),
+            "success": total(10), "cancelled": total(2)
+        } } } });
+        let mock = mock_operation(
+            &mut server,
+            "GetJobSummary",
+            &serde_json::json!({ "orgId": "org-1" }),
+            &body.to_string(),
+        );
+
+        let summary = get_mock_client(&server)
+            .get_job_summary()
+            .org_id("org-1".to_string())
+            .call()
+            .unwrap();
+
+        assert_eq!(summary.len(), crate::operations::all_job_statuses().len());
+        assert_eq!(summary[&get_jobs::JobStatusEnum::WAITING], 0);
+        assert_eq!(summary[&get_jobs::JobStatusEnum::RUNNING], 3);
+        assert_eq!(summary[&get_jobs::JobStatusEnum::FAILED], 1);
+        assert_eq!(summary[&get_jobs::JobStatusEnum::SUCCESS], 10);
+        assert_eq!(summary[&get_jobs::JobStatusEnum::CANCELLED], 2);
+        mock.assert();
     }
 
     #[test]
-    fn test_get_devices() {
-        let response = get_client().get_devices().call().unwrap();
-        assert!(response.data.is_some());
-        assert!(response.errors.is_none());
+    fn test_shutdown_and_rebuild() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("POST", GRAPHQL_PATH)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "data": { "login": { "account": { "devices": { "total": 3 } } } } }"#)
+            .expect(2)
+            .create();
+
+        let client = get_mock_client(&server);
+        assert_eq!(client.get_device_count().call().unwrap(), 3);
+        client.shutdown();
+
+        let client = get_mock_client(&server);
+        assert_eq!(client.get_device_count().call().unwrap(), 3);
+        client.shutdown();
+        mock.assert();
     }
 }
@@ -6,19 +6,166 @@ use base64::Engine;
 use bon::builder;
 use chrono::Utc;
 use reqwest::Method;
-use ring::hmac;
+use subtle::ConstantTimeEq;
+
+use crate::crypto::{HmacAlgorithm, HmacKey};
+
+/// An implementation of HMAC, which is used to sign requests in [`create_signature`].
+///
+/// [`RingHmac`] is used by default. Enable the `hmac-pure` feature to use [`PureHmac`] instead,
+/// which also builds for targets that `ring` doesn't support, like `wasm32-unknown-unknown`.
+/// [`HmacKey`] always uses the same implementation as [`DefaultHmac`].
+/// Use [`create_signature_with_backend`] to pick a backend explicitly.
+pub trait HmacBackend {
+    /// # Returns
+    /// The HMAC of `message` with the given `key` and `algorithm`.
+    fn hmac(algorithm: HmacAlgorithm, key: &[u8], message: &[u8]) -> Vec<u8>;
+
+    /// # Returns
+    /// The HMAC-SHA256 of `message` with the given `key`.
+    #[must_use]
+    fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+        Self::hmac(HmacAlgorithm::Sha256, key, message)
+    }
+}
+
+/// Enabled by the `ring` feature. [`HmacBackend`] using `ring`.
+#[cfg(feature = "ring")]
+pub struct RingHmac;
+
+#[cfg(feature = "ring")]
+impl HmacBackend for RingHmac {
+    fn hmac(algorithm: HmacAlgorithm, key: &[u8], message: &[u8]) -> Vec<u8> {
+        let key = ring::hmac::Key::new(algorithm.ring_algorithm(), key);
+        ring::hmac::sign(&key, message).as_ref().to_vec()
+    }
+}
+
+/// Enabled by the `hmac-pure` feature. [`HmacBackend`] using the pure Rust `hmac` and `sha2` crates.
+#[cfg(feature = "hmac-pure")]
+pub struct PureHmac;
+
+#[cfg(feature = "hmac-pure")]
+impl HmacBackend for PureHmac {
+    fn hmac(algorithm: HmacAlgorithm, key: &[u8], message: &[u8]) -> Vec<u8> {
+        crate::crypto::pure_hmac(algorithm, key, message)
+    }
+}
+
+/// The [`HmacBackend`] used by [`create_signature`]. [`PureHmac`] if the `hmac-pure` feature is enabled, [`RingHmac`] otherwise.
+#[cfg(feature = "hmac-pure")]
+pub type DefaultHmac = PureHmac;
+/// The [`HmacBackend`] used by [`create_signature`]. [`PureHmac`] if the `hmac-pure` feature is enabled, [`RingHmac`] otherwise.
+#[cfg(not(feature = "hmac-pure"))]
+pub type DefaultHmac = RingHmac;
 
 /// You probably don't want to use this function directly, unless you are implementing your own abstraction over the remote.it API.
 ///
 /// Signs the given `message` with the given `key` with the HMAC algorithm and base64-encodes the result.
+/// The HMAC is computed by the [`DefaultHmac`] backend.
 ///
 /// # Returns
 /// Base64 encoded HMAC signature.
 #[must_use]
 pub fn create_signature(key: &[u8], message: &str) -> String {
-    let signing_key = hmac::Key::new(hmac::HMAC_SHA256, key);
-    let signature = hmac::sign(&signing_key, message.as_bytes());
-    BASE64_STANDARD.encode(signature.as_ref())
+    create_signature_with_backend::<DefaultHmac>(key, message)
+}
+
+/// Same as [`create_signature`], but computes the HMAC with the given [`HmacBackend`].
+///
+/// # Returns
+/// Base64 encoded HMAC signature.
+#[must_use]
+pub fn create_signature_with_backend<B: HmacBackend>(key: &[u8], message: &str) -> String {
+    BASE64_STANDARD.encode(B::hmac_sha256(key, message.as_bytes()))
+}
+
+/// You probably don't want to use this function directly, unless you are implementing your own abstraction over the remote.it API.
+///
+/// Same as [`create_signature`], but uses an already derived HMAC key, like the one from [`Credentials::signing_key`](crate::Credentials::signing_key).
+///
+/// # Returns
+/// Base64 encoded HMAC signature.
+#[must_use]
+pub fn create_signature_with_key(key: &HmacKey, message: &str) -> String {
+    BASE64_STANDARD.encode(key.sign(message.as_bytes()))
+}
+
+/// Compares two byte slices in constant time.
+///
+/// Comparing secrets like keys or signatures with `==` returns as soon as the first byte differs.
+/// By measuring how long a comparison takes, an attacker can then guess the secret byte by byte (a timing attack).
+/// This function always takes the same time for inputs of the same length. Only the length of the inputs is not kept secret.
+///
+/// # Returns
+/// `true` if both slices have the same length and contents.
+#[must_use]
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+/// You probably don't want to use this function directly, unless you are implementing your own abstraction over the remote.it API.
+///
+/// Checks whether `signature` is the signature of `message` with the given `key`, as created by [`create_signature_with_key`].
+/// The signatures are compared with [`constant_time_eq`].
+///
+/// # Returns
+/// `true` if the signature is valid.
+#[must_use]
+pub fn verify_signature(key: &HmacKey, message: &str, signature: &str) -> bool {
+    let expected = create_signature_with_key(key, message);
+    constant_time_eq(expected.as_bytes(), signature.as_bytes())
+}
+
+/// A header, that can be included in the request signature of [`build_auth_header`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignedHeader {
+    /// The pseudo-header `(request-target)`, i.e. the lowercase method and the path.
+    RequestTarget,
+    /// The `host` header. This is `api.remote.it`, unless another host is passed to [`build_auth_header`].
+    Host,
+    /// The `date` header.
+    Date,
+    /// The `content-type` header.
+    ContentType,
+    /// The `digest` header. Only included, if a digest is passed to [`build_auth_header`].
+    Digest,
+}
+
+impl SignedHeader {
+    /// The headers, that [`build_auth_header`] signs by default, in this order.
+    /// [`SignedHeader::Digest`] is only included, if a digest is passed.
+    pub const DEFAULT: [SignedHeader; 5] = [
+        SignedHeader::RequestTarget,
+        SignedHeader::Host,
+        SignedHeader::Date,
+        SignedHeader::ContentType,
+        SignedHeader::Digest,
+    ];
+
+    /// # Returns
+    /// The name of the header, as used in the `headers` list of the signature.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            SignedHeader::RequestTarget => "(request-target)",
+            SignedHeader::Host => "host",
+            SignedHeader::Date => "date",
+            SignedHeader::ContentType => "content-type",
+            SignedHeader::Digest => "digest",
+        }
+    }
+}
+
+/// # Returns
+/// The label of the given HMAC algorithm, as used in the `algorithm` field of the signature, e.g. `hmac-sha256`.
+#[must_use]
+pub fn hmac_algorithm_label(algorithm: HmacAlgorithm) -> &'static str {
+    match algorithm {
+        HmacAlgorithm::Sha256 => "hmac-sha256",
+        HmacAlgorithm::Sha384 => "hmac-sha384",
+        HmacAlgorithm::Sha512 => "hmac-sha512",
+    }
 }
 
 /// You probably don't want to use this function directly, unless you are implementing your own abstraction over the remote.it API.
@@ -45,7 +192,7 @@ pub fn create_signature(key: &[u8], message: &str) -> String {
 /// let date = remoteit_api::auth::get_date();
 /// let auth_header = remoteit_api::auth::build_auth_header()
 ///     .key_id(credentials.access_key_id())
-///     .key(credentials.key())
+///     .key(credentials.signing_key())
 ///     .content_type("application/json")
 ///     .method(&Method::POST)
 ///     .path(GRAPHQL_PATH)
@@ -56,22 +203,62 @@ pub fn create_signature(key: &[u8], message: &str) -> String {
 #[builder]
 pub fn build_auth_header(
     key_id: &str,
-    key: &[u8],
+    key: &HmacKey,
     content_type: &str,
     method: &Method,
     path: &str,
     date: &str,
+    /// Optional value of the `Digest` header, as created by [`create_digest`].
+    /// If set, the digest is included in the signed headers. The same value must be sent in the `Digest` header.
+    digest: Option<&str>,
+    /// Optional label for the `algorithm` field of the signature.
+    /// Defaults to the label of the algorithm of `key` (see [`hmac_algorithm_label`]), so it matches the algorithm that is actually used.
+    /// Only override this, if the server expects a different name for the same algorithm.
+    algorithm_label: Option<&str>,
+    /// Optional list of the headers to sign, in this order. Defaults to [`SignedHeader::DEFAULT`].
+    /// [`SignedHeader::Digest`] is skipped, if no `digest` is passed.
+    signed_headers: Option<&[SignedHeader]>,
+    /// Optional value of the `host` header. Defaults to `api.remote.it`, the host of the production API.
+    /// See [`Environment::host`](crate::Environment::host) for other environments.
+    host: Option<&str>,
 ) -> String {
-    let signature_params =
-        format!(
-            "(request-target): {} {path}\nhost: api.remote.it\ndate: {date}\ncontent-type: {content_type}",
-            method.to_string().to_lowercase()
-        );
-    #[cfg(debug_assertions)]
-    dbg!(&signature_params);
-    let signature = create_signature(key, &signature_params);
+    let method = method.to_string().to_lowercase();
+    let mut header_names = Vec::new();
+    let mut signature_lines = Vec::new();
+    for header in signed_headers.unwrap_or(&SignedHeader::DEFAULT) {
+        let value = match header {
+            SignedHeader::RequestTarget => format!("{method} {path}"),
+            SignedHeader::Host => host.unwrap_or("api.remote.it").to_string(),
+            SignedHeader::Date => date.to_string(),
+            SignedHeader::ContentType => content_type.to_string(),
+            SignedHeader::Digest => match digest {
+                Some(digest) => digest.to_string(),
+                None => continue,
+            },
+        };
+        header_names.push(header.name());
+        signature_lines.push(format!("{}: {value}", header.name()));
+    }
+    let signed_headers = header_names.join(" ");
+    let signature_params = signature_lines.join("\n");
+    #[cfg(feature = "log")]
+    log::trace!("Signing headers `{signed_headers}` with params {signature_params:?}");
+    let signature = create_signature_with_key(key, &signature_params);
+    let algorithm = algorithm_label.unwrap_or_else(|| hmac_algorithm_label(key.algorithm()));
     format!(
-        "Signature keyId=\"{key_id}\",algorithm=\"hmac-sha256\",headers=\"(request-target) host date content-type\",signature=\"{signature}\"")
+        "Signature keyId=\"{key_id}\",algorithm=\"{algorithm}\",headers=\"{signed_headers}\",signature=\"{signature}\"")
+}
+
+/// You probably don't want to use this function directly, unless you are implementing your own abstraction for making requests to the remote.it API.
+///
+/// Creates the value of the `Digest` header for the given request body.
+///
+/// # Returns
+/// The SHA-256 hash of the body, in the form `SHA-256=<base64>`.
+#[must_use]
+pub fn create_digest(body: &[u8]) -> String {
+    let hash = crate::crypto::Sha256::digest(body);
+    format!("SHA-256={}", BASE64_STANDARD.encode(hash))
 }
 
 /// You probably don't want to use this function directly, unless you are implementing your own abstraction for making requests to the remote.it API.
@@ -83,5 +270,255 @@ pub fn build_auth_header(
 /// # Returns
 /// A date string (now) in the format required by the remote.it API.
 pub fn get_date() -> String {
-    Utc::now().format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+    get_date_with_offset(chrono::Duration::zero())
+}
+
+/// Same as [`get_date`], but shifted by the given offset.
+/// This is used to compensate for a local clock, that is out of sync with the clock of the remote.it API.
+/// [`R3Client`](crate::R3Client) signs its requests with this, using the offset measured by `check_clock_skew` or set with [`R3Client::with_clock_skew`](crate::R3Client::with_clock_skew).
+///
+/// # Returns
+/// A date string (now + `offset`) in the format required by the remote.it API.
+#[must_use]
+pub fn get_date_with_offset(offset: chrono::Duration) -> String {
+    (Utc::now() + offset)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Collects the messages of all log records, so tests can check what this crate logs.
+    struct CaptureLogger(Mutex<Vec<String>>);
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CaptureLogger = CaptureLogger(Mutex::new(Vec::new()));
+
+    #[test]
+    fn test_create_signature() {
+        assert_eq!(
+            create_signature(b"bar", "foo"),
+            "FHkzIYqqvAuLEKKzpcNGhMjZQ0G88QpHNtxycPd0GFE="
+        );
+    }
+
+    #[test]
+    fn test_create_signature_with_key_matches() {
+        let credentials = crate::Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        assert_eq!(
+            create_signature_with_key(credentials.signing_key(), "foo"),
+            create_signature(credentials.key(), "foo")
+        );
+    }
+
+    /// Test case 2 of RFC 4231, base64 encoded.
+    const RFC_4231_SIGNATURE: &str = "W9zBRr9gdU5qBCQmCJV1x1oAPwidJzmDnexYuWTsOEM=";
+
+    #[cfg(feature = "ring")]
+    #[test]
+    fn test_ring_hmac_test_vector() {
+        assert_eq!(
+            create_signature_with_backend::<RingHmac>(b"Jefe", "what do ya want for nothing?"),
+            RFC_4231_SIGNATURE
+        );
+    }
+
+    #[cfg(feature = "hmac-pure")]
+    #[test]
+    fn test_pure_hmac_test_vector() {
+        assert_eq!(
+            create_signature_with_backend::<PureHmac>(b"Jefe", "what do ya want for nothing?"),
+            RFC_4231_SIGNATURE
+        );
+        assert_eq!(
+            create_signature_with_backend::<PureHmac>(b"bar", "foo"),
+            create_signature(b"bar", "foo")
+        );
+        for algorithm in [HmacAlgorithm::Sha384, HmacAlgorithm::Sha512] {
+            assert_eq!(
+                PureHmac::hmac(algorithm, b"bar", b"foo"),
+                HmacKey::new(algorithm, b"bar").sign(b"foo")
+            );
+        }
+    }
+
+    #[cfg(all(feature = "ring", feature = "hmac-pure"))]
+    #[test]
+    fn test_pure_hmac_matches_ring() {
+        for algorithm in [
+            HmacAlgorithm::Sha256,
+            HmacAlgorithm::Sha384,
+            HmacAlgorithm::Sha512,
+        ] {
+            assert_eq!(
+                PureHmac::hmac(algorithm, b"bar", b"foo"),
+                RingHmac::hmac(algorithm, b"bar", b"foo")
+            );
+        }
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(b"foo", b"foo"));
+        assert!(!constant_time_eq(b"foo", b"fox"));
+        assert!(!constant_time_eq(b"foo", b"foobar"));
+        assert!(!constant_time_eq(b"", b"foo"));
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let key = HmacKey::new(HmacAlgorithm::Sha256, b"bar");
+        let signature = create_signature(b"bar", "foo");
+
+        assert!(verify_signature(&key, "foo", &signature));
+        assert!(!verify_signature(&key, "fox", &signature));
+        assert!(!verify_signature(&key, "foo", &signature[1..]));
+    }
+
+    #[test]
+    fn test_create_digest() {
+        // echo -n 'foo' | openssl dgst -sha256 -binary | base64
+        assert_eq!(
+            create_digest(b"foo"),
+            "SHA-256=LCa0a2j/xo/5m0U8HTBBNBNCLXBkg7+g+YpeiGJm564="
+        );
+    }
+
+    #[test]
+    fn test_build_auth_header_with_digest() {
+        let key = HmacKey::new(HmacAlgorithm::Sha256, b"bar");
+        let digest = create_digest(b"{}");
+
+        let header = build_auth_header()
+            .key_id("foo")
+            .key(&key)
+            .content_type("application/json")
+            .method(&Method::POST)
+            .path("/graphql/v1")
+            .date("Fri, 30 Aug 2024 12:00:00 GMT")
+            .digest(&digest)
+            .call();
+
+        let expected_signature = create_signature(
+            b"bar",
+            &format!("(request-target): post /graphql/v1\nhost: api.remote.it\ndate: Fri, 30 Aug 2024 12:00:00 GMT\ncontent-type: application/json\ndigest: {digest}"),
+        );
+        assert!(header.contains("headers=\"(request-target) host date content-type digest\""));
+        assert!(header.contains(&format!("signature=\"{expected_signature}\"")));
+    }
+
+    #[test]
+    fn test_build_auth_header_logging() {
+        // Other tests may have installed the logger already.
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Trace);
+        let key = HmacKey::new(HmacAlgorithm::Sha256, b"bar");
+
+        build_auth_header()
+            .key_id("foo")
+            .key(&key)
+            .content_type("application/json")
+            .method(&Method::POST)
+            .path("/logging-test")
+            .date("Fri, 30 Aug 2024 12:00:00 GMT")
+            .call();
+
+        let records: Vec<String> = LOGGER
+            .0
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|message| message.contains("/logging-test"))
+            .cloned()
+            .collect();
+        #[cfg(not(feature = "log"))]
+        assert!(records.is_empty());
+        #[cfg(feature = "log")]
+        {
+            assert_eq!(records.len(), 1);
+            assert!(!records[0].contains("bar"));
+        }
+    }
+
+    #[test]
+    fn test_build_auth_header_default_label() {
+        let key = HmacKey::new(HmacAlgorithm::Sha256, b"bar");
+        let date = "Fri, 30 Aug 2024 12:00:00 GMT";
+
+        let header = build_auth_header()
+            .key_id("foo")
+            .key(&key)
+            .content_type("application/json")
+            .method(&Method::POST)
+            .path("/graphql/v1")
+            .date(date)
+            .call();
+
+        let expected_signature = create_signature(
+            b"bar",
+            &format!("(request-target): post /graphql/v1\nhost: api.remote.it\ndate: {date}\ncontent-type: application/json"),
+        );
+        assert_eq!(
+            header,
+            format!("Signature keyId=\"foo\",algorithm=\"hmac-sha256\",headers=\"(request-target) host date content-type\",signature=\"{expected_signature}\"")
+        );
+    }
+
+    #[test]
+    fn test_build_auth_header_custom_label_and_headers() {
+        let key = HmacKey::new(HmacAlgorithm::Sha256, b"bar");
+        let date = "Fri, 30 Aug 2024 12:00:00 GMT";
+
+        let header = build_auth_header()
+            .key_id("foo")
+            .key(&key)
+            .content_type("application/json")
+            .method(&Method::POST)
+            .path("/graphql/v1")
+            .date(date)
+            .algorithm_label("HMAC-SHA256")
+            .signed_headers(&[
+                SignedHeader::Date,
+                SignedHeader::RequestTarget,
+                SignedHeader::Digest,
+            ])
+            .call();
+
+        let expected_signature = create_signature(
+            b"bar",
+            &format!("date: {date}\n(request-target): post /graphql/v1"),
+        );
+        assert!(header.contains("algorithm=\"HMAC-SHA256\""));
+        assert!(header.contains("headers=\"date (request-target)\""));
+        assert!(header.contains(&format!("signature=\"{expected_signature}\"")));
+    }
+
+    #[test]
+    fn test_hmac_algorithm_label() {
+        assert_eq!(hmac_algorithm_label(HmacAlgorithm::Sha256), "hmac-sha256");
+        assert_eq!(hmac_algorithm_label(HmacAlgorithm::Sha512), "hmac-sha512");
+        assert_eq!(
+            hmac_algorithm_label(HmacKey::new(HmacAlgorithm::Sha384, b"bar").algorithm()),
+            "hmac-sha384"
+        );
+    }
 }
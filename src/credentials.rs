@@ -2,9 +2,16 @@
 //! This is of course not the most secure way to store credentials, but it is the most convenient and recommended by remote.it.
 //! If you store your credentials in a different way, you can pass them to the functions in this module directly instead of using this module to load them.
 
-use base64::prelude::BASE64_STANDARD;
+use crate::crypto::{HmacAlgorithm, HmacKey};
+use crate::R3Error;
+use base64::engine::GeneralPurpose;
+use base64::prelude::{
+    BASE64_STANDARD, BASE64_STANDARD_NO_PAD, BASE64_URL_SAFE, BASE64_URL_SAFE_NO_PAD,
+};
 use base64::Engine;
 use bon::bon;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 
 /// Credentials for the remote.it API.
 /// Remote.it credentials consist of an access key ID and a base64 encoded secret access key.
@@ -24,22 +31,97 @@ use bon::bon;
 /// let creds_from_default_loc = Credentials::load_from_disk().call().unwrap();
 /// let creds_from_custom_loc = Credentials::load_from_disk().custom_credentials_path(".env.remoteit").call().unwrap();
 /// ```
-#[derive(
-    Debug, Clone, PartialOrd, PartialEq, Eq, Ord, Hash, serde::Deserialize, serde::Serialize,
-)]
+///
+/// [`Credentials`] intentionally don't implement [`serde::Serialize`], so the secret access key can't end up in serialized data by accident.
+/// Use [`Credentials::public`] to get a view without the secret, which can be serialized safely.
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq, Ord, Hash, serde::Deserialize)]
 pub struct Credentials {
     pub(crate) r3_access_key_id: String,
     pub(crate) r3_secret_access_key: String,
-    #[serde(skip)] // Don't want to serialize this one
+    #[serde(skip)]
     pub(crate) key: Vec<u8>,
+    #[serde(skip)]
+    pub(crate) signing_key: SigningKey,
+}
+
+/// The environment variable, from which [`Credentials::from_env`] reads the access key ID.
+pub const ACCESS_KEY_ID_ENV_VAR: &str = "R3_ACCESS_KEY_ID";
+
+/// The environment variable, from which [`Credentials::from_env`] reads the secret access key.
+pub const SECRET_ACCESS_KEY_ENV_VAR: &str = "R3_SECRET_ACCESS_KEY";
+
+/// A view of [`Credentials`] without the secret access key.
+/// It is safe to serialize, e.g. into application state, logs or telemetry.
+///
+/// Obtain it using [`Credentials::public`].
+#[derive(
+    Debug, Clone, PartialOrd, PartialEq, Eq, Ord, Hash, serde::Deserialize, serde::Serialize,
+)]
+pub struct CredentialsPublic {
+    /// The access key ID of the credentials.
+    pub r3_access_key_id: String,
+}
+
+/// The HMAC key derived from the secret access key.
+/// It is computed once when the [`Credentials`] are created, so it doesn't have to be re-derived for every request.
+///
+/// Since it is derived from [`Credentials::key`], it is ignored for comparisons and hashing.
+#[derive(Clone)]
+pub(crate) struct SigningKey(pub(crate) HmacKey);
+
+impl SigningKey {
+    fn new(key: &[u8]) -> Self {
+        Self(HmacKey::new(HmacAlgorithm::Sha256, key))
+    }
+}
+
+impl Default for SigningKey {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl std::fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SigningKey(..)")
+    }
+}
+
+impl PartialEq for SigningKey {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for SigningKey {}
+
+impl PartialOrd for SigningKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SigningKey {
+    fn cmp(&self, _other: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+impl Hash for SigningKey {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
 }
 
 #[bon]
 impl Credentials {
     /// Validated the given secret access key and creates a new [`Credentials`] struct.
     ///
+    /// The secret access key may be encoded with the standard base64 alphabet (as provided by remote.it),
+    /// or with the url-safe alphabet, each with or without the trailing `=` padding.
+    /// Since the two alphabets only differ in the characters `+/` and `-_`, this is never ambiguous.
+    ///
     /// # Errors
     /// - [`base64::DecodeError`] if the secret access key is not base64 encoded.
+    ///   This is the error from decoding with the standard alphabet.
     ///
     /// # Example
     /// ```
@@ -54,14 +136,37 @@ impl Credentials {
         r3_access_key_id: String,
         r3_secret_access_key: String,
     ) -> Result<Self, base64::DecodeError> {
-        let key = BASE64_STANDARD.decode(&r3_secret_access_key)?;
+        let key = decode_secret_access_key(&r3_secret_access_key)?;
+        let signing_key = SigningKey::new(&key);
         Ok(Self {
             r3_access_key_id,
             r3_secret_access_key,
             key,
+            signing_key,
         })
     }
 
+    /// Reads the credentials from the environment variables [`ACCESS_KEY_ID_ENV_VAR`] and [`SECRET_ACCESS_KEY_ENV_VAR`].
+    /// These are named like the keys of the remote.it credentials file.
+    ///
+    /// # Errors
+    /// - [`R3Error::EnvVarNotSet`] if one of the environment variables is not set, or not valid unicode.
+    /// - [`R3Error::InvalidSecretAccessKey`] if the secret access key is not base64 encoded.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use remoteit_api::Credentials;
+    /// let credentials = Credentials::from_env().unwrap();
+    /// ```
+    pub fn from_env() -> Result<Self, R3Error> {
+        let env_var =
+            |name: &str| std::env::var(name).map_err(|_| R3Error::EnvVarNotSet(name.to_string()));
+        Ok(Credentials::builder()
+            .r3_access_key_id(env_var(ACCESS_KEY_ID_ENV_VAR)?)
+            .r3_secret_access_key(env_var(SECRET_ACCESS_KEY_ENV_VAR)?)
+            .build()?)
+    }
+
     /// # Returns
     /// The base64 decoded secret access key.
     #[must_use]
@@ -69,6 +174,13 @@ impl Credentials {
         &self.key
     }
 
+    /// # Returns
+    /// The HMAC-SHA256 key derived from the secret access key. It is precomputed, so signing requests doesn't need to derive it again.
+    #[must_use]
+    pub fn signing_key(&self) -> &HmacKey {
+        &self.signing_key.0
+    }
+
     /// # Returns
     /// A reference to the r3_access_key_id
     pub fn access_key_id(&self) -> &str {
@@ -80,6 +192,31 @@ impl Credentials {
     pub fn secret_access_key(&self) -> &str {
         &self.r3_secret_access_key
     }
+
+    /// # Returns
+    /// A [`CredentialsPublic`] view of these credentials, which doesn't contain the secret access key.
+    #[must_use]
+    pub fn public(&self) -> CredentialsPublic {
+        CredentialsPublic {
+            r3_access_key_id: self.r3_access_key_id.clone(),
+        }
+    }
+}
+
+/// The base64 engines tried for the secret access key, if it is not encoded with [`BASE64_STANDARD`].
+const FALLBACK_ENGINES: [GeneralPurpose; 3] = [
+    BASE64_STANDARD_NO_PAD,
+    BASE64_URL_SAFE,
+    BASE64_URL_SAFE_NO_PAD,
+];
+
+fn decode_secret_access_key(secret_access_key: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    BASE64_STANDARD.decode(secret_access_key).or_else(|e| {
+        FALLBACK_ENGINES
+            .iter()
+            .find_map(|engine| engine.decode(secret_access_key).ok())
+            .ok_or(e)
+    })
 }
 
 #[cfg(test)]
@@ -97,4 +234,118 @@ mod tests {
         assert_eq!(credentials.r3_access_key_id, "foo");
         assert_eq!(credentials.r3_secret_access_key, "YmFy");
     }
+
+    #[test]
+    fn test_credentials_url_safe_secret() {
+        let key = [0xfb, 0xff, 0xbf, 0x3e, 0x01];
+        let standard = BASE64_STANDARD.encode(key);
+        let url_safe = BASE64_URL_SAFE.encode(key);
+        let url_safe_no_pad = BASE64_URL_SAFE_NO_PAD.encode(key);
+        assert_eq!(standard, "+/+/PgE=");
+        assert_eq!(url_safe, "-_-_PgE=");
+
+        for secret in [standard, url_safe, url_safe_no_pad] {
+            let credentials = Credentials::builder()
+                .r3_access_key_id("foo")
+                .r3_secret_access_key(secret.clone())
+                .build()
+                .unwrap();
+            assert_eq!(credentials.key(), key, "{secret}");
+        }
+
+        let result = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("+/-_")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_credentials_unpadded_secret() {
+        let key = [0xfb, 0xff, 0xbf, 0x3e, 0x01];
+        let padded = BASE64_STANDARD.encode(key);
+        let unpadded = BASE64_STANDARD_NO_PAD.encode(key);
+        assert_eq!(padded, "+/+/PgE=");
+        assert_eq!(unpadded, "+/+/PgE");
+
+        for secret in [padded, unpadded] {
+            let credentials = Credentials::builder()
+                .r3_access_key_id("foo")
+                .r3_secret_access_key(secret.clone())
+                .build()
+                .unwrap();
+            assert_eq!(credentials.key(), key, "{secret}");
+        }
+    }
+
+    #[test]
+    fn test_credentials_public() {
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+
+        let serialized = serde_json::to_string(&credentials.public()).unwrap();
+
+        assert_eq!(serialized, r#"{"r3_access_key_id":"foo"}"#);
+        assert!(!serialized.contains("YmFy"));
+        assert!(!serialized.contains("bar"));
+    }
+
+    /// Restores an environment variable to its previous value when dropped.
+    struct EnvGuard(&'static str, Option<String>);
+
+    impl EnvGuard {
+        fn set(name: &'static str, value: Option<&str>) -> Self {
+            let guard = Self(name, std::env::var(name).ok());
+            match value {
+                Some(value) => std::env::set_var(name, value),
+                None => std::env::remove_var(name),
+            }
+            guard
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            match &self.1 {
+                Some(value) => std::env::set_var(self.0, value),
+                None => std::env::remove_var(self.0),
+            }
+        }
+    }
+
+    #[test]
+    fn test_credentials_from_env() {
+        let _key_id = EnvGuard::set(ACCESS_KEY_ID_ENV_VAR, Some("foo"));
+        let secret = EnvGuard::set(SECRET_ACCESS_KEY_ENV_VAR, Some("YmFy"));
+
+        let credentials = Credentials::from_env().unwrap();
+        assert_eq!(credentials.access_key_id(), "foo");
+        assert_eq!(credentials.key(), b"bar");
+        let client = crate::R3Client::from_env().unwrap();
+        assert_eq!(client.credentials(), &credentials);
+        #[cfg(feature = "credentials_loader")]
+        {
+            let client = crate::R3Client::try_new()
+                .credentials_from_env(true)
+                .call()
+                .unwrap();
+            assert_eq!(client.credentials(), &credentials);
+        }
+
+        std::env::set_var(SECRET_ACCESS_KEY_ENV_VAR, "not base64!");
+        assert!(matches!(
+            Credentials::from_env(),
+            Err(R3Error::InvalidSecretAccessKey(_))
+        ));
+
+        drop(secret);
+        let _secret = EnvGuard::set(SECRET_ACCESS_KEY_ENV_VAR, None);
+        assert!(matches!(
+            Credentials::from_env(),
+            Err(R3Error::EnvVarNotSet(name)) if name == SECRET_ACCESS_KEY_ENV_VAR
+        ));
+    }
 }
@@ -0,0 +1,129 @@
+//! Enabled by the `keyring` feature. Contains items related to storing credentials in the keychain of the operating system.
+//!
+//! Please see [`Credentials::load_from_keyring`] and [`Credentials::save_to_keyring`] for more.
+
+use crate::credentials::Credentials;
+use crate::R3Error;
+
+/// How the credentials are stored in a keyring entry.
+/// [`Credentials`] don't implement [`serde::Serialize`], so the secret access key is copied in here explicitly.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct KeyringEntry {
+    r3_access_key_id: String,
+    r3_secret_access_key: String,
+}
+
+impl Credentials {
+    /// Loads the credentials of the given profile from the keychain of the operating system.
+    ///
+    /// The entry has to be created using [`Credentials::save_to_keyring`] first.
+    /// The secret access key is validated just like in [`Credentials::builder`].
+    ///
+    /// # Platform support
+    /// - macOS: The login Keychain.
+    /// - Windows: The Credential Manager.
+    /// - Linux: The kernel keyutils session keyring. Entries do **not** survive a reboot.
+    ///
+    /// On other platforms, entries are not stored at all.
+    ///
+    /// # Errors
+    /// - [`R3Error::Keyring`] if the entry does not exist or the keyring could not be accessed.
+    /// - [`R3Error::InvalidKeyringEntry`] if the entry was not created by [`Credentials::save_to_keyring`].
+    /// - [`R3Error::InvalidSecretAccessKey`] if the stored secret access key is not base64 encoded.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use remoteit_api::Credentials;
+    /// let credentials = Credentials::load_from_keyring("remoteit", "default").unwrap();
+    /// ```
+    pub fn load_from_keyring(service: &str, profile: &str) -> Result<Self, R3Error> {
+        Self::load_from_keyring_entry(&keyring::Entry::new(service, profile)?)
+    }
+
+    /// Same as [`Credentials::load_from_keyring`], but loads the credentials from the given [`keyring::Entry`].
+    /// Use this if you need more control over where the entry is stored.
+    ///
+    /// # Errors
+    /// See [`Credentials::load_from_keyring`].
+    pub fn load_from_keyring_entry(entry: &keyring::Entry) -> Result<Self, R3Error> {
+        let stored: KeyringEntry =
+            serde_json::from_str(&entry.get_password()?).map_err(R3Error::InvalidKeyringEntry)?;
+        Ok(Credentials::builder()
+            .r3_access_key_id(stored.r3_access_key_id)
+            .r3_secret_access_key(stored.r3_secret_access_key)
+            .build()?)
+    }
+
+    /// Saves the credentials under the given profile in the keychain of the operating system.
+    /// An existing entry for the same service and profile is overwritten.
+    ///
+    /// See [`Credentials::load_from_keyring`] for the supported platforms.
+    ///
+    /// # Errors
+    /// - [`R3Error::Keyring`] if the keyring could not be accessed.
+    pub fn save_to_keyring(&self, service: &str, profile: &str) -> Result<(), R3Error> {
+        self.save_to_keyring_entry(&keyring::Entry::new(service, profile)?)
+    }
+
+    /// Same as [`Credentials::save_to_keyring`], but saves the credentials to the given [`keyring::Entry`].
+    ///
+    /// # Errors
+    /// - [`R3Error::Keyring`] if the keyring could not be accessed.
+    pub fn save_to_keyring_entry(&self, entry: &keyring::Entry) -> Result<(), R3Error> {
+        let stored = KeyringEntry {
+            r3_access_key_id: self.r3_access_key_id.clone(),
+            r3_secret_access_key: self.r3_secret_access_key.clone(),
+        };
+        let serialized = serde_json::to_string(&stored).map_err(R3Error::InvalidKeyringEntry)?;
+        entry.set_password(&serialized)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock_entry() -> keyring::Entry {
+        keyring::Entry::new_with_credential(Box::<keyring::mock::MockCredential>::default())
+    }
+
+    #[test]
+    fn test_keyring_roundtrip() {
+        let entry = mock_entry();
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+
+        credentials.save_to_keyring_entry(&entry).unwrap();
+        let loaded = Credentials::load_from_keyring_entry(&entry).unwrap();
+
+        assert_eq!(loaded, credentials);
+        assert_eq!(loaded.key(), b"bar");
+    }
+
+    #[test]
+    fn test_keyring_invalid_entries() {
+        let entry = mock_entry();
+        assert!(matches!(
+            Credentials::load_from_keyring_entry(&entry),
+            Err(R3Error::Keyring(keyring::Error::NoEntry))
+        ));
+
+        entry.set_password("not json").unwrap();
+        assert!(matches!(
+            Credentials::load_from_keyring_entry(&entry),
+            Err(R3Error::InvalidKeyringEntry(_))
+        ));
+
+        entry
+            .set_password(r#"{ "r3_access_key_id": "foo", "r3_secret_access_key": "not base64!" }"#)
+            .unwrap();
+        assert!(matches!(
+            Credentials::load_from_keyring_entry(&entry),
+            Err(R3Error::InvalidSecretAccessKey(_))
+        ));
+    }
+}
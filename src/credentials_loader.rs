@@ -3,9 +3,11 @@
 //! Please see [`Credentials`] for more.
 
 use crate::credentials::Credentials;
+use crate::{Environment, R3Client, R3Error, RetryPolicy, FILE_UPLOAD_PATH, GRAPHQL_PATH};
 use bon::bon;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Errors that can occur during the loading of credentials from disk.
 #[allow(missing_docs)]
@@ -15,8 +17,18 @@ pub enum CredentialsLoaderError {
     HomeDirNotFound,
     #[error("The credentials file could not be loaded: {0}")]
     CouldNotReadCredentials(#[from] std::io::Error),
+    #[error("The credentials file does not exist: {0:?}")]
+    FileNotFound(PathBuf),
+    #[error("The credentials file can't be read due to missing permissions: {0:?}")]
+    PermissionDenied(PathBuf),
     #[error("The credentials file could not be parsed: {0}")]
     CredentialsParse(#[from] config::ConfigError),
+    #[error("The credentials path is not valid UTF-8: {0:?}")]
+    NonUtf8Path(PathBuf),
+    #[error("The environment variable `{0}` referenced by the credentials is not set")]
+    UndefinedVariable(String),
+    #[error("The secret access key of these profiles is not valid base64: {}", .0.join(", "))]
+    InvalidProfiles(Vec<String>),
 }
 
 /// This is how the credentials are saved in the file.
@@ -29,6 +41,16 @@ pub(crate) struct UnverifiedCredentials {
     pub(crate) r3_secret_access_key: String,
 }
 
+impl UnverifiedCredentials {
+    /// Validates the secret access key and builds [`Credentials`] from it.
+    fn verify(&self) -> Result<Credentials, base64::DecodeError> {
+        Credentials::builder()
+            .r3_access_key_id(&self.r3_access_key_id)
+            .r3_secret_access_key(&self.r3_secret_access_key)
+            .build()
+    }
+}
+
 /// A struct representing the remote.it credentials file.
 ///
 /// The credentials file can have multiple profiles, each with its own access key ID and secret access key.
@@ -64,11 +86,39 @@ impl CredentialProfiles {
             return Ok(None);
         };
 
-        Credentials::builder()
-            .r3_access_key_id(&unverified_credentials.r3_access_key_id)
-            .r3_secret_access_key(&unverified_credentials.r3_secret_access_key)
-            .build()
-            .map(Some)
+        unverified_credentials.verify().map(Some)
+    }
+
+    /// Same as [`CredentialProfiles::take_profile`], but matches the profile name case-insensitively and ignores surrounding whitespace.
+    /// An exact match is preferred, if there is one.
+    ///
+    /// [`CredentialProfiles::take_profile`] itself stays an exact lookup.
+    /// Note that [`Credentials::load_from_disk`] already lowercases and trims the section names of the file,
+    /// so an exact lookup of e.g. `"Default"` will not find the section `[Default]`, while this function will.
+    ///
+    /// # Returns
+    /// - [`None`] if no profile matches the given name.
+    /// - [`Some`] containing the [`Credentials`] of the matching profile.
+    ///
+    /// # Errors
+    /// - [`base64::DecodeError`] if the secret access key of the matching profile is not base64 encoded.
+    pub fn take_profile_ci(
+        &mut self,
+        profile_name: &str,
+    ) -> Result<Option<Credentials>, base64::DecodeError> {
+        if self.profiles.contains_key(profile_name) {
+            return self.take_profile(profile_name);
+        }
+        let wanted = profile_name.trim().to_lowercase();
+        let matching_name = self
+            .profiles
+            .keys()
+            .find(|name| name.trim().to_lowercase() == wanted)
+            .cloned();
+        match matching_name {
+            Some(matching_name) => self.take_profile(&matching_name),
+            None => Ok(None),
+        }
     }
 
     /// # Returns
@@ -95,6 +145,89 @@ impl CredentialProfiles {
     pub fn available_profiles(&self) -> Vec<String> {
         self.profiles.keys().cloned().collect()
     }
+
+    /// Validates every profile and builds an [`R3Client`] for each of them.
+    ///
+    /// # Returns
+    /// A [`HashMap`] from the profile name to the client for that profile.
+    /// Profiles with an invalid secret access key are contained as [`Err`] entries, instead of failing the whole map.
+    ///
+    /// # Example
+    /// ```
+    /// # use remoteit_api::Credentials;
+    /// let clients = Credentials::load_from_disk()
+    ///     .custom_credentials_path(".env.remoteit")
+    ///     .call()
+    ///     .expect("Couldn't load credentials!")
+    ///     .into_clients();
+    /// for (profile_name, client) in clients {
+    ///     let Ok(client) = client else {
+    ///         eprintln!("Profile {profile_name} is invalid");
+    ///         continue;
+    ///     };
+    ///     // Start making API calls
+    /// }
+    /// ```
+    #[must_use]
+    pub fn into_clients(self) -> HashMap<String, Result<R3Client, base64::DecodeError>> {
+        self.into_iter()
+            .map(|(profile_name, credentials)| {
+                let client = credentials
+                    .map(|credentials| R3Client::builder().credentials(credentials).build());
+                (profile_name, client)
+            })
+            .collect()
+    }
+
+    /// Validates the secret access key of every profile, without consuming the profiles.
+    /// Use this to report malformed profiles, e.g. at startup.
+    ///
+    /// # Returns
+    /// The name of each profile together with its validated [`Credentials`], or the error if the secret access key is not base64 encoded.
+    /// The list is sorted by profile name.
+    #[must_use]
+    pub fn validate_all(&self) -> Vec<(String, Result<Credentials, base64::DecodeError>)> {
+        let mut profiles: Vec<_> = self
+            .profiles
+            .iter()
+            .map(|(profile_name, unverified_credentials)| {
+                (profile_name.clone(), unverified_credentials.verify())
+            })
+            .collect();
+        profiles.sort_by(|(a, _), (b, _)| a.cmp(b));
+        profiles
+    }
+}
+
+/// Iterates over all profiles of a [`CredentialProfiles`] and validates their secret access keys.
+/// The order of the profiles is not guaranteed.
+impl IntoIterator for CredentialProfiles {
+    type Item = (String, Result<Credentials, base64::DecodeError>);
+    type IntoIter = CredentialProfilesIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        CredentialProfilesIntoIter(self.profiles.into_iter())
+    }
+}
+
+/// An iterator over the profiles of a [`CredentialProfiles`]. Created by [`CredentialProfiles::into_iter`].
+#[derive(Debug)]
+pub struct CredentialProfilesIntoIter(
+    std::collections::hash_map::IntoIter<String, UnverifiedCredentials>,
+);
+
+impl Iterator for CredentialProfilesIntoIter {
+    type Item = (String, Result<Credentials, base64::DecodeError>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(profile_name, unverified_credentials)| {
+            (profile_name, unverified_credentials.verify())
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
 }
 
 /// Impl block for credentials_loader related functions.
@@ -105,7 +238,13 @@ impl Credentials {
     ///
     /// # Errors
     /// * [`CredentialsLoaderError::HomeDirNotFound`], when the [`dirs`] create cannot find the user's home directory.
-    /// * [`CredentialsLoaderError::CouldNotReadCredentials`], when the credentials file could not be parsed by the [`config`] crate.
+    /// * [`CredentialsLoaderError::FileNotFound`], when the credentials file does not exist.
+    /// * [`CredentialsLoaderError::PermissionDenied`], when the credentials file can't be read due to missing permissions.
+    /// * [`CredentialsLoaderError::CouldNotReadCredentials`], when the credentials file can't be opened for any other reason.
+    /// * [`CredentialsLoaderError::CredentialsParse`], when the credentials file could not be parsed by the [`config`] crate.
+    /// * [`CredentialsLoaderError::NonUtf8Path`], when the path to the credentials file is not valid UTF-8.
+    /// * [`CredentialsLoaderError::UndefinedVariable`], when the custom path, or a value in the file if `expand_env_vars` is set, references an environment variable, that is not set.
+    /// * [`CredentialsLoaderError::InvalidProfiles`], when `strict` is set and the secret access key of at least one profile is not base64 encoded.
     ///
     /// # Example
     /// You can load credentials from the default path (`~/.remoteit/credentials` on Unix-like), or provide a custom path.
@@ -123,36 +262,318 @@ impl Credentials {
     ///     .custom_credentials_path(PathBuf::from("path/to/file")) // Optional
     ///     .call();
     /// ```
+    /// A leading `~` in the custom path is expanded to the home directory,
+    /// and environment variables in the form of `$VAR` or `${VAR}` are replaced by their values.
+    /// ```
+    /// # use remoteit_api::Credentials;
+    /// let credentials_file = Credentials::load_from_disk()
+    ///     .custom_credentials_path("~/.config/remoteit/credentials")
+    ///     .call();
+    /// ```
+    /// If the values in the file reference environment variables as `${VAR}`, e.g. because a secrets manager injects them at runtime,
+    /// set `expand_env_vars` to replace them with their values. This happens before the secret access key is validated.
+    /// ```
+    /// # use remoteit_api::Credentials;
+    /// let credentials_file = Credentials::load_from_disk()
+    ///     .expand_env_vars(true)
+    ///     .call();
+    /// ```
     #[builder]
     pub fn load_from_disk(
         custom_credentials_path: Option<PathBuf>,
+        /// Whether to replace `${VAR}` in the values of the file with the values of the environment variables. Defaults to `false`.
+        /// Values without `${` are left untouched.
+        #[builder(default)]
+        expand_env_vars: bool,
+        /// Whether to validate the secret access key of every profile while loading. Defaults to `false`,
+        /// in which case a profile is only validated when it is retrieved, so a file with a malformed profile still loads.
+        /// Set this to catch a misconfigured file at startup.
+        #[builder(default)]
+        strict: bool,
     ) -> Result<CredentialProfiles, CredentialsLoaderError> {
-        let credentials_path = custom_credentials_path.unwrap_or(
-            dirs::home_dir()
+        let credentials_path = match custom_credentials_path {
+            Some(path) => expand_path(&path)?,
+            None => dirs::home_dir()
                 .ok_or(CredentialsLoaderError::HomeDirNotFound)?
                 .join(".remoteit")
                 .join("credentials"),
-        );
+        };
+
+        let credentials_path_str = credentials_path
+            .to_str()
+            .ok_or_else(|| CredentialsLoaderError::NonUtf8Path(credentials_path.clone()))?;
+        // The `config` crate reports a missing file as a parse error, so check that the file can be opened first.
+        check_readable(&credentials_path)?;
 
         let profiles: CredentialProfiles = config::Config::builder()
             .add_source(config::File::new(
-                credentials_path
-                    .to_str()
-                    .expect("It is highly unlikely, that there would be a "),
+                credentials_path_str,
                 config::FileFormat::Ini,
             ))
             .build()?
             .try_deserialize()?;
 
+        let mut profiles = profiles;
+        if expand_env_vars {
+            for credentials in profiles.profiles.values_mut() {
+                credentials.r3_access_key_id =
+                    expand_braced_env_vars(&credentials.r3_access_key_id)?;
+                credentials.r3_secret_access_key =
+                    expand_braced_env_vars(&credentials.r3_secret_access_key)?;
+            }
+        }
+        if strict {
+            let invalid_profiles: Vec<String> = profiles
+                .validate_all()
+                .into_iter()
+                .filter(|(_, credentials)| credentials.is_err())
+                .map(|(profile_name, _)| profile_name)
+                .collect();
+            if !invalid_profiles.is_empty() {
+                return Err(CredentialsLoaderError::InvalidProfiles(invalid_profiles));
+            }
+        }
         Ok(profiles)
     }
 }
 
+/// Checks, that the file at `path` exists and can be opened for reading.
+fn check_readable(path: &Path) -> Result<(), CredentialsLoaderError> {
+    match std::fs::File::open(path) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            Err(CredentialsLoaderError::FileNotFound(path.to_path_buf()))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            Err(CredentialsLoaderError::PermissionDenied(path.to_path_buf()))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Expands a leading `~` to the home directory and replaces `$VAR` and `${VAR}` with the values of the environment variables.
+fn expand_path(path: &Path) -> Result<PathBuf, CredentialsLoaderError> {
+    let path_str = path
+        .to_str()
+        .ok_or_else(|| CredentialsLoaderError::NonUtf8Path(path.to_path_buf()))?;
+
+    let mut expanded = String::with_capacity(path_str.len());
+    let mut rest = path_str;
+    if rest == "~" || rest.starts_with("~/") || rest.starts_with("~\\") {
+        let home_dir = dirs::home_dir().ok_or(CredentialsLoaderError::HomeDirNotFound)?;
+        let home_dir = home_dir
+            .to_str()
+            .ok_or_else(|| CredentialsLoaderError::NonUtf8Path(home_dir.clone()))?;
+        expanded.push_str(home_dir);
+        rest = &rest[1..];
+    }
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        rest = &rest[dollar + 1..];
+        let (name, remainder) = if let Some(braced) = rest.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => (braced, ""),
+            }
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+        if name.is_empty() {
+            // A lone `$` is not a variable reference.
+            expanded.push('$');
+            continue;
+        }
+        let value = std::env::var(name)
+            .map_err(|_| CredentialsLoaderError::UndefinedVariable(name.to_string()))?;
+        expanded.push_str(&value);
+        rest = remainder;
+    }
+    expanded.push_str(rest);
+
+    Ok(PathBuf::from(expanded))
+}
+
+/// Replaces `${VAR}` with the value of the environment variable `VAR`.
+/// Unlike [`expand_path`], `$VAR` without braces and a `${` without a closing `}` are kept as they are.
+fn expand_braced_env_vars(value: &str) -> Result<String, CredentialsLoaderError> {
+    let mut expanded = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + end];
+        let value = std::env::var(name)
+            .map_err(|_| CredentialsLoaderError::UndefinedVariable(name.to_string()))?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&value);
+        rest = &rest[start + 2 + end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+/// Impl block for constructing an [`R3Client`] directly from a credentials file.
+impl R3Client {
+    /// Loads the credentials file from the default location (`~/.remoteit/credentials`),
+    /// takes the profile with the given name and builds a new [`R3Client`] with it.
+    ///
+    /// # Errors
+    /// - [`R3Error::CredentialsLoader`] if the credentials file could not be loaded.
+    /// - [`R3Error::ProfileNotFound`] if there is no profile with the given name.
+    /// - [`R3Error::InvalidSecretAccessKey`] if the secret access key of the profile is not base64 encoded.
+    ///
+    /// # Example
+    /// ```
+    /// # use remoteit_api::R3Client;
+    /// let client = R3Client::from_profile("default").unwrap();
+    /// ```
+    pub fn from_profile(profile_name: &str) -> Result<Self, R3Error> {
+        let profiles = Credentials::load_from_disk().call()?;
+        Self::from_profiles(profiles, profile_name)
+    }
+
+    /// Same as [`R3Client::from_profile`], but loads the credentials file from the given path.
+    ///
+    /// # Errors
+    /// - [`R3Error::CredentialsLoader`] if the credentials file could not be loaded.
+    /// - [`R3Error::ProfileNotFound`] if there is no profile with the given name.
+    /// - [`R3Error::InvalidSecretAccessKey`] if the secret access key of the profile is not base64 encoded.
+    ///
+    /// # Example
+    /// ```
+    /// # use remoteit_api::R3Client;
+    /// let client = R3Client::from_profile_at(".env.remoteit", "default").unwrap();
+    /// ```
+    pub fn from_profile_at(path: impl Into<PathBuf>, profile_name: &str) -> Result<Self, R3Error> {
+        let profiles = Credentials::load_from_disk()
+            .custom_credentials_path(path.into())
+            .call()?;
+        Self::from_profiles(profiles, profile_name)
+    }
+
+    fn from_profiles(
+        mut profiles: CredentialProfiles,
+        profile_name: &str,
+    ) -> Result<Self, R3Error> {
+        let credentials = profiles
+            .take_profile(profile_name)?
+            .ok_or_else(|| R3Error::ProfileNotFound(profile_name.to_string()))?;
+        Ok(R3Client::builder().credentials(credentials).build())
+    }
+}
+
+/// Impl block for building an [`R3Client`], that loads its credentials while it is built.
+#[bon]
+impl R3Client {
+    /// Same as [`R3Client::builder`], but the credentials can also be loaded while building the client,
+    /// from the credentials file (`credentials_from_disk`) or from the environment (`credentials_from_env`).
+    /// Exactly one of `credentials`, `credentials_from_disk` and `credentials_from_env` must be set.
+    ///
+    /// All other options are the same as for [`R3Client::builder`].
+    ///
+    /// # Errors
+    /// - [`R3Error::InvalidArgument`] if not exactly one source of credentials is set.
+    /// - [`R3Error::CredentialsLoader`] if the credentials file could not be loaded.
+    /// - [`R3Error::ProfileNotFound`] if there is no profile with the given name in the credentials file.
+    /// - [`R3Error::EnvVarNotSet`] if one of the environment variables of [`Credentials::from_env`] is not set.
+    /// - [`R3Error::InvalidSecretAccessKey`] if the secret access key is not base64 encoded.
+    ///
+    /// # Panics
+    /// If the HTTP client can't be initialized, same as [`R3Client::builder`].
+    ///
+    /// # Example
+    /// ```
+    /// # use remoteit_api::R3Client;
+    /// let client = R3Client::try_new()
+    ///     .credentials_from_disk("default")
+    ///     .credentials_path(".env.remoteit")
+    ///     .call()
+    ///     .unwrap();
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    #[builder]
+    pub fn try_new(
+        /// Already loaded credentials, like [`R3ClientBuilder::credentials`](crate::R3ClientBuilder::credentials).
+        credentials: Option<Credentials>,
+        /// The name of the profile to load from the credentials file, see [`R3Client::from_profile`].
+        credentials_from_disk: Option<String>,
+        /// The path of the credentials file for `credentials_from_disk`. Defaults to `~/.remoteit/credentials`.
+        credentials_path: Option<PathBuf>,
+        /// Whether to read the credentials from the environment, see [`Credentials::from_env`].
+        #[builder(default)]
+        credentials_from_env: bool,
+        /// See [`R3ClientBuilder::environment`](crate::R3ClientBuilder::environment).
+        #[builder(default)]
+        environment: Environment,
+        /// See [`R3ClientBuilder::base_url`](crate::R3ClientBuilder::base_url).
+        base_url: Option<String>,
+        /// See [`R3ClientBuilder::graphql_path`](crate::R3ClientBuilder::graphql_path).
+        #[builder(default = GRAPHQL_PATH.to_string())]
+        graphql_path: String,
+        /// See [`R3ClientBuilder::file_upload_path`](crate::R3ClientBuilder::file_upload_path).
+        #[builder(default = FILE_UPLOAD_PATH.to_string())]
+        file_upload_path: String,
+        /// See [`R3ClientBuilder::sign_body_digest`](crate::R3ClientBuilder::sign_body_digest).
+        #[builder(default)]
+        sign_body_digest: bool,
+        /// See [`R3ClientBuilder::retry_policy`](crate::R3ClientBuilder::retry_policy).
+        #[builder(default = RetryPolicy::none())]
+        retry_policy: RetryPolicy,
+        /// See [`R3ClientBuilder::error_on_graphql_errors`](crate::R3ClientBuilder::error_on_graphql_errors).
+        #[builder(default)]
+        error_on_graphql_errors: bool,
+        /// See [`R3ClientBuilder::pool_max_idle_per_host`](crate::R3ClientBuilder::pool_max_idle_per_host).
+        pool_max_idle_per_host: Option<usize>,
+        /// See [`R3ClientBuilder::pool_idle_timeout`](crate::R3ClientBuilder::pool_idle_timeout).
+        pool_idle_timeout: Option<Duration>,
+        /// See [`R3ClientBuilder::timeout`](crate::R3ClientBuilder::timeout).
+        timeout: Option<Duration>,
+    ) -> Result<Self, R3Error> {
+        let credentials = match (credentials, credentials_from_disk, credentials_from_env) {
+            (Some(credentials), None, false) => credentials,
+            (None, Some(profile_name), false) => {
+                let mut profiles = Credentials::load_from_disk()
+                    .maybe_custom_credentials_path(credentials_path)
+                    .call()?;
+                profiles
+                    .take_profile(&profile_name)?
+                    .ok_or(R3Error::ProfileNotFound(profile_name))?
+            }
+            (None, None, true) => Credentials::from_env()?,
+            _ => {
+                return Err(R3Error::InvalidArgument(
+                    "Set exactly one of `credentials`, `credentials_from_disk` and `credentials_from_env`.".to_string(),
+                ))
+            }
+        };
+        Ok(R3Client::builder()
+            .credentials(credentials)
+            .environment(environment)
+            .maybe_base_url(base_url)
+            .graphql_path(graphql_path)
+            .file_upload_path(file_upload_path)
+            .sign_body_digest(sign_body_digest)
+            .retry_policy(retry_policy)
+            .error_on_graphql_errors(error_on_graphql_errors)
+            .maybe_pool_max_idle_per_host(pool_max_idle_per_host)
+            .maybe_pool_idle_timeout(pool_idle_timeout)
+            .maybe_timeout(timeout)
+            .build())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::{expand_braced_env_vars, expand_path};
     use crate::credentials::Credentials;
-    use crate::CredentialsLoaderError;
+    use crate::{CredentialProfiles, CredentialsLoaderError, R3Client, R3Error};
     use std::io::Write;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn test_load_from_disk_empty() {
@@ -236,6 +657,36 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_load_from_disk_strict() {
+        let credentials = r"
+            [good]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=YmFy
+
+            [bad]
+            R3_ACCESS_KEY_ID=baz
+            R3_SECRET_ACCESS_KEY=not base64!
+        ";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let lenient = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .call()
+            .unwrap();
+        assert_eq!(lenient.len(), 2);
+
+        let strict = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .strict(true)
+            .call();
+        assert!(
+            matches!(strict, Err(CredentialsLoaderError::InvalidProfiles(names)) if names == ["bad"])
+        );
+    }
+
     #[test]
     fn test_load_from_disk_invalid_file() {
         let credentials = r"
@@ -281,4 +732,365 @@ mod tests {
         assert!(profiles.contains(&"default".to_string()));
         assert!(profiles.contains(&"other".to_string()));
     }
+
+    #[test]
+    fn test_from_profile_at() {
+        let credentials = r"
+            [default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=YmFy
+        ";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let client = R3Client::from_profile_at(file.path(), "default").unwrap();
+        assert_eq!(client.credentials().access_key_id(), "foo");
+        assert_eq!(client.credentials().key(), b"bar");
+    }
+
+    #[test]
+    fn test_try_new_credentials_from_disk() {
+        let credentials = r"
+            [default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=YmFy
+        ";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let client = R3Client::try_new()
+            .credentials_from_disk("default")
+            .credentials_path(file.path().to_path_buf())
+            .base_url("http://localhost")
+            .call()
+            .unwrap();
+        assert_eq!(client.credentials().access_key_id(), "foo");
+        assert_eq!(client.credentials().key(), b"bar");
+        assert_eq!(client.base_url(), "http://localhost");
+
+        let result = R3Client::try_new()
+            .credentials_from_disk("other")
+            .credentials_path(file.path().to_path_buf())
+            .call();
+        assert!(matches!(result, Err(R3Error::ProfileNotFound(name)) if name == "other"));
+    }
+
+    #[test]
+    fn test_try_new_credentials_sources() {
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+
+        let client = R3Client::try_new()
+            .credentials(credentials.clone())
+            .call()
+            .unwrap();
+        assert_eq!(client.credentials(), &credentials);
+
+        assert!(matches!(
+            R3Client::try_new().call(),
+            Err(R3Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            R3Client::try_new()
+                .credentials(credentials)
+                .credentials_from_env(true)
+                .call(),
+            Err(R3Error::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_profile_at_missing_profile() {
+        let credentials = r"
+            [default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=YmFy
+        ";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let result = R3Client::from_profile_at(file.path(), "other");
+        assert!(matches!(result, Err(R3Error::ProfileNotFound(name)) if name == "other"));
+    }
+
+    #[test]
+    fn test_from_profile_at_invalid_base64() {
+        let credentials = r"
+            [default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=bar
+        ";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let result = R3Client::from_profile_at(file.path(), "default");
+        assert!(matches!(result, Err(R3Error::InvalidSecretAccessKey(_))));
+    }
+
+    #[test]
+    fn test_into_clients_mixed() {
+        let credentials = r"
+            [default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=YmFy
+
+            [other]
+            R3_ACCESS_KEY_ID=baz
+            R3_SECRET_ACCESS_KEY=cXV4
+
+            [broken]
+            R3_ACCESS_KEY_ID=nope
+            R3_SECRET_ACCESS_KEY=not base64!
+        ";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let clients = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .call()
+            .unwrap()
+            .into_clients();
+
+        assert_eq!(clients.len(), 3);
+        let default = clients["default"].as_ref().unwrap();
+        assert_eq!(default.credentials().access_key_id(), "foo");
+        let other = clients["other"].as_ref().unwrap();
+        assert_eq!(other.credentials().access_key_id(), "baz");
+        assert!(clients["broken"].is_err());
+    }
+
+    #[test]
+    fn test_iterate_and_validate_all() {
+        let credentials = r"
+            [default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=YmFy
+
+            [broken]
+            R3_ACCESS_KEY_ID=nope
+            R3_SECRET_ACCESS_KEY=not base64!
+        ";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let profiles = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .call()
+            .unwrap();
+
+        let validated = profiles.validate_all();
+        assert_eq!(validated.len(), 2);
+        assert_eq!(validated[0].0, "broken");
+        assert!(validated[0].1.is_err());
+        assert_eq!(validated[1].0, "default");
+        assert_eq!(validated[1].1.as_ref().unwrap().access_key_id(), "foo");
+
+        let mut iterated: Vec<_> = profiles.into_iter().collect();
+        iterated.sort_by(|(a, _), (b, _)| a.cmp(b));
+        assert_eq!(iterated.len(), 2);
+        assert!(iterated[0].1.is_err());
+        assert!(iterated[1].1.is_ok());
+    }
+
+    #[test]
+    fn test_take_profile_ci() {
+        let credentials = r"
+            [Default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=YmFy
+
+            [  Prod  ]
+            R3_ACCESS_KEY_ID=baz
+            R3_SECRET_ACCESS_KEY=cXV4
+        ";
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let mut profiles = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .call()
+            .unwrap();
+
+        assert!(profiles.take_profile("Default").unwrap().is_none());
+        let default = profiles.take_profile_ci("Default").unwrap().unwrap();
+        assert_eq!(default.access_key_id(), "foo");
+        let prod = profiles.take_profile_ci("  PROD ").unwrap().unwrap();
+        assert_eq!(prod.access_key_id(), "baz");
+        assert!(profiles.is_empty());
+    }
+
+    #[test]
+    fn test_take_profile_ci_unnormalized_names() {
+        let mut profiles: CredentialProfiles = serde_json::from_str(
+            r#"{ " Staging ": { "r3_access_key_id": "foo", "r3_secret_access_key": "YmFy" } }"#,
+        )
+        .unwrap();
+
+        assert!(profiles.take_profile_ci("missing").unwrap().is_none());
+        let staging = profiles.take_profile_ci("staging").unwrap().unwrap();
+        assert_eq!(staging.access_key_id(), "foo");
+    }
+
+    #[test]
+    fn test_expand_path_tilde() {
+        let home_dir = dirs::home_dir().unwrap();
+
+        let expanded = expand_path(Path::new("~/.remoteit/credentials")).unwrap();
+
+        assert_eq!(expanded, home_dir.join(".remoteit/credentials"));
+        assert_eq!(
+            expand_path(Path::new("foo/~/bar")).unwrap(),
+            PathBuf::from("foo/~/bar")
+        );
+    }
+
+    #[test]
+    fn test_load_from_disk_env_var() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = std::fs::File::create(dir.path().join("credentials")).unwrap();
+        file.write_all(b"[default]\nR3_ACCESS_KEY_ID=foo\nR3_SECRET_ACCESS_KEY=YmFy\n")
+            .unwrap();
+        std::env::set_var("R3_TEST_CREDENTIALS_DIR", dir.path());
+
+        let plain = Credentials::load_from_disk()
+            .custom_credentials_path(PathBuf::from("$R3_TEST_CREDENTIALS_DIR/credentials"))
+            .call()
+            .unwrap();
+        let braced = Credentials::load_from_disk()
+            .custom_credentials_path(PathBuf::from("${R3_TEST_CREDENTIALS_DIR}/credentials"))
+            .call()
+            .unwrap();
+
+        assert_eq!(plain.len(), 1);
+        assert_eq!(plain, braced);
+        assert!(matches!(
+            Credentials::load_from_disk()
+                .custom_credentials_path(PathBuf::from("$R3_TEST_UNDEFINED_VARIABLE/credentials"))
+                .call(),
+            Err(CredentialsLoaderError::UndefinedVariable(name)) if name == "R3_TEST_UNDEFINED_VARIABLE"
+        ));
+    }
+
+    #[test]
+    fn test_load_from_disk_file_not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials");
+
+        let result = Credentials::load_from_disk()
+            .custom_credentials_path(path.clone())
+            .call();
+
+        assert!(matches!(result, Err(CredentialsLoaderError::FileNotFound(p)) if p == path));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_from_disk_permission_denied() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::set_permissions(file.path(), std::fs::Permissions::from_mode(0o000)).unwrap();
+        if std::fs::File::open(file.path()).is_ok() {
+            // Permissions are not enforced, e.g. when running as root.
+            return;
+        }
+
+        let result = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .call();
+
+        assert!(
+            matches!(result, Err(CredentialsLoaderError::PermissionDenied(p)) if p == file.path())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_load_from_disk_non_utf8_path() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path = PathBuf::from(std::ffi::OsStr::from_bytes(b"credentials-\xff"));
+
+        let result = Credentials::load_from_disk()
+            .custom_credentials_path(path.clone())
+            .call();
+
+        assert!(matches!(result, Err(CredentialsLoaderError::NonUtf8Path(p)) if p == path));
+    }
+
+    #[test]
+    fn test_load_from_disk_expand_env_vars() {
+        let credentials = r"
+            [default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=${R3_TEST_SECRET_ACCESS_KEY}
+        ";
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+        std::env::set_var("R3_TEST_SECRET_ACCESS_KEY", "YmFy");
+
+        let mut expanded = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .expand_env_vars(true)
+            .call()
+            .unwrap();
+        let credentials = expanded.take_profile("default").unwrap().unwrap();
+        assert_eq!(credentials.r3_access_key_id, "foo");
+        assert_eq!(credentials.key(), b"bar");
+
+        let mut literal = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .call()
+            .unwrap();
+        assert!(literal.take_profile("default").is_err());
+    }
+
+    #[test]
+    fn test_load_from_disk_expand_env_vars_undefined() {
+        let credentials = r"
+            [default]
+            R3_ACCESS_KEY_ID=foo
+            R3_SECRET_ACCESS_KEY=${R3_TEST_UNDEFINED_SECRET}
+        ";
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(credentials.as_bytes()).unwrap();
+
+        let result = Credentials::load_from_disk()
+            .custom_credentials_path(file.path().to_path_buf())
+            .expand_env_vars(true)
+            .call();
+
+        assert!(matches!(
+            result,
+            Err(CredentialsLoaderError::UndefinedVariable(name)) if name == "R3_TEST_UNDEFINED_SECRET"
+        ));
+    }
+
+    #[test]
+    fn test_expand_braced_env_vars() {
+        std::env::set_var("R3_TEST_BRACED", "value");
+        assert_eq!(expand_braced_env_vars("YmFy").unwrap(), "YmFy");
+        assert_eq!(
+            expand_braced_env_vars("a${R3_TEST_BRACED}b").unwrap(),
+            "avalueb"
+        );
+        assert_eq!(
+            expand_braced_env_vars("$R3_TEST_BRACED").unwrap(),
+            "$R3_TEST_BRACED"
+        );
+        assert_eq!(
+            expand_braced_env_vars("${R3_TEST_BRACED").unwrap(),
+            "${R3_TEST_BRACED"
+        );
+    }
 }
@@ -0,0 +1,181 @@
+//! Contains the cryptographic primitives of this crate: HMAC for request signatures, SHA-256 for digests and checksums, and random bytes.
+//! They are computed with `ring`, or with the pure Rust `hmac`, `sha2` and `getrandom` crates, if the `hmac-pure` feature is enabled.
+
+/// An HMAC algorithm, that an [`HmacKey`] signs with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HmacAlgorithm {
+    /// HMAC-SHA256. This is the algorithm, that the remote.it API uses.
+    #[default]
+    Sha256,
+    /// HMAC-SHA384.
+    Sha384,
+    /// HMAC-SHA512.
+    Sha512,
+}
+
+#[cfg(feature = "ring")]
+impl HmacAlgorithm {
+    pub(crate) fn ring_algorithm(self) -> ring::hmac::Algorithm {
+        match self {
+            HmacAlgorithm::Sha256 => ring::hmac::HMAC_SHA256,
+            HmacAlgorithm::Sha384 => ring::hmac::HMAC_SHA384,
+            HmacAlgorithm::Sha512 => ring::hmac::HMAC_SHA512,
+        }
+    }
+}
+
+/// A key for signing messages with HMAC, like the one from [`Credentials::signing_key`](crate::Credentials::signing_key).
+///
+/// Without the `hmac-pure` feature, the key is prepared for `ring` once when it is created, so signing many messages doesn't have to repeat that.
+#[derive(Clone)]
+pub struct HmacKey {
+    algorithm: HmacAlgorithm,
+    #[cfg(not(feature = "hmac-pure"))]
+    key: ring::hmac::Key,
+    #[cfg(feature = "hmac-pure")]
+    key: Vec<u8>,
+}
+
+impl HmacKey {
+    /// Creates a new key for the given algorithm from the given secret.
+    #[must_use]
+    pub fn new(algorithm: HmacAlgorithm, key: &[u8]) -> Self {
+        Self {
+            algorithm,
+            #[cfg(not(feature = "hmac-pure"))]
+            key: ring::hmac::Key::new(algorithm.ring_algorithm(), key),
+            #[cfg(feature = "hmac-pure")]
+            key: key.to_vec(),
+        }
+    }
+
+    /// # Returns
+    /// The algorithm, that this key signs with.
+    #[must_use]
+    pub fn algorithm(&self) -> HmacAlgorithm {
+        self.algorithm
+    }
+
+    /// # Returns
+    /// The HMAC of `message` with this key.
+    #[must_use]
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        #[cfg(not(feature = "hmac-pure"))]
+        let signature = ring::hmac::sign(&self.key, message).as_ref().to_vec();
+        #[cfg(feature = "hmac-pure")]
+        let signature = pure_hmac(self.algorithm, &self.key, message);
+        signature
+    }
+}
+
+impl std::fmt::Debug for HmacKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HmacKey")
+            .field("algorithm", &self.algorithm)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Computes the HMAC of `message` with the pure Rust `hmac` and `sha2` crates.
+#[cfg(feature = "hmac-pure")]
+pub(crate) fn pure_hmac(algorithm: HmacAlgorithm, key: &[u8], message: &[u8]) -> Vec<u8> {
+    use hmac::{Hmac, KeyInit, Mac};
+
+    fn compute<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+        let mut mac = <M as KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(message);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    match algorithm {
+        HmacAlgorithm::Sha256 => compute::<Hmac<sha2::Sha256>>(key, message),
+        HmacAlgorithm::Sha384 => compute::<Hmac<sha2::Sha384>>(key, message),
+        HmacAlgorithm::Sha512 => compute::<Hmac<sha2::Sha512>>(key, message),
+    }
+}
+
+/// Computes a SHA-256 hash incrementally, e.g. while reading a file in chunks.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) struct Sha256 {
+    #[cfg(not(feature = "hmac-pure"))]
+    context: ring::digest::Context,
+    #[cfg(feature = "hmac-pure")]
+    context: sha2::Sha256,
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+impl Sha256 {
+    pub(crate) fn new() -> Self {
+        Self {
+            #[cfg(not(feature = "hmac-pure"))]
+            context: ring::digest::Context::new(&ring::digest::SHA256),
+            #[cfg(feature = "hmac-pure")]
+            context: <sha2::Sha256 as sha2::Digest>::new(),
+        }
+    }
+
+    pub(crate) fn update(&mut self, data: &[u8]) {
+        #[cfg(not(feature = "hmac-pure"))]
+        self.context.update(data);
+        #[cfg(feature = "hmac-pure")]
+        sha2::Digest::update(&mut self.context, data);
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        #[cfg(not(feature = "hmac-pure"))]
+        let hash = self.context.finish().as_ref().to_vec();
+        #[cfg(feature = "hmac-pure")]
+        let hash = sha2::Digest::finalize(self.context).to_vec();
+        hash
+    }
+
+    /// # Returns
+    /// The SHA-256 hash of `data`.
+    pub(crate) fn digest(data: &[u8]) -> Vec<u8> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finish()
+    }
+}
+
+/// Fills `bytes` with random bytes from the random number generator of the operating system.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn fill_random(bytes: &mut [u8]) {
+    #[cfg(not(feature = "hmac-pure"))]
+    {
+        use ring::rand::SecureRandom;
+        ring::rand::SystemRandom::new()
+            .fill(bytes)
+            .expect("the system random number generator should be available");
+    }
+    #[cfg(feature = "hmac-pure")]
+    getrandom::getrandom(bytes).expect("the system random number generator should be available");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_key_algorithms() {
+        let message = b"what do ya want for nothing?";
+        for (algorithm, length) in [
+            (HmacAlgorithm::Sha256, 32),
+            (HmacAlgorithm::Sha384, 48),
+            (HmacAlgorithm::Sha512, 64),
+        ] {
+            let key = HmacKey::new(algorithm, b"Jefe");
+            assert_eq!(key.algorithm(), algorithm);
+            assert_eq!(key.sign(message).len(), length);
+        }
+    }
+
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    #[test]
+    fn test_sha256_chunks() {
+        let mut hasher = Sha256::new();
+        hasher.update(b"fo");
+        hasher.update(b"o");
+        assert_eq!(hasher.finish(), Sha256::digest(b"foo"));
+    }
+}
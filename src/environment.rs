@@ -0,0 +1,69 @@
+//! Contains the [`Environment`], which selects the remote.it API, that an [`R3Client`](crate::R3Client) sends requests to.
+
+use crate::BASE_URL;
+
+/// Base URL of the remote.it staging API.
+pub const STAGING_BASE_URL: &str = "https://api.staging.remote.it";
+
+/// A remote.it environment, that an [`R3Client`](crate::R3Client) sends requests to.
+///
+/// The environment sets both the base URL and the `host`, that requests are signed for.
+/// Set it with [`R3ClientBuilder::environment`](crate::R3ClientBuilder::environment).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum Environment {
+    /// The production API at [`BASE_URL`].
+    #[default]
+    Production,
+    /// The staging API at [`STAGING_BASE_URL`].
+    Staging,
+    /// Any other environment, given by its base URL, e.g. `https://api.example.com`.
+    Custom(String),
+}
+
+impl Environment {
+    /// # Returns
+    /// The base URL of the API of this environment.
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        match self {
+            Self::Production => BASE_URL,
+            Self::Staging => STAGING_BASE_URL,
+            Self::Custom(base_url) => base_url,
+        }
+    }
+
+    /// # Returns
+    /// The host of the API of this environment, i.e. the base URL without the scheme and path.
+    /// This is the value of the `host` header in the request signature.
+    #[must_use]
+    pub fn host(&self) -> &str {
+        let base_url = self.base_url();
+        let authority = base_url
+            .split_once("://")
+            .map_or(base_url, |(_, rest)| rest);
+        authority
+            .split_once('/')
+            .map_or(authority, |(host, _)| host)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environment_urls() {
+        assert_eq!(Environment::default(), Environment::Production);
+        assert_eq!(Environment::Production.base_url(), "https://api.remote.it");
+        assert_eq!(Environment::Production.host(), "api.remote.it");
+        assert_eq!(
+            Environment::Staging.base_url(),
+            "https://api.staging.remote.it"
+        );
+        assert_eq!(Environment::Staging.host(), "api.staging.remote.it");
+
+        let custom = Environment::Custom("http://localhost:8080/remoteit".to_string());
+        assert_eq!(custom.base_url(), "http://localhost:8080/remoteit");
+        assert_eq!(custom.host(), "localhost:8080");
+    }
+}
@@ -0,0 +1,227 @@
+//! Contains the general error type of this crate.
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write};
+
+/// Errors that can occur when using the [`R3Client`](crate::R3Client).
+#[allow(missing_docs)]
+#[derive(thiserror::Error, Debug)]
+pub enum R3Error {
+    #[cfg(feature = "credentials_loader")]
+    #[error("The credentials could not be loaded: {0}")]
+    CredentialsLoader(#[from] crate::CredentialsLoaderError),
+    #[cfg(feature = "keyring")]
+    #[error("The keyring could not be accessed: {0}")]
+    Keyring(#[from] keyring::Error),
+    #[cfg(feature = "keyring")]
+    #[error("The keyring entry does not contain valid credentials: {0}")]
+    InvalidKeyringEntry(serde_json::Error),
+    #[error("The profile `{0}` does not exist in the credentials file.")]
+    ProfileNotFound(String),
+    #[error("The secret access key is not valid base64: {0}")]
+    InvalidSecretAccessKey(#[from] base64::DecodeError),
+    #[error("The environment variable `{0}` is not set.")]
+    EnvVarNotSet(String),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    #[error("The API returned errors:{}", format_graphql_errors(.0))]
+    GraphQl(Vec<R3GraphQlError>),
+    #[error("The API response did not contain any data.")]
+    NoData,
+    #[error("The API response did not contain a valid `Date` header.")]
+    NoServerDate,
+    #[error("The API returned {actual} responses for a batch of {expected} operations.")]
+    BatchSizeMismatch { expected: usize, actual: usize },
+    #[error("There is no job with the ID `{0}`.")]
+    JobNotFound(String),
+    #[error("There is no device with the ID `{0}`.")]
+    DeviceNotFound(String),
+    #[error("There is no device named `{0}`.")]
+    DeviceNameNotFound(String),
+    #[error("There are multiple devices named `{name}`: {device_ids:?}")]
+    AmbiguousDeviceName {
+        name: String,
+        device_ids: Vec<String>,
+    },
+    #[error("The device `{0}` is offline.")]
+    DeviceOffline(String),
+    #[error("The operation was cancelled.")]
+    Cancelled,
+}
+
+impl R3Error {
+    /// # Returns
+    /// The remote.it error codes of all GraphQL errors, if this is an [`R3Error::GraphQl`]. Empty otherwise.
+    /// See [`R3GraphQlError::code`].
+    #[must_use]
+    pub fn graphql_error_codes(&self) -> Vec<&str> {
+        match self {
+            Self::GraphQl(errors) => errors.iter().filter_map(R3GraphQlError::code).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Formats each error on its own line, for the [`Display`] of [`R3Error::GraphQl`].
+fn format_graphql_errors(errors: &[R3GraphQlError]) -> String {
+    errors.iter().fold(String::new(), |mut formatted, error| {
+        let _ = write!(formatted, "\n- {error}");
+        formatted
+    })
+}
+
+/// A single error returned by the remote.it GraphQL API.
+///
+/// Wraps a [`graphql_client::Error`] and adds accessors for the remote.it specific parts, like [`R3GraphQlError::code`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct R3GraphQlError(pub graphql_client::Error);
+
+impl R3GraphQlError {
+    /// The key in the `extensions` of an error, under which remote.it reports the error code.
+    pub const CODE_KEY: &'static str = "code";
+
+    /// # Returns
+    /// The human-readable error message.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.0.message
+    }
+
+    /// # Returns
+    /// The path of the field in the query, that caused the error, if the API reported one.
+    #[must_use]
+    pub fn path(&self) -> Option<&[graphql_client::PathFragment]> {
+        self.0.path.as_deref()
+    }
+
+    /// # Returns
+    /// Additional information about the error, if the API reported any.
+    #[must_use]
+    pub fn extensions(&self) -> Option<&HashMap<String, serde_json::Value>> {
+        self.0.extensions.as_ref()
+    }
+
+    /// # Returns
+    /// The error code from the `extensions` of the error, like `"NOT_FOUND"` or `"FORBIDDEN"`.
+    /// [`None`] if the error has no code, or the code is not a string.
+    #[must_use]
+    pub fn code(&self) -> Option<&str> {
+        self.extensions()?.get(Self::CODE_KEY)?.as_str()
+    }
+}
+
+impl From<graphql_client::Error> for R3GraphQlError {
+    fn from(error: graphql_client::Error) -> Self {
+        Self(error)
+    }
+}
+
+/// Formats the error like GraphQL servers report it, e.g.
+/// `Device not found (line 3, column 5; path: login.device[0])`.
+/// The locations and the path are left out, if the API didn't report them.
+impl Display for R3GraphQlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut details: Vec<String> = self
+            .0
+            .locations
+            .iter()
+            .flatten()
+            .map(|location| format!("line {}, column {}", location.line, location.column))
+            .collect();
+        if let Some(path) = self.path().filter(|path| !path.is_empty()) {
+            let mut formatted = String::from("path: ");
+            for (i, fragment) in path.iter().enumerate() {
+                let _ = match fragment {
+                    graphql_client::PathFragment::Key(key) if i == 0 => write!(formatted, "{key}"),
+                    graphql_client::PathFragment::Key(key) => write!(formatted, ".{key}"),
+                    graphql_client::PathFragment::Index(index) => write!(formatted, "[{index}]"),
+                };
+            }
+            details.push(formatted);
+        }
+        write!(f, "{}", self.0.message)?;
+        if !details.is_empty() {
+            write!(f, " ({})", details.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for R3GraphQlError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graphql_error_codes() {
+        let errors: Vec<graphql_client::Error> = serde_json::from_str(
+            r#"[
+                {
+                    "message": "Device not found",
+                    "path": ["login", "device", 0],
+                    "extensions": { "code": "NOT_FOUND" }
+                },
+                {
+                    "message": "Access denied",
+                    "extensions": { "code": "FORBIDDEN", "details": { "role": "GUEST" } }
+                },
+                {
+                    "message": "Something went wrong"
+                }
+            ]"#,
+        )
+        .unwrap();
+        let error = R3Error::GraphQl(errors.into_iter().map(R3GraphQlError::from).collect());
+
+        assert_eq!(error.graphql_error_codes(), vec!["NOT_FOUND", "FORBIDDEN"]);
+
+        let R3Error::GraphQl(errors) = error else {
+            unreachable!()
+        };
+        assert_eq!(errors[0].message(), "Device not found");
+        assert_eq!(
+            errors[0].path(),
+            Some(
+                &[
+                    graphql_client::PathFragment::Key("login".to_string()),
+                    graphql_client::PathFragment::Key("device".to_string()),
+                    graphql_client::PathFragment::Index(0),
+                ][..]
+            )
+        );
+        assert!(errors[1].extensions().unwrap().contains_key("details"));
+        assert_eq!(errors[2].code(), None);
+        assert!(R3Error::NoData.graphql_error_codes().is_empty());
+    }
+
+    #[test]
+    fn test_graphql_error_display() {
+        let errors: Vec<graphql_client::Error> = serde_json::from_str(
+            r#"[
+                {
+                    "message": "Cannot query field \"nme\" on type \"Device\".",
+                    "locations": [{ "line": 5, "column": 13 }, { "line": 9, "column": 13 }]
+                },
+                {
+                    "message": "Device not found",
+                    "locations": [{ "line": 3, "column": 9 }],
+                    "path": ["login", "device", 0, "name"]
+                },
+                {
+                    "message": "Something went wrong"
+                }
+            ]"#,
+        )
+        .unwrap();
+        let error = R3Error::GraphQl(errors.into_iter().map(R3GraphQlError::from).collect());
+
+        assert_eq!(
+            error.to_string(),
+            "The API returned errors:
+- Cannot query field \"nme\" on type \"Device\". (line 5, column 13; line 9, column 13)
+- Device not found (line 3, column 9; path: login.device[0].name)
+- Something went wrong"
+        );
+    }
+}
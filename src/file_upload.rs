@@ -5,9 +5,13 @@
 //! Please see [`R3Client`](crate::R3Client) for the actual functions you can call.
 
 use bon::{bon, builder};
-use std::path::PathBuf;
+use std::fmt::Write as _;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::auth::{build_auth_header, get_date};
+use crate::auth::build_auth_header;
+use crate::crypto::Sha256;
 
 /// Struct to hold the details of a file to be uploaded to remote.it.
 #[derive(Debug, Clone)]
@@ -43,7 +47,120 @@ pub struct UploadFileResponse {
     pub owner_id: String,
     /// The available arguments for this file, if it is an executable script.
     /// See https://docs.remote.it/developer-tools/device-scripting#creating-scripts for more information.
-    pub file_arguments: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub file_arguments: Vec<ScriptArgument>,
+    /// The lowercase hex encoded SHA-256 checksum of the uploaded file.
+    /// Only set if the upload was started with `compute_checksum(true)`, since it is computed locally before the upload and not returned by the API.
+    #[serde(skip)]
+    pub checksum: Option<String>,
+}
+
+/// Encode a SHA-256 digest of `data` as a lowercase hex string.
+#[must_use]
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex_encode(&Sha256::digest(data))
+}
+
+/// Compute the SHA-256 checksum of the file at `path` as a lowercase hex string.
+/// The file is read in chunks, so this also works for files which do not fit into memory.
+///
+/// # Errors
+/// Any [`std::io::Error`] while opening or reading the file.
+pub fn file_sha256(path: &Path) -> std::io::Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut context = Sha256::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        context.update(&buffer[..read]);
+    }
+    Ok(hex_encode(&context.finish()))
+}
+
+/// How long to wait at most for an uploaded file to appear in the file list, if `verify_after_upload` is set.
+pub const VERIFY_UPLOAD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long to wait between two checks, whether an uploaded file appears in the file list.
+pub const VERIFY_UPLOAD_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Callback for reporting the progress of an upload.
+/// Called with the number of bytes read from the file so far and the total size of the file in bytes.
+pub type ProgressCallback = Box<dyn FnMut(u64, u64) + Send>;
+
+/// A [`Read`] adapter, which reports how many bytes have been read from the inner reader so far.
+#[cfg(feature = "blocking")]
+struct ProgressReader<R, F> {
+    inner: R,
+    read: u64,
+    total: u64,
+    callback: F,
+}
+
+#[cfg(feature = "blocking")]
+impl<R: Read, F: FnMut(u64, u64)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        if read > 0 {
+            self.read += read as u64;
+            (self.callback)(self.read, self.total);
+        }
+        Ok(read)
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// An argument, that an executable script accepts.
+/// Arguments are declared in the header of the script. The remote.it API parses them when the script is uploaded.
+/// See <https://docs.remote.it/developer-tools/device-scripting#creating-scripts> for more information.
+#[derive(serde::Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptArgument {
+    /// The ID of the argument.
+    #[serde(default)]
+    pub id: String,
+    /// The name of the argument. Use this name when passing arguments to [`R3Client::start_job`](crate::R3Client::start_job).
+    pub name: String,
+    /// The description of the argument.
+    #[serde(default)]
+    pub desc: String,
+    /// The position of the argument in the script header.
+    #[serde(default)]
+    pub order: f64,
+    /// How the value of the argument is selected.
+    #[serde(default)]
+    pub argument_type: ScriptArgumentType,
+    /// The options to choose from, if the argument is a selection.
+    #[serde(default)]
+    pub options: Vec<String>,
+    /// Any other fields the API returned for this argument, which are not (yet) modeled by this struct.
+    #[serde(flatten)]
+    pub raw: serde_json::Map<String, serde_json::Value>,
+}
+
+/// How the value of a [`ScriptArgument`] is selected.
+#[derive(serde::Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ScriptArgumentType {
+    /// The value is a file uploaded to remote.it.
+    FileSelect,
+    /// The value is one of the [`ScriptArgument::options`].
+    StringSelect,
+    /// The value is free text.
+    #[default]
+    StringEntry,
+    /// An argument type unknown to this crate.
+    #[serde(other)]
+    Unknown,
 }
 
 /// The negative response from the remote.it API when uploading a file.
@@ -64,6 +181,12 @@ pub enum UploadFileError {
     ParseJson(reqwest::Error),
     #[error("The API returned an error: {0:?}")]
     ApiError(ErrorResponse),
+    #[error(
+        "The uploaded file `{0}` did not appear in the file list within {VERIFY_UPLOAD_TIMEOUT:?}."
+    )]
+    NotListed(String),
+    #[error("Failed to verify the upload: {0}")]
+    Verify(String),
 }
 
 #[cfg(feature = "blocking")]
@@ -82,18 +205,63 @@ impl crate::R3Client {
     /// - [`UploadFileError::Reqwest`] if there is an error sending the request.
     /// - [`UploadFileError::ApiError`] if the remote.it API returns an error response.
     /// - [`UploadFileError::ParseJson`] if there is an error parsing the response.
+    /// - [`UploadFileError::NotListed`] or [`UploadFileError::Verify`] if `verify_after_upload` is set and the upload could not be verified.
+    ///
+    /// # Verification
+    /// The file list of the API is eventually consistent, so a file may not be listed right after it was uploaded.
+    /// If `verify_after_upload` is `true`, the file list is polled every [`VERIFY_UPLOAD_INTERVAL`] until the uploaded file appears,
+    /// but at most for [`VERIFY_UPLOAD_TIMEOUT`].
+    ///
+    /// # Checksum
+    /// If `compute_checksum` is `true`, the SHA-256 checksum of the file is computed before uploading and returned in [`UploadFileResponse::checksum`].
+    /// The remote.it API does not offer a way to download files, so the checksum cannot be verified against the uploaded file (yet).
+    ///
+    /// # Progress
+    /// If `on_progress` is set, it is called with the number of bytes sent so far and the total size of the file, whenever a chunk of the file is read by the request body.
     #[builder]
     pub fn upload_file(
         &self,
         file_upload: FileUpload,
+        #[builder(default)] compute_checksum: bool,
+        on_progress: Option<ProgressCallback>,
+        #[builder(default)] verify_after_upload: bool,
     ) -> Result<UploadFileResponse, UploadFileError> {
-        use crate::BASE_URL;
-        use crate::FILE_UPLOAD_PATH;
+        #[cfg(feature = "log")]
+        log::trace!(
+            "Uploading `{}` as `{}` (executable: {})",
+            file_upload.file_path.display(),
+            file_upload.file_name,
+            file_upload.executable
+        );
+        let checksum = if compute_checksum {
+            Some(file_sha256(&file_upload.file_path)?)
+        } else {
+            None
+        };
 
-        let client = reqwest::blocking::Client::new();
-        let mut form = reqwest::blocking::multipart::Form::new()
-            .file(file_upload.file_name, file_upload.file_path)?
-            .text("executable", file_upload.executable.to_string());
+        let form = reqwest::blocking::multipart::Form::new();
+        let form = if let Some(callback) = on_progress {
+            let file = std::fs::File::open(&file_upload.file_path)?;
+            let total = file.metadata()?.len();
+            let reader = ProgressReader {
+                inner: file,
+                read: 0,
+                total,
+                callback,
+            };
+            let part = reqwest::blocking::multipart::Part::reader_with_length(reader, total)
+                .file_name(
+                    file_upload
+                        .file_path
+                        .file_name()
+                        .map(|val| val.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                );
+            form.part(file_upload.file_name, part)
+        } else {
+            form.file(file_upload.file_name, file_upload.file_path)?
+        };
+        let mut form = form.text("executable", file_upload.executable.to_string());
 
         if let Some(short_descr) = file_upload.short_desc {
             form = form.text("shortDesc", short_descr);
@@ -102,32 +270,38 @@ impl crate::R3Client {
             form = form.text("longDesc", long_descr);
         }
 
-        #[cfg(debug_assertions)]
-        dbg!(&form);
-
         let content_type = format!("multipart/form-data; boundary={}", form.boundary());
-        let date = get_date();
+        let date = self.signing_date();
         let auth_header = build_auth_header()
             .key_id(&self.credentials.r3_access_key_id)
-            .key(&self.credentials.key)
+            .key(self.credentials.signing_key())
             .content_type(&content_type)
             .method(&reqwest::Method::POST)
-            .path(FILE_UPLOAD_PATH)
+            .path(&self.file_upload_path)
             .date(&date)
+            .host(self.environment.host())
             .call();
 
-        let response = client
-            .post(format!("{BASE_URL}{FILE_UPLOAD_PATH}"))
+        let mut request = self
+            .blocking_http_client()
+            .post(format!("{}{}", self.base_url, self.file_upload_path))
             .header("Date", date)
             .header("Authorization", auth_header)
             .header("Content-Type", content_type)
-            .multipart(form)
-            .send()?;
+            .multipart(form);
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request.send()?;
 
         if response.status().is_success() {
-            let file_upload_response = response
+            let mut file_upload_response = response
                 .json::<UploadFileResponse>()
                 .map_err(|e| UploadFileError::ParseJson(e))?;
+            file_upload_response.checksum = checksum;
+            if verify_after_upload {
+                self.wait_until_file_listed(&file_upload_response.file_id)?;
+            }
             Ok(file_upload_response)
         } else {
             let response: ErrorResponse =
@@ -135,6 +309,51 @@ impl crate::R3Client {
             Err(UploadFileError::ApiError(response))
         }
     }
+
+    /// Upload multiple files to remote.it, one after another. See [`R3Client::upload_file`](crate::R3Client::upload_file) for the details of each upload.
+    ///
+    /// A failed upload does not stop the remaining uploads.
+    ///
+    /// # Returns
+    /// The result of the upload of every file, in the same order as `uploads`.
+    #[builder]
+    pub fn upload_files(
+        &self,
+        uploads: Vec<FileUpload>,
+        #[builder(default)] compute_checksum: bool,
+        #[builder(default)] verify_after_upload: bool,
+    ) -> Vec<Result<UploadFileResponse, UploadFileError>> {
+        uploads
+            .into_iter()
+            .map(|file_upload| {
+                self.upload_file()
+                    .file_upload(file_upload)
+                    .compute_checksum(compute_checksum)
+                    .verify_after_upload(verify_after_upload)
+                    .call()
+            })
+            .collect()
+    }
+
+    /// Polls the file list until the file with the given ID appears, see `verify_after_upload` of [`R3Client::upload_file`](crate::R3Client::upload_file).
+    fn wait_until_file_listed(&self, file_id: &str) -> Result<(), UploadFileError> {
+        let deadline = std::time::Instant::now() + VERIFY_UPLOAD_TIMEOUT;
+        loop {
+            let response = self
+                .get_files()
+                .call()
+                .map_err(|e| UploadFileError::Verify(e.to_string()))?;
+            let files = crate::response_data(response)
+                .map_err(|e| UploadFileError::Verify(e.to_string()))?;
+            if files.contains_file(file_id) {
+                return Ok(());
+            }
+            if std::time::Instant::now() + VERIFY_UPLOAD_INTERVAL > deadline {
+                return Err(UploadFileError::NotListed(file_id.to_string()));
+            }
+            std::thread::sleep(VERIFY_UPLOAD_INTERVAL);
+        }
+    }
 }
 
 #[cfg(feature = "async")]
@@ -153,15 +372,38 @@ impl crate::R3Client {
     /// - [`UploadFileError::Reqwest`] if there is an error sending the request.
     /// - [`UploadFileError::ApiError`] if the remote.it API returns an error response.
     /// - [`UploadFileError::ParseJson`] if there is an error parsing the response.
+    /// - [`UploadFileError::NotListed`] or [`UploadFileError::Verify`] if `verify_after_upload` is set and the upload could not be verified.
+    ///
+    /// # Verification
+    /// See [`R3Client::upload_file`](crate::R3Client::upload_file).
+    ///
+    /// # Checksum
+    /// If `compute_checksum` is `true`, the SHA-256 checksum of the file is computed before uploading and returned in [`UploadFileResponse::checksum`].
+    /// The remote.it API does not offer a way to download files, so the checksum cannot be verified against the uploaded file (yet).
     #[builder]
     pub async fn upload_file_async(
         &self,
         file_upload: FileUpload,
+        #[builder(default)] compute_checksum: bool,
+        #[builder(default)] verify_after_upload: bool,
     ) -> Result<UploadFileResponse, UploadFileError> {
-        use crate::BASE_URL;
-        use crate::FILE_UPLOAD_PATH;
-
-        let client = reqwest::Client::new();
+        #[cfg(feature = "log")]
+        log::trace!(
+            "Uploading `{}` as `{}` (executable: {})",
+            file_upload.file_path.display(),
+            file_upload.file_name,
+            file_upload.executable
+        );
+        let checksum = if compute_checksum {
+            // Hashing reads the file with blocking IO in chunks, so it runs on the blocking thread pool instead of the runtime.
+            let file_path = file_upload.file_path.clone();
+            let checksum = tokio::task::spawn_blocking(move || file_sha256(&file_path))
+                .await
+                .map_err(|error| std::io::Error::new(std::io::ErrorKind::Other, error))??;
+            Some(checksum)
+        } else {
+            None
+        };
 
         let file_name = file_upload
             .file_path
@@ -169,7 +411,7 @@ impl crate::R3Client {
             .map(|val| val.to_string_lossy().to_string())
             .unwrap_or_default();
 
-        let file = tokio::fs::File::open(&file_upload.file_name).await?;
+        let file = tokio::fs::File::open(&file_upload.file_path).await?;
 
         let reader = reqwest::Body::wrap_stream(tokio_util::codec::FramedRead::new(
             file,
@@ -189,34 +431,40 @@ impl crate::R3Client {
             form = form.text("longDesc", long_descr);
         }
 
-        #[cfg(debug_assertions)]
-        dbg!(&form);
-
         let content_type = format!("multipart/form-data; boundary={}", form.boundary());
-        let date = get_date();
+        let date = self.signing_date();
         let auth_header = build_auth_header()
             .key_id(&self.credentials.r3_access_key_id)
-            .key(&self.credentials.key)
+            .key(self.credentials.signing_key())
             .content_type(&content_type)
             .method(&reqwest::Method::POST)
-            .path(FILE_UPLOAD_PATH)
+            .path(&self.file_upload_path)
             .date(&date)
+            .host(self.environment.host())
             .call();
 
-        let response = client
-            .post(format!("{BASE_URL}{FILE_UPLOAD_PATH}"))
+        let mut request = self
+            .http_client
+            .post(format!("{}{}", self.base_url, self.file_upload_path))
             .header("Date", date)
             .header("Authorization", auth_header)
             .header("Content-Type", content_type)
-            .multipart(form)
-            .send()
-            .await?;
+            .multipart(form);
+        if let Some(timeout) = self.request_timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request.send().await?;
 
         if response.status().is_success() {
-            let file_upload_response = response
+            let mut file_upload_response = response
                 .json::<UploadFileResponse>()
                 .await
                 .map_err(|e| UploadFileError::ParseJson(e))?;
+            file_upload_response.checksum = checksum;
+            if verify_after_upload {
+                self.wait_until_file_listed_async(&file_upload_response.file_id)
+                    .await?;
+            }
             Ok(file_upload_response)
         } else {
             let response: ErrorResponse = response
@@ -226,4 +474,323 @@ impl crate::R3Client {
             Err(UploadFileError::ApiError(response))
         }
     }
+
+    /// Upload multiple files to remote.it, with up to `concurrency` uploads at the same time.
+    /// See [`R3Client::upload_file_async`](crate::R3Client::upload_file_async) for the details of each upload.
+    ///
+    /// A failed upload does not stop the remaining uploads.
+    ///
+    /// # Returns
+    /// The result of the upload of every file, in the same order as `uploads`.
+    #[builder]
+    pub async fn upload_files_async(
+        &self,
+        uploads: Vec<FileUpload>,
+        #[builder(default)] compute_checksum: bool,
+        #[builder(default)] verify_after_upload: bool,
+        /// The maximum number of files to upload at the same time. Defaults to 4. `0` is treated as `1`.
+        #[builder(default = 4)]
+        concurrency: usize,
+    ) -> Vec<Result<UploadFileResponse, UploadFileError>> {
+        use futures::StreamExt;
+
+        futures::stream::iter(uploads)
+            .map(|file_upload| {
+                self.upload_file_async()
+                    .file_upload(file_upload)
+                    .compute_checksum(compute_checksum)
+                    .verify_after_upload(verify_after_upload)
+                    .call()
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Polls the file list until the file with the given ID appears, see `verify_after_upload` of [`R3Client::upload_file_async`](crate::R3Client::upload_file_async).
+    async fn wait_until_file_listed_async(&self, file_id: &str) -> Result<(), UploadFileError> {
+        let deadline = std::time::Instant::now() + VERIFY_UPLOAD_TIMEOUT;
+        loop {
+            let response = self
+                .get_files_async()
+                .call()
+                .await
+                .map_err(|e| UploadFileError::Verify(e.to_string()))?;
+            let files = crate::response_data(response)
+                .map_err(|e| UploadFileError::Verify(e.to_string()))?;
+            if files.contains_file(file_id) {
+                return Ok(());
+            }
+            if std::time::Instant::now() + VERIFY_UPLOAD_INTERVAL > deadline {
+                return Err(UploadFileError::NotListed(file_id.to_string()));
+            }
+            tokio::time::sleep(VERIFY_UPLOAD_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_upload_file_response() {
+        let json = r#"{
+            "fileId": "f1b2c3",
+            "fileVersionId": "v4d5e6",
+            "version": 2,
+            "name": "reboot.sh",
+            "executable": true,
+            "ownerId": "u7f8a9",
+            "fileArguments": [
+                {
+                    "id": "a1",
+                    "name": "delay",
+                    "desc": "Seconds to wait before rebooting",
+                    "order": 0,
+                    "argumentType": "StringEntry",
+                    "options": [],
+                    "created": "2024-08-30T12:00:00.000Z"
+                },
+                {
+                    "id": "a2",
+                    "name": "mode",
+                    "desc": "Reboot mode",
+                    "order": 1,
+                    "argumentType": "StringSelect",
+                    "options": ["soft", "hard"],
+                    "created": "2024-08-30T12:00:00.000Z"
+                },
+                {
+                    "id": "a3",
+                    "name": "config",
+                    "desc": "Config file",
+                    "order": 2,
+                    "argumentType": "SomethingNew",
+                    "options": []
+                }
+            ]
+        }"#;
+
+        let response: UploadFileResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.file_arguments.len(), 3);
+
+        let delay = &response.file_arguments[0];
+        assert_eq!(delay.name, "delay");
+        assert_eq!(delay.argument_type, ScriptArgumentType::StringEntry);
+        assert!(delay.raw.contains_key("created"));
+
+        let mode = &response.file_arguments[1];
+        assert_eq!(mode.argument_type, ScriptArgumentType::StringSelect);
+        assert_eq!(mode.options, vec!["soft", "hard"]);
+
+        let config = &response.file_arguments[2];
+        assert_eq!(config.argument_type, ScriptArgumentType::Unknown);
+    }
+
+    #[test]
+    fn test_file_sha256() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"foo").unwrap();
+
+        let expected = "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae";
+        assert_eq!(file_sha256(file.path()).unwrap(), expected);
+        assert_eq!(sha256_hex(b"foo"), expected);
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn test_progress_reader() {
+        let data = vec![7u8; 10_000];
+        let mut progress = Vec::new();
+        let mut reader = ProgressReader {
+            inner: std::io::Cursor::new(&data),
+            read: 0,
+            total: data.len() as u64,
+            callback: |read, total| progress.push((read, total)),
+        };
+
+        let mut buffer = [0u8; 4096];
+        while reader.read(&mut buffer).unwrap() > 0 {}
+
+        assert_eq!(
+            progress,
+            vec![(4096, 10_000), (8192, 10_000), (10_000, 10_000)]
+        );
+    }
+
+    /// Mocks the upload of `ok.sh`, which succeeds, and of `bad.sh`, which the API rejects.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    fn mock_batch_upload(server: &mut mockito::Server) -> [mockito::Mock; 2] {
+        let ok = server
+            .mock("POST", crate::FILE_UPLOAD_PATH)
+            .match_body(mockito::Matcher::Regex(r#"name="ok\.sh""#.to_string()))
+            .with_header("content-type", "application/json")
+            .with_body(
+                r#"{ "fileId": "file-1", "fileVersionId": "version-1", "version": 1,
+                     "name": "ok.sh", "executable": true, "ownerId": "user-1" }"#,
+            )
+            .create();
+        let bad = server
+            .mock("POST", crate::FILE_UPLOAD_PATH)
+            .match_body(mockito::Matcher::Regex(r#"name="bad\.sh""#.to_string()))
+            .with_status(400)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{ "message": "Invalid script" }"#)
+            .create();
+        [ok, bad]
+    }
+
+    /// Uploads of `ok.sh` and `bad.sh`, which exist in `dir`, and of `missing.sh`, which doesn't.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    fn batch_uploads(dir: &Path) -> Vec<FileUpload> {
+        ["ok.sh", "missing.sh", "bad.sh"]
+            .into_iter()
+            .map(|name| {
+                let file_path = dir.join(name);
+                if name != "missing.sh" {
+                    std::fs::write(&file_path, "#!/bin/sh\necho hi\n").unwrap();
+                }
+                FileUpload::builder()
+                    .file_name(name.to_string())
+                    .file_path(file_path)
+                    .executable(true)
+                    .build()
+            })
+            .collect()
+    }
+
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    fn assert_batch_results(results: &[Result<UploadFileResponse, UploadFileError>]) {
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap().file_id, "file-1");
+        assert!(matches!(results[1], Err(UploadFileError::IO(_))));
+        assert!(matches!(
+            &results[2],
+            Err(UploadFileError::ApiError(error)) if error.message == "Invalid script"
+        ));
+    }
+
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    fn get_mock_client(server: &mockito::Server) -> crate::R3Client {
+        let credentials = crate::Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        crate::R3Client::builder()
+            .credentials(credentials)
+            .base_url(server.url())
+            .build()
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn test_upload_files() {
+        let mut server = mockito::Server::new();
+        let mocks = mock_batch_upload(&mut server);
+        let dir = tempfile::tempdir().unwrap();
+
+        let results = get_mock_client(&server)
+            .upload_files()
+            .uploads(batch_uploads(dir.path()))
+            .call();
+
+        assert_batch_results(&results);
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn test_verify_after_upload() {
+        let mut server = mockito::Server::new();
+        let [upload, _] = mock_batch_upload(&mut server);
+        let files_body = |ids: &[&str]| {
+            let files: Vec<serde_json::Value> = ids
+                .iter()
+                .map(|id| {
+                    serde_json::json!({
+                        "owner": { "email": "alice@example.com", "id": "user-1" }, "id": id,
+                        "name": "ok.sh", "shortDesc": null, "longDesc": null, "executable": true,
+                        "created": "2024-08-30T12:00:00.000Z", "updated": "2024-08-30T12:00:00.000Z",
+                        "versions": []
+                    })
+                })
+                .collect();
+            serde_json::json!({ "data": { "login": { "account": { "files": files } } } })
+                .to_string()
+        };
+        let polls = [files_body(&["file-0"]), files_body(&["file-0", "file-1"])].map(|body| {
+            server
+                .mock("POST", crate::GRAPHQL_PATH)
+                .match_body(mockito::Matcher::PartialJson(
+                    serde_json::json!({ "operationName": "GetFiles" }),
+                ))
+                .with_header("content-type", "application/json")
+                .with_body(body)
+                .create()
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let file_upload = batch_uploads(dir.path()).remove(0);
+
+        let response = get_mock_client(&server)
+            .upload_file()
+            .file_upload(file_upload)
+            .verify_after_upload(true)
+            .call()
+            .unwrap();
+
+        assert_eq!(response.file_id, "file-1");
+        upload.assert();
+        for poll in polls {
+            poll.assert();
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "async")]
+    async fn test_upload_files_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mocks = mock_batch_upload(&mut server);
+        let dir = tempfile::tempdir().unwrap();
+
+        let results = get_mock_client(&server)
+            .upload_files_async()
+            .uploads(batch_uploads(dir.path()))
+            .concurrency(2)
+            .call()
+            .await;
+
+        assert_batch_results(&results);
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "async")]
+    async fn test_upload_file_async_checksum() {
+        let mut server = mockito::Server::new_async().await;
+        let [upload, _] = mock_batch_upload(&mut server);
+        let dir = tempfile::tempdir().unwrap();
+        let file_upload = batch_uploads(dir.path()).remove(0);
+        let expected = file_sha256(&file_upload.file_path).unwrap();
+
+        let response = get_mock_client(&server)
+            .upload_file_async()
+            .file_upload(file_upload)
+            .compute_checksum(true)
+            .call()
+            .await
+            .unwrap();
+
+        assert_eq!(response.checksum, Some(expected));
+        upload.assert_async().await;
+    }
 }
@@ -8,12 +8,22 @@
 //!
 //! Then instantiate an [`R3Client`] using [`R3Client::builder`] and start calling the API functions on it.
 //!
+//! # Retries
+//!
+//! An [`R3Client`] doesn't retry failed requests, unless you set a [`RetryPolicy`] with [`R3ClientBuilder::retry_policy`].
+//! Note that [`RetryPolicy::default`] is *not* the same as not setting a policy: it retries up to 3 times with exponential backoff.
+//! Use [`RetryPolicy::none`] to explicitly disable retries.
+//!
 //! # Features
 //!
 //! - Enable `blocking` to use the blocking versions of the API functions from the [`api_blocking`] module.
 //! - Enable `async` to use the asynchronous versions of the API funcitons from the [`api_async`] module.
 //! - Enable `credentials_loader` to use the [`Credentials::load_from_disk`] function.
 //!     This is gated behind a feature, because it introduces additional dependencies.
+//! - Enable `keyring` to store credentials in the keychain of the operating system, see [`Credentials::load_from_keyring`].
+//! - Enable `hmac-pure` to compute request signatures, digests and checksums with the pure Rust `hmac` and `sha2` crates instead of `ring`. Together with `default-features = false`, which disables the default `ring` feature, this crate doesn't depend on `ring` at all, e.g. for `wasm32-unknown-unknown`.
+//! - Enable `log` to log the request signing and file uploads at trace level through the [`log`](https://docs.rs/log) crate. Secrets and file contents are never logged. Without this feature, this crate doesn't print or log anything.
+//! - Enable `gzip` and/or `brotli` to receive compressed responses, which helps with large device and job lists.
 //!
 
 // Enable all features for the documentation tests
@@ -26,25 +36,66 @@
 ))]
 compile_error!("The `file_upload` feature is useless on it's own. You also need to enable one of: `async`, `blocking` ");
 
-use bon::builder;
+#[cfg(not(any(feature = "ring", feature = "hmac-pure")))]
+compile_error!("Enable one of the features `ring` (enabled by default) or `hmac-pure`, to compute request signatures.");
+
+use bon::bon;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+#[cfg(feature = "blocking")]
+use std::sync::OnceLock;
+#[cfg(any(feature = "async", feature = "blocking"))]
+use std::sync::{Mutex, PoisonError};
+use std::time::Duration;
 
 #[cfg(feature = "async")]
 pub mod api_async;
 
 #[cfg(feature = "blocking")]
 pub mod api_blocking;
+#[cfg(feature = "blocking")]
+pub use api_blocking::R3Api;
 
 // If neither the `async` nor `blocking` features are enabled, then the `auth` module is not needed.
 #[cfg(any(feature = "async", feature = "blocking"))]
 pub mod auth;
 
+mod crypto;
+pub use crypto::{HmacAlgorithm, HmacKey};
+
 mod credentials;
-pub use credentials::Credentials;
+pub use credentials::{
+    Credentials, CredentialsPublic, ACCESS_KEY_ID_ENV_VAR, SECRET_ACCESS_KEY_ENV_VAR,
+};
+
+mod error;
+pub use error::{R3Error, R3GraphQlError};
+
+mod retry;
+pub use retry::{BackoffStrategy, RetryPolicy};
+
+mod environment;
+pub use environment::{Environment, STAGING_BASE_URL};
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+mod paginator;
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use paginator::{PageCursor, Paginator};
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub mod org_scoped;
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub use org_scoped::OrgScopedClient;
 
 #[cfg(feature = "credentials_loader")]
 mod credentials_loader;
 #[cfg(feature = "credentials_loader")]
-pub use credentials_loader::{CredentialProfiles, CredentialsLoaderError};
+pub use credentials_loader::{
+    CredentialProfiles, CredentialProfilesIntoIter, CredentialsLoaderError,
+};
+
+#[cfg(feature = "keyring")]
+mod credentials_keyring;
 
 pub mod operations;
 
@@ -60,6 +111,10 @@ pub const GRAPHQL_PATH: &str = "/graphql/v1";
 /// Path for file uploads. Append this to [`BASE_URL`] to get the full URL.
 pub const FILE_UPLOAD_PATH: &str = "/graphql/v1/file/upload";
 
+/// Name of the header, in which an idempotency key is sent with mutations.
+/// The API uses it to recognize retries of a request, that was already processed.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
 /// A client for the remote.it API.
 ///
 /// # Example
@@ -78,16 +133,761 @@ pub const FILE_UPLOAD_PATH: &str = "/graphql/v1/file/upload";
 /// // Start making API calls
 /// let devices = client.get_devices().call().unwrap();
 /// ```
-#[builder]
+///
+/// The client is cheap to clone. Clones share the same credentials and the same underlying HTTP connection pool,
+/// so you can hand a clone to each task or thread instead of wrapping the client in an [`Arc`] yourself.
+#[derive(Clone)]
 pub struct R3Client {
-    credentials: Credentials,
+    credentials: Arc<Credentials>,
+    /// The environment, whose host requests are signed for.
+    environment: Environment,
+    base_url: String,
+    graphql_path: String,
+    file_upload_path: String,
+    /// Whether to send and sign a `Digest` header over the request body.
+    sign_body_digest: bool,
+    /// How GraphQL requests are retried after transient failures.
+    retry_policy: RetryPolicy,
+    /// Whether GraphQL errors in a response are returned as [`R3Error::GraphQl`].
+    error_on_graphql_errors: bool,
+    /// The rate limit state of the last response, that had rate limit headers. Shared between clones.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    last_rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
+    /// The timeout, that overrides the timeout of the HTTP clients for the requests of this client.
+    request_timeout: Option<Duration>,
+    /// The difference between the clock of the API and the local clock, in milliseconds.
+    /// Shared between clones, so measuring it once is enough.
+    clock_skew_millis: Arc<AtomicI64>,
+    /// Options used to build the blocking HTTP client, which is created lazily.
+    #[cfg(feature = "blocking")]
+    http_client_options: HttpClientOptions,
+    /// Shared HTTP client for async requests. [`reqwest::Client`] is reference counted internally.
+    #[cfg(feature = "async")]
+    http_client: reqwest::Client,
+    /// Shared HTTP client for blocking requests.
+    /// This is created lazily on first use, because a blocking client must not be created inside an async runtime.
+    #[cfg(feature = "blocking")]
+    blocking_http_client: Arc<OnceLock<reqwest::blocking::Client>>,
 }
 
+#[bon]
 impl R3Client {
+    /// Creates a new [`R3Client`] using the given [`Credentials`].
+    ///
+    /// # Example
+    /// ```
+    /// # use remoteit_api::{Credentials, R3Client};
+    /// let credentials = Credentials::builder()
+    ///     .r3_access_key_id("foo")
+    ///     .r3_secret_access_key("YmFy")
+    ///     .build()
+    ///     .unwrap();
+    /// let client = R3Client::builder().credentials(credentials).build();
+    /// ```
+    ///
+    /// For long-running processes, you can tune the connection pool of the HTTP client:
+    /// ```
+    /// # use remoteit_api::{Credentials, R3Client};
+    /// # use std::time::Duration;
+    /// # let credentials = Credentials::builder()
+    /// #     .r3_access_key_id("foo")
+    /// #     .r3_secret_access_key("YmFy")
+    /// #     .build()
+    /// #     .unwrap();
+    /// let client = R3Client::builder()
+    ///     .credentials(credentials)
+    ///     .pool_max_idle_per_host(2)
+    ///     .pool_idle_timeout(Duration::from_secs(30))
+    ///     .build();
+    /// ```
+    ///
+    /// # Panics
+    /// If the HTTP client can't be initialized, e.g. because the TLS backend can't be loaded.
+    /// This is the same condition, under which [`reqwest::Client::new`] panics.
+    #[builder]
+    pub fn new(
+        credentials: Credentials,
+        /// The remote.it environment to send requests to, see [`Environment`]. Defaults to [`Environment::Production`].
+        #[builder(default)]
+        environment: Environment,
+        /// Overrides the base URL of the `environment`.
+        /// You usually only need to set this when testing against a mock server.
+        /// Unlike [`Environment::Custom`], this doesn't change the host, that requests are signed for.
+        base_url: Option<String>,
+        /// The path of the GraphQL API, appended to `base_url`. Defaults to [`GRAPHQL_PATH`].
+        /// The path is also part of the request signature, so it must be the path the server actually receives.
+        #[builder(default = GRAPHQL_PATH.to_string())]
+        graphql_path: String,
+        /// The path for file uploads, appended to `base_url`. Defaults to [`FILE_UPLOAD_PATH`].
+        /// Like `graphql_path`, this is part of the request signature.
+        #[builder(default = FILE_UPLOAD_PATH.to_string())]
+        file_upload_path: String,
+        /// Whether to send a `Digest` header with the SHA-256 hash of the body of GraphQL requests, and include it in the request signature.
+        /// This protects the integrity of the request body. Defaults to `false`, in which case only
+        /// `(request-target) host date content-type` are signed.
+        #[builder(default)]
+        sign_body_digest: bool,
+        /// How GraphQL requests are retried after transient failures, see [`RetryPolicy`].
+        /// Defaults to [`RetryPolicy::none`], so requests are not retried. [`RetryPolicy::default`] is a good starting point.
+        #[builder(default = RetryPolicy::none())]
+        retry_policy: RetryPolicy,
+        /// Whether [`R3Client::send_remoteit_graphql_request`] and its variants return [`R3Error::GraphQl`],
+        /// if the response contains GraphQL errors. Defaults to `false`, in which case the errors are returned
+        /// in [`Response::errors`](graphql_client::Response::errors) of an `Ok` response.
+        #[builder(default)]
+        error_on_graphql_errors: bool,
+        /// The maximum number of idle connections per host, that the connection pool keeps open.
+        /// Defaults to reqwest's default, which is unlimited.
+        ///
+        /// All clones of the client share one connection pool (one per blocking/async flavor),
+        /// so this limit applies to all of them together, not to each clone.
+        pool_max_idle_per_host: Option<usize>,
+        /// How long an idle connection is kept open in the connection pool.
+        /// Defaults to reqwest's default, which is 90 seconds.
+        ///
+        /// Like [`pool_max_idle_per_host`](R3ClientBuilder::pool_max_idle_per_host), this applies to the pool shared by all clones of the client.
+        pool_idle_timeout: Option<Duration>,
+        /// The timeout for each request, from connecting until the response body has been read.
+        /// Defaults to reqwest's default, which is 30 seconds for blocking requests and no timeout for async requests.
+        ///
+        /// Use [`R3Client::with_timeout`] to override this for single calls.
+        timeout: Option<Duration>,
+    ) -> Self {
+        #[cfg_attr(
+            not(any(feature = "async", feature = "blocking")),
+            allow(unused_variables)
+        )]
+        let http_client_options = HttpClientOptions {
+            pool_max_idle_per_host,
+            pool_idle_timeout,
+            timeout,
+        };
+        Self {
+            credentials: Arc::new(credentials),
+            base_url: base_url.unwrap_or_else(|| environment.base_url().to_string()),
+            environment,
+            graphql_path,
+            file_upload_path,
+            sign_body_digest,
+            retry_policy,
+            error_on_graphql_errors,
+            request_timeout: None,
+            clock_skew_millis: Arc::new(AtomicI64::new(0)),
+            #[cfg(any(feature = "async", feature = "blocking"))]
+            last_rate_limit: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "async")]
+            http_client: http_client_options.async_client(),
+            #[cfg(feature = "blocking")]
+            http_client_options,
+            #[cfg(feature = "blocking")]
+            blocking_http_client: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Reads the credentials from the environment and builds a new [`R3Client`] with the default options.
+    /// See [`Credentials::from_env`] for the environment variables.
+    ///
+    /// Use [`R3Client::builder`] with [`Credentials::from_env`] if you need to change other options of the client.
+    ///
+    /// # Errors
+    /// See [`Credentials::from_env`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use remoteit_api::R3Client;
+    /// let client = R3Client::from_env().unwrap();
+    /// ```
+    pub fn from_env() -> Result<Self, R3Error> {
+        Ok(R3Client::builder()
+            .credentials(Credentials::from_env()?)
+            .build())
+    }
+
     /// # Returns
     /// A reference to the credentials used by the client.
     #[must_use]
     pub fn credentials(&self) -> &Credentials {
         &self.credentials
     }
+
+    /// Replaces the credentials used to sign requests, for example after rotating the API key.
+    /// The HTTP clients and their connection pools are kept.
+    ///
+    /// Only this client is affected. Clones made before keep using the old credentials.
+    /// Since this requires `&mut self`, requests of this client can't be in flight while the credentials are replaced.
+    pub fn set_credentials(&mut self, credentials: Credentials) {
+        self.credentials = Arc::new(credentials);
+    }
+
+    /// # Returns
+    /// A mutable reference to the credentials used by the client.
+    ///
+    /// If the credentials are shared with clones of this client, they are copied first, so the clones are not affected.
+    /// See [`R3Client::set_credentials`].
+    #[must_use]
+    pub fn credentials_mut(&mut self) -> &mut Credentials {
+        Arc::make_mut(&mut self.credentials)
+    }
+
+    /// # Returns
+    /// The remote.it environment, that this client signs requests for.
+    #[must_use]
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    /// # Returns
+    /// The base URL of the remote.it API, that this client sends requests to.
+    #[must_use]
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// # Returns
+    /// The path of the GraphQL API, that this client sends requests to.
+    #[must_use]
+    pub fn graphql_path(&self) -> &str {
+        &self.graphql_path
+    }
+
+    /// # Returns
+    /// The path, that this client uploads files to.
+    #[must_use]
+    pub fn file_upload_path(&self) -> &str {
+        &self.file_upload_path
+    }
+
+    /// # Returns
+    /// Whether this client sends a signed `Digest` header over the body of GraphQL requests.
+    #[must_use]
+    pub fn sign_body_digest(&self) -> bool {
+        self.sign_body_digest
+    }
+
+    /// # Returns
+    /// The [`RetryPolicy`], with which this client retries GraphQL requests.
+    #[must_use]
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// # Returns
+    /// Whether this client returns GraphQL errors in a response as [`R3Error::GraphQl`].
+    #[must_use]
+    pub fn error_on_graphql_errors(&self) -> bool {
+        self.error_on_graphql_errors
+    }
+
+    /// Turns the GraphQL errors in `response` into an [`R3Error::GraphQl`], if the client is configured to do so.
+    ///
+    /// # Errors
+    /// [`R3Error::GraphQl`] if [`R3Client::error_on_graphql_errors`] is set and `response` contains errors.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn check_graphql_errors<R>(
+        &self,
+        response: graphql_client::Response<R>,
+    ) -> Result<graphql_client::Response<R>, R3Error> {
+        match response.errors {
+            Some(errors) if self.error_on_graphql_errors && !errors.is_empty() => Err(
+                R3Error::GraphQl(errors.into_iter().map(R3GraphQlError::from).collect()),
+            ),
+            _ => Ok(response),
+        }
+    }
+
+    /// Sets the difference between the clock of the remote.it API and the local clock.
+    /// Requests are then signed with the local time plus `skew`, which prevents the API from rejecting them
+    /// because of a stale `Date` header when the local clock is wrong.
+    ///
+    /// Usually you would measure this with `check_clock_skew` instead, but you can set it manually if you already know it.
+    /// The returned client doesn't share the skew with the clones of `self`.
+    #[must_use]
+    pub fn with_clock_skew(mut self, skew: chrono::Duration) -> Self {
+        self.clock_skew_millis = Arc::new(AtomicI64::new(skew.num_milliseconds()));
+        self
+    }
+
+    /// Moves the client into an [`Arc`], so it can be shared between tasks or threads.
+    ///
+    /// All methods of the client take `&self`, so they can be called through the [`Arc`].
+    /// Hand each task its own [`Arc::clone`] of it. The futures of the async methods, which send a single request, are [`Send`],
+    /// so they can be awaited in spawned tasks:
+    /// ```no_run
+    /// # use remoteit_api::R3Client;
+    /// # use std::sync::Arc;
+    /// # async fn example(client: R3Client) {
+    /// let client = client.into_shared();
+    /// for _ in 0..4 {
+    ///     let client = Arc::clone(&client);
+    ///     tokio::spawn(async move {
+    ///         let count = client.get_device_count_async().call().await.map_err(|e| e.to_string());
+    ///         println!("{count:?}");
+    ///     });
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// The same goes for the helpers, which send many requests, like `delete_files_async`, `start_job_bulk_async`,
+    /// `disconnect_all_async` or `get_devices_all_orgs_async`. The errors of their items are `Send + Sync`.
+    ///
+    /// Cloning the client is cheap as well, since clones share the credentials and connection pools.
+    /// Sharing one [`Arc`] additionally shares the settings, which are otherwise copied per clone.
+    #[must_use]
+    pub fn into_shared(self) -> Arc<Self> {
+        Arc::new(self)
+    }
+
+    /// Creates a clone of the client, whose requests time out after `timeout`.
+    /// Use this to give single calls a different timeout, e.g. a longer one for large job lists and a shorter one for the files:
+    /// ```no_run
+    /// # use remoteit_api::R3Client;
+    /// # use std::time::Duration;
+    /// # fn example(client: R3Client) -> Result<(), Box<dyn std::error::Error>> {
+    /// let jobs = client.with_timeout(Duration::from_secs(120)).get_jobs().call()?;
+    /// let files = client.with_timeout(Duration::from_secs(5)).get_files().call()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// The timeout takes precedence over the client-wide [`timeout`](R3ClientBuilder::timeout) of the builder,
+    /// no matter whether it is shorter or longer. Like the client-wide timeout, it applies to each attempt of a request separately,
+    /// from connecting until the response body has been read. The clone shares the connection pools with `self`.
+    #[must_use]
+    pub fn with_timeout(&self, timeout: Duration) -> Self {
+        Self {
+            request_timeout: Some(timeout),
+            ..self.clone()
+        }
+    }
+
+    /// # Returns
+    /// The timeout, that was set for the requests of this client with [`R3Client::with_timeout`], if any.
+    #[must_use]
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// # Returns
+    /// The difference between the clock of the remote.it API and the local clock, that is applied when signing requests.
+    /// Zero, unless it was measured or set using [`R3Client::with_clock_skew`].
+    #[must_use]
+    pub fn clock_skew(&self) -> chrono::Duration {
+        chrono::Duration::milliseconds(self.clock_skew_millis.load(Ordering::Relaxed))
+    }
+
+    /// # Returns
+    /// The date to sign a request with, corrected by the clock skew.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn signing_date(&self) -> String {
+        auth::get_date_with_offset(self.clock_skew())
+    }
+
+    /// Measures the clock skew from the `Date` header of a response and applies it to all following requests.
+    ///
+    /// # Returns
+    /// The measured clock skew, or [`None`] if the response has no valid `Date` header.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn apply_server_date(
+        &self,
+        headers: &reqwest::header::HeaderMap,
+    ) -> Option<chrono::Duration> {
+        let server_date = headers.get(reqwest::header::DATE)?.to_str().ok()?;
+        let server_date = chrono::DateTime::parse_from_rfc2822(server_date).ok()?;
+        let skew_millis =
+            (server_date.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_milliseconds();
+        self.clock_skew_millis.store(skew_millis, Ordering::Relaxed);
+        Some(chrono::Duration::milliseconds(skew_millis))
+    }
+
+    /// # Returns
+    /// The rate limit state from the headers of the last response, that had rate limit headers,
+    /// or [`None`] if no response had them yet. Updated by all requests of this client and its clones.
+    ///
+    /// Use this to pace your requests before the API starts responding with `429 Too Many Requests`.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    #[must_use]
+    pub fn last_rate_limit(&self) -> Option<RateLimitInfo> {
+        self.last_rate_limit
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Stores the rate limit state from the headers of a response, if it has rate limit headers.
+    #[cfg(any(feature = "async", feature = "blocking"))]
+    pub(crate) fn record_rate_limit(
+        &self,
+        status: reqwest::StatusCode,
+        headers: &reqwest::header::HeaderMap,
+    ) {
+        if let Some(rate_limit) = ResponseMeta::new(status, headers).rate_limit_info() {
+            *self
+                .last_rate_limit
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = Some(rate_limit);
+        }
+    }
+
+    /// # Returns
+    /// The shared blocking HTTP client. It is created on first use.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn blocking_http_client(&self) -> &reqwest::blocking::Client {
+        self.blocking_http_client
+            .get_or_init(|| self.http_client_options.blocking_client())
+    }
+
+    /// Shuts the client down and drops its HTTP clients, which closes the idle connections of their connection pools.
+    ///
+    /// The client is consumed, so it can't be used afterward. Build a new one with [`R3Client::builder`], e.g. after reloading the configuration.
+    /// Clones share the connection pools, so the connections are only closed once the last clone is shut down or dropped.
+    ///
+    /// Dropping the client has the same effect. This method only makes the point of shutdown explicit,
+    /// so you can order it with the shutdown of the rest of your program.
+    /// Inside an async runtime, use `shutdown_async` instead.
+    pub fn shutdown(self) {
+        drop(self);
+    }
+
+    /// Same as [`R3Client::shutdown()`], but can be awaited inside an async runtime.
+    ///
+    /// If the `blocking` feature is enabled, dropping the blocking HTTP client waits for its background thread to stop.
+    /// To not block the runtime, the client is dropped on a separate thread in that case, and this waits until it is dropped.
+    #[cfg(feature = "async")]
+    pub async fn shutdown_async(self) {
+        #[cfg(feature = "blocking")]
+        {
+            let (sender, receiver) = futures::channel::oneshot::channel();
+            std::thread::spawn(move || {
+                self.shutdown();
+                let _ = sender.send(());
+            });
+            let _ = receiver.await;
+        }
+        #[cfg(not(feature = "blocking"))]
+        self.shutdown();
+    }
+}
+
+/// Options for the HTTP clients used by [`R3Client`]. Unset options use reqwest's defaults.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(not(any(feature = "async", feature = "blocking")), allow(dead_code))]
+struct HttpClientOptions {
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+impl HttpClientOptions {
+    #[cfg(feature = "async")]
+    fn async_client(self) -> reqwest::Client {
+        let mut builder = reqwest::Client::builder();
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder
+            .build()
+            .expect("the HTTP client should be buildable")
+    }
+
+    #[cfg(feature = "blocking")]
+    fn blocking_client(self) -> reqwest::blocking::Client {
+        let mut builder = reqwest::blocking::Client::builder();
+        if let Some(max_idle) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max_idle);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        builder
+            .build()
+            .expect("the HTTP client should be buildable")
+    }
+}
+
+/// The result of starting a single scripting job. Contains the ID of the started job.
+///
+/// Returned per chunk by [`R3Client::start_job_bulk`] and [`R3Client::start_job_bulk_async`].
+/// The error is [`Send`] and [`Sync`], so the future of [`R3Client::start_job_bulk_async`] can be awaited in a spawned task.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub type StartJobResult = Result<String, Box<dyn std::error::Error + Send + Sync>>;
+
+/// The result of deleting a single file. Contains whether the API reported the file as deleted.
+///
+/// Returned per file by [`R3Client::delete_files`] and [`R3Client::delete_files_async`].
+/// The error is [`Send`] and [`Sync`], so the future of [`R3Client::delete_files_async`] can be awaited in a spawned task.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub type DeleteFileResult = Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+/// The result of closing a single connection. Contains whether the API reported the connection as closed.
+///
+/// Returned per connection by [`R3Client::disconnect_all`] and [`R3Client::disconnect_all_async`].
+/// The error is [`Send`] and [`Sync`], so the future of [`R3Client::disconnect_all_async`] can be awaited in a spawned task.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub type DisconnectResult = Result<bool, Box<dyn std::error::Error + Send + Sync>>;
+
+/// A single page of a list query, together with the pagination metadata returned by the API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PagedResult<T> {
+    /// The items on this page.
+    pub items: Vec<T>,
+    /// The total number of items matching the query, across all pages.
+    pub total: i64,
+    /// Whether there are more items after this page.
+    pub has_more: bool,
+    /// The offset, that was requested for this page.
+    pub offset: Option<i64>,
+    /// The limit, that was requested for this page.
+    pub limit: Option<i64>,
+}
+
+/// Metadata of a response from the remote.it API, taken from the HTTP status and headers.
+///
+/// Returned by [`R3Client::send_remoteit_graphql_request_with_meta`] and [`R3Client::send_remoteit_graphql_request_with_meta_async`].
+#[cfg(any(feature = "async", feature = "blocking"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMeta {
+    /// The HTTP status of the response.
+    pub status: reqwest::StatusCode,
+    /// The value of the `x-request-id` header, if present. Include this when reporting issues to remote.it.
+    pub request_id: Option<String>,
+    /// All rate limit headers (`ratelimit-*` and `x-ratelimit-*`), keyed by their lowercase name.
+    pub rate_limit: std::collections::BTreeMap<String, String>,
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+impl ResponseMeta {
+    /// Collects the metadata from the status and headers of a response.
+    pub(crate) fn new(status: reqwest::StatusCode, headers: &reqwest::header::HeaderMap) -> Self {
+        let header_value = |value: &reqwest::header::HeaderValue| {
+            String::from_utf8_lossy(value.as_bytes()).into_owned()
+        };
+        let rate_limit = headers
+            .iter()
+            .filter(|(name, _)| {
+                name.as_str().starts_with("ratelimit-") || name.as_str().starts_with("x-ratelimit-")
+            })
+            .map(|(name, value)| (name.as_str().to_string(), header_value(value)))
+            .collect();
+        Self {
+            status,
+            request_id: headers.get("x-request-id").map(header_value),
+            rate_limit,
+        }
+    }
+
+    /// The number of requests remaining in the current rate limit window,
+    /// taken from the `ratelimit-remaining` or `x-ratelimit-remaining` header.
+    #[must_use]
+    pub fn rate_limit_remaining(&self) -> Option<u64> {
+        ["ratelimit-remaining", "x-ratelimit-remaining"]
+            .iter()
+            .find_map(|name| self.rate_limit.get(*name))
+            .and_then(|value| value.trim().parse().ok())
+    }
+
+    /// # Returns
+    /// The rate limit state from the rate limit headers, or [`None`] if the response has none of them.
+    #[must_use]
+    pub fn rate_limit_info(&self) -> Option<RateLimitInfo> {
+        let header = |name: &str| {
+            [format!("ratelimit-{name}"), format!("x-ratelimit-{name}")]
+                .iter()
+                .find_map(|name| self.rate_limit.get(name))
+                .and_then(|value| value.trim().parse::<u64>().ok())
+        };
+        let info = RateLimitInfo {
+            limit: header("limit"),
+            remaining: header("remaining"),
+            reset: header("reset").map(|reset| {
+                if reset >= RateLimitInfo::RESET_EPOCH_THRESHOLD {
+                    chrono::DateTime::from_timestamp(i64::try_from(reset).unwrap_or(i64::MAX), 0)
+                        .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC)
+                } else {
+                    chrono::Utc::now()
+                        + chrono::Duration::seconds(i64::try_from(reset).unwrap_or(i64::MAX))
+                }
+            }),
+        };
+        (info.limit.is_some() || info.remaining.is_some() || info.reset.is_some()).then_some(info)
+    }
+}
+
+/// The rate limit state of the remote.it API, as reported by the rate limit headers of a response.
+///
+/// Returned by [`R3Client::last_rate_limit`] and [`ResponseMeta::rate_limit_info`].
+/// Each value is taken from the `ratelimit-*` header, or the `x-ratelimit-*` header if the former is missing.
+#[cfg(any(feature = "async", feature = "blocking"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// The maximum number of requests in the current rate limit window.
+    pub limit: Option<u64>,
+    /// The number of requests remaining in the current rate limit window.
+    pub remaining: Option<u64>,
+    /// When the current rate limit window resets.
+    /// The `reset` header is read as a Unix timestamp if it is large enough to be one, and as seconds from now otherwise.
+    pub reset: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[cfg(any(feature = "async", feature = "blocking"))]
+impl RateLimitInfo {
+    /// Values of the `reset` header from this on are Unix timestamps (September 2001), smaller values are seconds from now.
+    const RESET_EPOCH_THRESHOLD: u64 = 1_000_000_000;
+}
+
+/// Applies `offset` and `limit` to a list of items, for queries which the API doesn't paginate itself.
+/// Negative values are treated as zero.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn paginate<T>(items: &mut Vec<T>, limit: Option<i64>, offset: Option<i64>) {
+    let offset = offset.map_or(0, |offset| usize::try_from(offset).unwrap_or(0));
+    items.drain(..offset.min(items.len()));
+    if let Some(limit) = limit {
+        items.truncate(usize::try_from(limit).unwrap_or(0));
+    }
+}
+
+/// Generates a new random idempotency key in the form of a version 4 UUID.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn generate_idempotency_key() -> String {
+    let mut bytes = [0u8; 16];
+    crypto::fill_random(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = format!("{:032x}", u128::from_be_bytes(bytes));
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Parses the body of a GraphQL response.
+///
+/// Some mutations answer with an empty body (e.g. `204 No Content`). On a success status,
+/// an empty body is treated as a response without data and without errors.
+///
+/// # Errors
+/// Any error that occurs during deserialization of a non-empty body.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn parse_graphql_response<R: for<'a> serde::Deserialize<'a>>(
+    status: reqwest::StatusCode,
+    body: &[u8],
+) -> Result<graphql_client::Response<R>, serde_json::Error> {
+    if status.is_success() && body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(graphql_client::Response {
+            data: None,
+            errors: None,
+            extensions: None,
+        });
+    }
+    serde_json::from_slice(body)
+}
+
+/// Extension methods for [`graphql_client::Response`].
+pub trait ResponseExt {
+    /// Formats the response as pretty-printed JSON, e.g. for logging, debugging, or capturing fixtures for tests.
+    /// `errors` and `extensions` are left out, if the response has none, like the API does.
+    ///
+    /// Use it with the untyped responses of e.g. [`R3Client::send_remoteit_graphql_request_value`],
+    /// since the generated response types of [`operations`] are not serializable.
+    ///
+    /// # Errors
+    /// Any error that occurs during serialization of the response.
+    fn to_pretty_json(&self) -> Result<String, serde_json::Error>;
+}
+
+impl<T: serde::Serialize> ResponseExt for graphql_client::Response<T> {
+    fn to_pretty_json(&self) -> Result<String, serde_json::Error> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some(object) = value.as_object_mut() {
+            for key in ["errors", "extensions"] {
+                if object.get(key).is_some_and(serde_json::Value::is_null) {
+                    object.remove(key);
+                }
+            }
+        }
+        serde_json::to_string_pretty(&value)
+    }
+}
+
+/// Converts a typed GraphQL query into an untyped one, so that different queries can be sent together
+/// using [`R3Client::send_batch`] or [`R3Client::send_batch_async`].
+///
+/// # Errors
+/// Any error that occurs during serialization of the variables.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub fn batch_query<V: serde::Serialize>(
+    query_body: &graphql_client::QueryBody<V>,
+) -> Result<graphql_client::QueryBody<serde_json::Value>, serde_json::Error> {
+    Ok(graphql_client::QueryBody {
+        variables: serde_json::to_value(&query_body.variables)?,
+        query: query_body.query,
+        operation_name: query_body.operation_name,
+    })
+}
+
+/// Converts an untyped GraphQL response, as returned by a batch, into a typed one.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn typed_response<R: serde::de::DeserializeOwned>(
+    response: graphql_client::Response<serde_json::Value>,
+) -> Result<graphql_client::Response<R>, serde_json::Error> {
+    Ok(graphql_client::Response {
+        data: response.data.map(serde_json::from_value).transpose()?,
+        errors: response.errors,
+        extensions: response.extensions,
+    })
+}
+
+/// Converts the error of a request into one, that is [`Send`] and [`Sync`].
+///
+/// The requests return `Box<dyn Error>`, even though all errors they produce are [`Send`] and [`Sync`].
+/// The per-item results of the bulk helpers need the bounds, so their futures can be awaited in spawned tasks.
+/// Errors of the known types keep their type, so they can still be downcast. Any other error is replaced by its message.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn into_send_error(
+    error: Box<dyn std::error::Error>,
+) -> Box<dyn std::error::Error + Send + Sync> {
+    let error = match error.downcast::<R3Error>() {
+        Ok(error) => return error,
+        Err(error) => error,
+    };
+    let error = match error.downcast::<reqwest::Error>() {
+        Ok(error) => return error,
+        Err(error) => error,
+    };
+    let error = match error.downcast::<serde_json::Error>() {
+        Ok(error) => return error,
+        Err(error) => error,
+    };
+    match error.downcast::<std::io::Error>() {
+        Ok(error) => error,
+        Err(error) => error.to_string().into(),
+    }
+}
+
+/// Extracts the data from a GraphQL response.
+///
+/// # Errors
+/// - [`R3Error::GraphQl`] if the response contains any errors.
+/// - [`R3Error::NoData`] if the response contains neither data nor errors.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn response_data<R>(response: graphql_client::Response<R>) -> Result<R, R3Error> {
+    match response.errors {
+        Some(errors) if !errors.is_empty() => Err(R3Error::GraphQl(
+            errors.into_iter().map(R3GraphQlError::from).collect(),
+        )),
+        _ => response.data.ok_or(R3Error::NoData),
+    }
 }
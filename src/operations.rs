@@ -1,8 +1,11 @@
 #![allow(missing_docs)]
 
-use std::fmt::Display;
+use bon::builder;
 use chrono::Local;
 use graphql_client::GraphQLQuery;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+use std::str::FromStr;
 
 /// Define [`DateTime`] as a [`chrono::DateTime<Local>`], because it is not a built-in type in GraphQL.
 type DateTime = chrono::DateTime<Local>;
@@ -22,6 +25,36 @@ type Object = serde_json::Map<String, Any>;
 )]
 pub struct GetFiles;
 
+impl get_files::ResponseData {
+    /// Removes all files, which are not executable scripts (`executable == true`) or not assets (`executable == false`).
+    ///
+    /// The API can't filter files by this, so this is what [`R3Client::get_files`](crate::R3Client::get_files) uses to filter on the client side.
+    pub fn retain_executable(&mut self, executable: bool) {
+        if let Some(account) = self.login.as_mut().and_then(|login| login.account.as_mut()) {
+            account.files.retain(|file| file.executable == executable);
+        }
+    }
+
+    /// Removes all files, which are not owned by the user with the given ID.
+    ///
+    /// The API can't filter files by owner either, so this is what [`R3Client::get_files`](crate::R3Client::get_files) uses to filter on the client side.
+    pub fn retain_owner(&mut self, owner_id: &str) {
+        if let Some(account) = self.login.as_mut().and_then(|login| login.account.as_mut()) {
+            account.files.retain(|file| file.owner.id == owner_id);
+        }
+    }
+
+    /// # Returns
+    /// Whether the file with the given ID is in the list.
+    #[must_use]
+    pub fn contains_file(&self, file_id: &str) -> bool {
+        self.login
+            .as_ref()
+            .and_then(|login| login.account.as_ref())
+            .is_some_and(|account| account.files.iter().any(|file| file.id == file_id))
+    }
+}
+
 /// Mutation, which deletes a file from remote.it. Deletes all versions of the file.
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -45,10 +78,44 @@ pub struct DeleteFileVersion;
 #[graphql(
     schema_path = "src/graphql/schema.json",
     query_path = "src/graphql/StartJob.graphql",
-    response_derives = "Debug"
+    response_derives = "Debug",
+    variables_derives = "Clone"
 )]
 pub struct StartJob;
 
+impl start_job::ArgumentInput {
+    /// Creates a new script argument with the given name and value.
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Converts name/value pairs into script arguments, for passing them to [`R3Client::start_job`](crate::R3Client::start_job).
+    ///
+    /// # Example
+    /// ```
+    /// # use remoteit_api::operations::start_job::ArgumentInput;
+    /// let arguments = ArgumentInput::from_pairs([("env", "prod"), ("delay", "10")]);
+    /// assert_eq!(arguments.len(), 2);
+    /// assert_eq!(arguments[0].name, "env");
+    /// ```
+    pub fn from_pairs<K, V>(pairs: impl IntoIterator<Item = (K, V)>) -> Vec<Self>
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        pairs.into_iter().map(Self::from).collect()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> From<(K, V)> for start_job::ArgumentInput {
+    fn from((name, value): (K, V)) -> Self {
+        Self::new(name, value)
+    }
+}
+
 /// Execution, to cancel a job. See remote.it docs on more information on when jobs can be cancelled.
 #[derive(GraphQLQuery)]
 #[graphql(
@@ -64,9 +131,176 @@ pub struct CancelJob;
 #[graphql(
     schema_path = "src/graphql/schema.json",
     query_path = "src/graphql/GetJobs.graphql",
-    response_derives = "Debug"
+    response_derives = "Debug",
+    variables_derives = "Clone, PartialEq, Eq, Hash"
 )]
 pub struct GetJobs;
+
+/// Query, which counts the jobs per status, without retrieving the jobs.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetJobSummary.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetJobSummary;
+
+impl get_job_summary::GetJobSummaryLoginAccount {
+    /// # Returns
+    /// The number of jobs for each status in [`get_jobs::JobStatusEnum::all`].
+    #[must_use]
+    pub fn into_counts(self) -> HashMap<get_jobs::JobStatusEnum, u64> {
+        let count = |total: i64| u64::try_from(total).unwrap_or(0);
+        HashMap::from([
+            (get_jobs::JobStatusEnum::WAITING, count(self.waiting.total)),
+            (get_jobs::JobStatusEnum::RUNNING, count(self.running.total)),
+            (get_jobs::JobStatusEnum::FAILED, count(self.failed.total)),
+            (get_jobs::JobStatusEnum::SUCCESS, count(self.success.total)),
+            (
+                get_jobs::JobStatusEnum::CANCELLED,
+                count(self.cancelled.total),
+            ),
+        ])
+    }
+}
+
+/// Query, which retrieves the versions of a single file.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetFileVersions.graphql",
+    response_derives = "Debug, Clone"
+)]
+pub struct GetFileVersions;
+
+/// A single version of a file, as returned by [`R3Client::get_file_versions`](crate::R3Client::get_file_versions).
+pub type FileVersion = get_file_versions::GetFileVersionsLoginAccountFilesVersions;
+
+/// Query, which retrieves a single job, including the status of the job on each device.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetJob.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetJob;
+
+/// A single job, as returned by [`R3Client::get_job`](crate::R3Client::get_job).
+pub type Job = get_job::GetJobLoginAccountJobsItems;
+
+/// Query, which retrieves the result of a job on each device it ran on.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetJobResults.graphql",
+    response_derives = "Debug, Clone, PartialEq, Eq"
+)]
+pub struct GetJobResults;
+
+/// The result of a job on a single device, as returned by [`R3Client::get_job_results`](crate::R3Client::get_job_results).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobDeviceResult {
+    /// The ID of the device the job ran on.
+    pub device_id: String,
+    /// The status of the job on this device.
+    pub status: get_job_results::JobStatusEnum,
+    /// The exit code of the script, if it has finished.
+    pub exit_code: Option<i32>,
+    /// The combined stdout and stderr of the script.
+    /// This is not truncated by this crate and can be large for chatty scripts, so avoid cloning it needlessly.
+    /// Empty if the script has not produced any output yet.
+    pub output: String,
+}
+
+impl JobDeviceResult {
+    /// The attribute key, under which the exit code of the script is reported.
+    pub const EXIT_CODE_KEY: &'static str = "exitCode";
+    /// The attribute key, under which the output of the script is reported.
+    pub const OUTPUT_KEY: &'static str = "output";
+}
+
+impl From<get_job_results::GetJobResultsLoginAccountJobsItemsJobDevices> for JobDeviceResult {
+    fn from(job_device: get_job_results::GetJobResultsLoginAccountJobsItemsJobDevices) -> Self {
+        let mut exit_code = None;
+        let mut output = String::new();
+        for attribute in job_device.attributes.into_iter().flatten() {
+            match attribute.key.as_str() {
+                Self::EXIT_CODE_KEY => exit_code = attribute.value.trim().parse().ok(),
+                Self::OUTPUT_KEY => output = attribute.value,
+                _ => {}
+            }
+        }
+        Self {
+            device_id: job_device.device.id,
+            status: job_device.status,
+            exit_code,
+            output,
+        }
+    }
+}
+
+impl get_job::JobStatusEnum {
+    /// # Returns
+    /// `true` if the job will not change its status anymore, i.e. it succeeded, failed or was cancelled.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::SUCCESS | Self::FAILED | Self::CANCELLED)
+    }
+}
+
+impl get_jobs::JobStatusEnum {
+    /// # Returns
+    /// All known job statuses. Useful for example for listing the possible values of a CLI argument.
+    #[must_use]
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::WAITING,
+            Self::RUNNING,
+            Self::FAILED,
+            Self::SUCCESS,
+            Self::CANCELLED,
+        ]
+    }
+}
+
+impl Display for get_jobs::JobStatusEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WAITING => write!(f, "WAITING"),
+            Self::RUNNING => write!(f, "RUNNING"),
+            Self::FAILED => write!(f, "FAILED"),
+            Self::SUCCESS => write!(f, "SUCCESS"),
+            Self::CANCELLED => write!(f, "CANCELLED"),
+            Self::Other(other) => write!(f, "{other}"),
+        }
+    }
+}
+
+/// # Returns
+/// All known job statuses, the same as [`get_jobs::JobStatusEnum::all`].
+/// Together with the [`Display`] and [`FromStr`] impls of [`get_jobs::JobStatusEnum`], you can list and parse the values of e.g. a `--status` CLI argument.
+#[must_use]
+pub fn all_job_statuses() -> &'static [get_jobs::JobStatusEnum] {
+    get_jobs::JobStatusEnum::all()
+}
+
+/// Error returned when parsing a [`get_jobs::JobStatusEnum`] from a string that is not a known job status.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown job status `{0}`")]
+pub struct ParseJobStatusError(pub String);
+
+impl FromStr for get_jobs::JobStatusEnum {
+    type Err = ParseJobStatusError;
+
+    /// Parses a job status. The comparison is case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .iter()
+            .find(|status| status.to_string().eq_ignore_ascii_case(s))
+            .cloned()
+            .ok_or_else(|| ParseJobStatusError(s.to_string()))
+    }
+}
 // endregion
 // region Organizations
 #[derive(GraphQLQuery)]
@@ -84,6 +318,329 @@ pub struct GetOwnedOrganization;
     response_derives = "Debug"
 )]
 pub struct GetOrganizationSelfMembership;
+
+/// Query, which retrieves the members of an organization, including their user ID, email and role.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetOrganizationMembers.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetOrganizationMembers;
+
+/// Mutation, which invites users to an organization, or changes the role of existing members.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/InviteOrganizationMember.graphql",
+    response_derives = "Debug"
+)]
+pub struct InviteOrganizationMember;
+
+/// Mutation, which removes users from an organization.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/RemoveOrganizationMember.graphql",
+    response_derives = "Debug"
+)]
+pub struct RemoveOrganizationMember;
+
+/// The role of a member within an organization.
+///
+/// In the GraphQL schema roles are plain strings in some places, so this type is used instead of the generated enums.
+/// Roles, that are unknown to this crate, are preserved in [`OrganizationRole::Unknown`] instead of failing deserialization.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OrganizationRole {
+    /// A regular member of the organization.
+    Member,
+    /// An administrator of the organization.
+    Admin,
+    /// A member without access to the organization's devices.
+    NoAccess,
+    /// A role, that is unknown to this crate.
+    Unknown(String),
+}
+
+impl OrganizationRole {
+    /// # Returns
+    /// The name of the role, as used by the remote.it API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            OrganizationRole::Member => "MEMBER",
+            OrganizationRole::Admin => "ADMIN",
+            OrganizationRole::NoAccess => "NO_ACCESS",
+            OrganizationRole::Unknown(role) => role,
+        }
+    }
+}
+
+impl Display for OrganizationRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for OrganizationRole {
+    type Err = std::convert::Infallible;
+
+    /// Parses a role. The comparison is case-insensitive. Unknown roles are parsed into [`OrganizationRole::Unknown`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "MEMBER" => OrganizationRole::Member,
+            "ADMIN" => OrganizationRole::Admin,
+            "NO_ACCESS" => OrganizationRole::NoAccess,
+            _ => OrganizationRole::Unknown(s.to_string()),
+        })
+    }
+}
+
+impl serde::Serialize for OrganizationRole {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for OrganizationRole {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let role = String::deserialize(deserializer)?;
+        Ok(role.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+impl TryFrom<OrganizationRole> for invite_organization_member::OrganizationRole {
+    type Error = crate::R3Error;
+
+    fn try_from(role: OrganizationRole) -> Result<Self, Self::Error> {
+        match role {
+            OrganizationRole::Member => Ok(Self::MEMBER),
+            OrganizationRole::Admin => Ok(Self::ADMIN),
+            OrganizationRole::NoAccess => Ok(Self::NO_ACCESS),
+            OrganizationRole::Unknown(role) => Err(crate::R3Error::InvalidArgument(format!(
+                "`{role}` is not a role that can be assigned to a member"
+            ))),
+        }
+    }
+}
+
+/// A permission, that can be granted to a role or a user.
+///
+/// Permissions, that are unknown to this crate, are preserved in [`Permission::Unknown`] instead of failing deserialization.
+/// The roles returned by [`GetOwnedOrganization`] and [`GetOrganizationSelfMembership`] list their permissions with `typed_permissions()`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// View devices and services.
+    View,
+    /// Connect to services.
+    Connect,
+    /// Run scripts on devices.
+    Scripting,
+    /// Manage devices and services.
+    Manage,
+    /// Administrate the organization.
+    Admin,
+    /// A permission, that is unknown to this crate.
+    Unknown(String),
+}
+
+impl Permission {
+    /// # Returns
+    /// The name of the permission, as used by the remote.it API.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        match self {
+            Permission::View => "VIEW",
+            Permission::Connect => "CONNECT",
+            Permission::Scripting => "SCRIPTING",
+            Permission::Manage => "MANAGE",
+            Permission::Admin => "ADMIN",
+            Permission::Unknown(permission) => permission,
+        }
+    }
+}
+
+impl Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Permission {
+    type Err = std::convert::Infallible;
+
+    /// Parses a permission. The comparison is case-insensitive. Unknown permissions are parsed into [`Permission::Unknown`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_uppercase().as_str() {
+            "VIEW" => Permission::View,
+            "CONNECT" => Permission::Connect,
+            "SCRIPTING" => Permission::Scripting,
+            "MANAGE" => Permission::Manage,
+            "ADMIN" => Permission::Admin,
+            _ => Permission::Unknown(s.to_string()),
+        })
+    }
+}
+
+impl serde::Serialize for Permission {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Permission {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let permission = String::deserialize(deserializer)?;
+        Ok(permission.parse().unwrap_or_else(|never| match never {}))
+    }
+}
+
+impl From<&get_owned_organization::RolePermission> for Permission {
+    fn from(permission: &get_owned_organization::RolePermission) -> Self {
+        match permission {
+            get_owned_organization::RolePermission::VIEW => Permission::View,
+            get_owned_organization::RolePermission::CONNECT => Permission::Connect,
+            get_owned_organization::RolePermission::SCRIPTING => Permission::Scripting,
+            get_owned_organization::RolePermission::MANAGE => Permission::Manage,
+            get_owned_organization::RolePermission::ADMIN => Permission::Admin,
+            get_owned_organization::RolePermission::Other(permission) => {
+                Permission::Unknown(permission.clone())
+            }
+        }
+    }
+}
+
+impl From<&get_organization_self_membership::RolePermission> for Permission {
+    fn from(permission: &get_organization_self_membership::RolePermission) -> Self {
+        match permission {
+            get_organization_self_membership::RolePermission::VIEW => Permission::View,
+            get_organization_self_membership::RolePermission::CONNECT => Permission::Connect,
+            get_organization_self_membership::RolePermission::SCRIPTING => Permission::Scripting,
+            get_organization_self_membership::RolePermission::MANAGE => Permission::Manage,
+            get_organization_self_membership::RolePermission::ADMIN => Permission::Admin,
+            get_organization_self_membership::RolePermission::Other(permission) => {
+                Permission::Unknown(permission.clone())
+            }
+        }
+    }
+}
+
+impl get_owned_organization::GetOwnedOrganizationLoginOrganizationRoles {
+    /// # Returns
+    /// The permissions, that this role grants, as [`Permission`]s.
+    #[must_use]
+    pub fn typed_permissions(&self) -> Vec<Permission> {
+        self.permissions.iter().map(Permission::from).collect()
+    }
+}
+
+impl get_organization_self_membership::GetOrganizationSelfMembershipLoginMembershipCustomRole {
+    /// # Returns
+    /// The permissions, that your role in the organization grants, as [`Permission`]s.
+    #[must_use]
+    pub fn typed_permissions(&self) -> Vec<Permission> {
+        self.permissions.iter().map(Permission::from).collect()
+    }
+}
+// endregion
+// region Account
+
+/// Query, which retrieves the account of the currently authenticated user.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetSelf.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetSelf;
+
+/// Query, which retrieves the plan of an account, its limits and its device usage.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetPlan.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetPlan;
+
+/// The name of the limit, which holds the maximum number of devices of an account.
+pub const DEVICE_LIMIT_NAME: &str = "devices";
+
+/// The plan of an account and its usage, as returned by [`R3Client::get_plan`](crate::R3Client::get_plan).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountPlan {
+    /// The ID of the plan. `None` for free accounts, which have no license.
+    pub plan_id: Option<String>,
+    /// The name of the plan. `None` for free accounts, which have no license.
+    pub plan_name: Option<String>,
+    /// Whether the plan is a commercial plan.
+    pub commercial: bool,
+    /// When the license of the plan expires, if it does.
+    pub expiration: Option<DateTime>,
+    /// The maximum number of devices, if the account has a device limit.
+    pub device_limit: Option<u64>,
+    /// The number of devices in the account.
+    pub devices_total: u64,
+    /// The number of devices, which are currently online.
+    pub devices_online: u64,
+    /// The number of devices, which are covered by a license.
+    pub devices_licensed: u64,
+    /// All limits of the account. The values are passed through as the API returns them.
+    pub limits: Vec<PlanLimit>,
+}
+
+/// A limit of an account, e.g. the maximum number of devices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanLimit {
+    /// The name of the limit, e.g. [`DEVICE_LIMIT_NAME`].
+    pub name: String,
+    /// The maximum allowed by the plan. `None` if unlimited.
+    pub value: Option<Any>,
+    /// The current usage, if the API reports it.
+    pub actual: Option<Any>,
+}
+
+impl From<get_plan::GetPlanLoginAccount> for AccountPlan {
+    fn from(account: get_plan::GetPlanLoginAccount) -> Self {
+        // Prefer a valid license, accounts may keep expired licenses around.
+        let license = account
+            .licenses
+            .iter()
+            .find(|license| license.valid)
+            .or_else(|| account.licenses.first());
+        let limits: Vec<PlanLimit> = account
+            .limits
+            .into_iter()
+            .map(|limit| PlanLimit {
+                name: limit.name,
+                value: limit.value.filter(|value| !value.is_null()),
+                actual: limit.actual.filter(|actual| !actual.is_null()),
+            })
+            .collect();
+        let device_limit = limits
+            .iter()
+            .find(|limit| limit.name == DEVICE_LIMIT_NAME)
+            .and_then(|limit| limit.value.as_ref())
+            .and_then(|value| {
+                value
+                    .as_u64()
+                    .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+            });
+        let devices = account.info.map(|info| info.devices);
+        let count = |n: Option<i64>| n.and_then(|n| u64::try_from(n).ok()).unwrap_or_default();
+        Self {
+            plan_id: license.map(|license| license.plan.id.clone()),
+            plan_name: license.map(|license| license.plan.name.clone()),
+            commercial: license.is_some_and(|license| license.plan.commercial),
+            expiration: license.and_then(|license| license.expiration),
+            device_limit,
+            devices_total: count(devices.as_ref().map(|d| d.total)),
+            devices_online: count(devices.as_ref().map(|d| d.online)),
+            devices_licensed: count(devices.as_ref().map(|d| d.licensed)),
+            limits,
+        }
+    }
+}
 // endregion
 // region Devices and Services
 
@@ -105,6 +662,15 @@ pub struct GetApplicationTypes;
     response_derives = "Debug"
 )]
 pub struct GetDevices;
+
+/// Query, which retrieves the number of devices, without fetching the devices themselves.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetDeviceCount.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetDeviceCount;
 /// Represents the state of a device.
 /// This is a implemented as a custom type, because in the GraphQL schema this is just a string.
 ///
@@ -114,17 +680,563 @@ pub struct GetDevices;
 /// - [`DeviceState::Inactive`] corresponds to the device being offline.
 ///
 /// The online-state of a device is also represented in the `online` field of the device. (when querying devices)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum DeviceState {
     /// The device is online.
     Active,
     /// The device is offline.
     Inactive,
 }
+
+impl DeviceState {
+    /// # Returns
+    /// The name of the state, as used by the remote.it API.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeviceState::Active => "active",
+            DeviceState::Inactive => "inactive",
+        }
+    }
+}
+
 impl Display for DeviceState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing a [`DeviceState`] from a string that is neither `active` nor `inactive`.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown device state `{0}`")]
+pub struct ParseDeviceStateError(pub String);
+
+impl FromStr for DeviceState {
+    type Err = ParseDeviceStateError;
+
+    /// Parses a device state. The comparison is case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [DeviceState::Active, DeviceState::Inactive]
+            .into_iter()
+            .find(|state| state.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseDeviceStateError(s.to_string()))
+    }
+}
+
+impl get_devices::GetDevicesLoginAccountDevicesItems {
+    /// # Returns
+    /// - [`true`] if the device is online.
+    /// - [`false`] if the device is offline.
+    #[must_use]
+    pub fn is_online(&self) -> bool {
+        self.online
+    }
+}
+
+/// A device, with the fields of [`R3Client::get_devices`](crate::R3Client::get_devices) flattened into a plain struct.
+/// Returned by [`R3Client::get_devices_typed`](crate::R3Client::get_devices_typed).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Device {
+    /// The ID of the device. Use this to reference the device in other API calls, e.g. for starting scripting jobs.
+    pub id: String,
+    /// The name of the device. Empty, if the device has no name.
+    pub name: String,
+    /// The state of the device as reported by the API, e.g. `"active"` or `"inactive"`. See [`DeviceState`].
+    pub state: String,
+    /// Whether the device is online.
+    pub online: bool,
+    /// When the device was created, if known.
+    pub created: Option<DateTime>,
+    /// When the device last reported to remote.it, if ever.
+    pub last_reported: Option<DateTime>,
+    /// The numeric platform code of the device (e.g. Linux, Windows), if known.
+    pub platform: Option<i64>,
+    /// The version of the remote.it agent running on the device, if known.
+    pub agent_version: Option<String>,
+    /// The hardware ID of the device, if it was registered with one.
+    pub hardware_id: Option<String>,
+    /// The names of the tags of the device.
+    pub tags: Vec<String>,
+    /// The services hosted on the device.
+    pub services: Vec<DeviceService>,
+}
+
+impl Device {
+    /// # Returns
+    /// How long ago the device last reported to remote.it, or `None` if it never did.
+    ///
+    /// Use this to find stale devices, since a device can still be marked online for a while after it stopped reporting.
+    #[must_use]
+    pub fn age(&self) -> Option<chrono::Duration> {
+        self.last_reported
+            .map(|last_reported| Local::now().signed_duration_since(last_reported))
+    }
+}
+
+/// Picks the ID of the only device in `devices`, whose name is exactly `name`.
+/// Used to resolve device names, since the name filter of the API also matches devices whose name only contains `name`.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn unique_device_id(name: &str, devices: Vec<Device>) -> Result<String, crate::R3Error> {
+    let mut device_ids: Vec<String> = devices
+        .into_iter()
+        .filter(|device| device.name == name)
+        .map(|device| device.id)
+        .collect();
+    match device_ids.len() {
+        0 => Err(crate::R3Error::DeviceNameNotFound(name.to_string())),
+        1 => Ok(device_ids.remove(0)),
+        _ => Err(crate::R3Error::AmbiguousDeviceName {
+            name: name.to_string(),
+            device_ids,
+        }),
+    }
+}
+
+/// A [`Device`], tagged with the organization it was listed in.
+/// Returned by [`R3Client::get_devices_all_orgs`](crate::R3Client::get_devices_all_orgs).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrgDevice {
+    /// The ID of the organization the device was listed in.
+    pub org_id: String,
+    /// The device.
+    pub device: Device,
+}
+
+/// Query, which retrieves whether a device is online, without fetching the rest of the device.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetDeviceReachability.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetDeviceReachability;
+
+/// Whether a device is online, as returned by [`R3Client::is_device_reachable`](crate::R3Client::is_device_reachable).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceReachability {
+    /// The ID of the device.
+    pub device_id: String,
+    /// Whether remote.it currently considers the device online.
+    pub online: bool,
+    /// When the device last reported to remote.it, if it ever did.
+    pub last_reported: Option<DateTime>,
+}
+
+impl DeviceReachability {
+    /// # Returns
+    /// How long ago the device last reported to remote.it, or `None` if it never did.
+    #[must_use]
+    pub fn last_reported_age(&self) -> Option<chrono::Duration> {
+        self.last_reported
+            .map(|last_reported| Local::now().signed_duration_since(last_reported))
+    }
+}
+
+impl From<get_device_reachability::GetDeviceReachabilityLoginDevice> for DeviceReachability {
+    fn from(device: get_device_reachability::GetDeviceReachabilityLoginDevice) -> Self {
+        Self {
+            device_id: device.id,
+            online: device.online,
+            last_reported: device.last_reported,
+        }
+    }
+}
+
+/// A service hosted on a [`Device`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceService {
+    /// The ID of the service.
+    pub id: String,
+    /// The name of the service. Empty, if the service has no name.
+    pub name: String,
+    /// The numeric application type of the service. See [`R3Client::get_application_types`](crate::R3Client::get_application_types).
+    pub application: Option<i64>,
+    /// Whether the service is enabled.
+    pub enabled: bool,
+}
+
+impl From<get_devices::GetDevicesLoginAccountDevicesItems> for Device {
+    fn from(device: get_devices::GetDevicesLoginAccountDevicesItems) -> Self {
+        Self {
+            id: device.id,
+            name: device.name.unwrap_or_default(),
+            state: device.state,
+            online: device.online,
+            created: device.created,
+            last_reported: device.last_reported,
+            platform: device.platform,
+            agent_version: device.version,
+            hardware_id: device.hardware_id,
+            tags: device.tags.into_iter().map(|tag| tag.name).collect(),
+            services: device
+                .services
+                .into_iter()
+                .map(|service| DeviceService {
+                    id: service.id,
+                    name: service.name.unwrap_or_default(),
+                    application: service.application,
+                    enabled: service.enabled,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Splits devices, as returned by [`R3Client::get_devices`](crate::R3Client::get_devices), into online and offline devices.
+/// The order of the devices is preserved within each list.
+///
+/// # Returns
+/// A tuple of the online devices and the offline devices.
+pub fn partition_devices(
+    devices: impl IntoIterator<Item = get_devices::GetDevicesLoginAccountDevicesItems>,
+) -> (
+    Vec<get_devices::GetDevicesLoginAccountDevicesItems>,
+    Vec<get_devices::GetDevicesLoginAccountDevicesItems>,
+) {
+    devices
+        .into_iter()
+        .partition(get_devices::GetDevicesLoginAccountDevicesItems::is_online)
+}
+
+/// Query, which retrieves the tags of a device.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetDeviceTags.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetDeviceTags;
+
+/// Mutation, which adds tags to devices.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/AddDeviceTag.graphql",
+    response_derives = "Debug"
+)]
+pub struct AddDeviceTag;
+
+/// Mutation, which removes tags from devices.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/RemoveDeviceTag.graphql",
+    response_derives = "Debug"
+)]
+pub struct RemoveDeviceTag;
+
+/// Mutation, which transfers a device to another account.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/TransferDevice.graphql",
+    response_derives = "Debug"
+)]
+pub struct TransferDevice;
+
+/// The new owner of a device, when transferring it using [`R3Client::transfer_device`](crate::R3Client::transfer_device).
+///
+/// The remote.it API identifies the new owner by the email of their account.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TransferTarget {
+    /// Transfer the device to the user with the given email.
+    User {
+        /// The email of the user.
+        email: String,
+    },
+    /// Transfer the device to the organization, which is owned by the user with the given email.
+    Organization {
+        /// The email of the owner of the organization.
+        owner_email: String,
+    },
+}
+
+impl TransferTarget {
+    /// # Returns
+    /// The email, that identifies the new owner of the device.
+    #[must_use]
+    pub fn email(&self) -> &str {
         match self {
-            DeviceState::Active => write!(f, "active"),
-            DeviceState::Inactive => write!(f, "inactive"),
+            TransferTarget::User { email } => email,
+            TransferTarget::Organization { owner_email } => owner_email,
+        }
+    }
+}
+
+/// The ID of a service on a device.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ServiceId(pub String);
+
+impl Display for ServiceId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for ServiceId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for ServiceId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl AsRef<str> for ServiceId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Mutation, which enables or disables a service.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/SetServiceEnabled.graphql",
+    response_derives = "Debug, Clone, PartialEq, Eq"
+)]
+pub struct SetServiceEnabled;
+
+/// The state of a service after it was changed, as returned by [`R3Client::set_service_enabled`](crate::R3Client::set_service_enabled).
+pub type ServiceState = set_service_enabled::SetServiceEnabledUpdateService;
+
+/// Mutation, which adds a service to a device.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/CreateService.graphql",
+    response_derives = "Debug"
+)]
+pub struct CreateService;
+
+/// Mutation, which removes a service from its device.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/RemoveService.graphql",
+    response_derives = "Debug"
+)]
+pub struct RemoveService;
+
+/// Mutation, which creates a proxy connection to a service.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/CreateConnection.graphql",
+    response_derives = "Debug, Clone, PartialEq, Eq",
+    variables_derives = "Debug, Clone"
+)]
+pub struct CreateConnection;
+
+/// A proxy connection to a service, as returned by [`R3Client::create_connection`](crate::R3Client::create_connection).
+pub type Connection = create_connection::CreateConnectionConnect;
+
+/// Options for creating a connection using [`R3Client::create_connection`](crate::R3Client::create_connection).
+///
+/// The timeout of the connection is chosen by the API and returned in [`Connection::timeout`].
+/// The API does not support persistent connections through this mutation.
+#[derive(Debug, Clone)]
+#[builder]
+pub struct ConnectionOptions {
+    /// The public IP address of the host, which will use the connection.
+    pub host_ip: String,
+    /// The type of proxy to create. Defaults to [`ProxyType::AUTO_PROXY`](create_connection::ProxyType::AUTO_PROXY),
+    /// which chooses the type based on the application type of the service.
+    #[builder(default = create_connection::ProxyType::AUTO_PROXY)]
+    pub proxy_type: create_connection::ProxyType,
+}
+
+/// Query, which retrieves the active connections of an account.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetConnections.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetConnections;
+
+/// An active connection to a service, as returned by [`R3Client::get_connections`](crate::R3Client::get_connections).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveConnection {
+    /// The ID of the connection. Pass this to [`R3Client::disconnect`](crate::R3Client::disconnect).
+    pub id: String,
+    /// The ID of the service the connection goes to, if the API reports it.
+    pub service_id: Option<ServiceId>,
+    /// When the connection was created.
+    pub created: DateTime,
+    /// The status of the connection, as reported by the API.
+    pub status: String,
+    /// Whether a client is currently connected.
+    pub connected: bool,
+}
+
+impl From<get_connections::GetConnectionsLoginAccountSessions> for ActiveConnection {
+    fn from(session: get_connections::GetConnectionsLoginAccountSessions) -> Self {
+        Self {
+            id: session.id,
+            service_id: session.target.map(|target| ServiceId(target.id)),
+            created: session.timestamp,
+            status: session.state,
+            connected: session.connected,
+        }
+    }
+}
+
+/// Mutation, which closes a proxy connection.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/RemoveConnection.graphql",
+    response_derives = "Debug"
+)]
+pub struct RemoveConnection;
+
+/// Query, which retrieves the event history of a device.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetDeviceEvents.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetDeviceEvents;
+
+/// An event in the history of a device, as returned by [`R3Client::get_device_events`](crate::R3Client::get_device_events).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceEvent {
+    /// The ID of the event.
+    pub id: String,
+    /// When the event happened, if the API reports it.
+    pub timestamp: Option<DateTime>,
+    /// The type of the event, e.g. `DEVICE_CONNECT` or `DEVICE_STATE`.
+    pub event_type: String,
+    /// The state the device or connection changed to, e.g. `active`, if applicable.
+    pub state: Option<String>,
+    /// What happened, e.g. `connected`, if applicable.
+    pub action: Option<String>,
+    /// The email of the user, who caused the event, if any.
+    pub actor: Option<String>,
+}
+
+impl From<get_device_events::GetDeviceEventsLoginDeviceEventsItems> for DeviceEvent {
+    fn from(event: get_device_events::GetDeviceEventsLoginDeviceEventsItems) -> Self {
+        Self {
+            id: event.id,
+            timestamp: event.timestamp,
+            event_type: event.type_,
+            state: event.state,
+            action: event.action,
+            actor: event.actor.and_then(|actor| actor.email),
+        }
+    }
+}
+
+/// Query, which retrieves the audit log of an account.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetAuditLog.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetAuditLog;
+
+/// An entry of the audit log of an account, as returned by [`R3Client::get_audit_log`](crate::R3Client::get_audit_log).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditLogEntry {
+    /// The ID of the event.
+    pub id: String,
+    /// When the event happened, if the API reports it.
+    pub timestamp: Option<DateTime>,
+    /// The type of the event, e.g. `AUTH_LOGIN` or `DEVICE_SHARE`.
+    pub event_type: String,
+    /// What happened, if applicable.
+    pub action: Option<String>,
+    /// The email of the user, who caused the event, if any.
+    pub actor: Option<String>,
+    /// The IDs of the devices or services the event refers to.
+    pub targets: Vec<String>,
+}
+
+impl From<get_audit_log::GetAuditLogLoginAccountEventsItems> for AuditLogEntry {
+    fn from(event: get_audit_log::GetAuditLogLoginAccountEventsItems) -> Self {
+        Self {
+            id: event.id,
+            timestamp: event.timestamp,
+            event_type: event.type_,
+            action: event.action,
+            actor: event.actor.and_then(|actor| actor.email),
+            targets: event
+                .target
+                .into_iter()
+                .flatten()
+                .map(|target| target.id)
+                .collect(),
+        }
+    }
+}
+
+/// Mutation, which shares a device with other users.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/ShareDevice.graphql",
+    response_derives = "Debug"
+)]
+pub struct ShareDevice;
+
+/// Mutation, which revokes the access of other users to a device.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/UnshareDevice.graphql",
+    response_derives = "Debug"
+)]
+pub struct UnshareDevice;
+
+/// Query, which retrieves the users a device is shared with.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetDeviceShares.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetDeviceShares;
+
+/// A permission, that a user can be granted on a shared device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SharePermission {
+    /// Connect to the services of the device. Every user the device is shared with has this permission.
+    Connect,
+    /// Run scripts on the device.
+    Scripting,
+}
+
+/// A user a device is shared with, as returned by [`R3Client::get_device_shares`](crate::R3Client::get_device_shares).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceShare {
+    /// The ID of the user.
+    pub user_id: String,
+    /// The email of the user, if known.
+    pub email: Option<String>,
+    /// The permissions of the user on the device.
+    pub permissions: BTreeSet<SharePermission>,
+    /// When the device was shared with the user, if known.
+    pub created: Option<DateTime>,
+}
+
+impl From<get_device_shares::GetDeviceSharesLoginDeviceAccess> for DeviceShare {
+    fn from(access: get_device_shares::GetDeviceSharesLoginDeviceAccess) -> Self {
+        let mut permissions = BTreeSet::from([SharePermission::Connect]);
+        if access.scripting.unwrap_or_default() {
+            permissions.insert(SharePermission::Scripting);
+        }
+        Self {
+            user_id: access.user.id,
+            email: access.user.email,
+            permissions,
+            created: access.created,
         }
     }
 }
@@ -138,3 +1250,319 @@ impl Display for DeviceState {
 )]
 pub struct GetDevicesCSV;
 // endregion
+// region Networks
+
+/// The ID of a network.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NetworkId(pub String);
+
+impl Display for NetworkId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<String> for NetworkId {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for NetworkId {
+    fn from(id: &str) -> Self {
+        Self(id.to_string())
+    }
+}
+
+impl AsRef<str> for NetworkId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Query, which retrieves the networks of an account.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/GetNetworks.graphql",
+    response_derives = "Debug"
+)]
+pub struct GetNetworks;
+
+/// A network, which groups services, as returned by [`R3Client::get_networks`](crate::R3Client::get_networks).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Network {
+    /// The ID of the network.
+    pub id: NetworkId,
+    /// The name of the network.
+    pub name: String,
+    /// The IDs of the services in the network.
+    pub service_ids: Vec<ServiceId>,
+}
+
+impl From<get_networks::GetNetworksLoginAccountNetworks> for Network {
+    fn from(network: get_networks::GetNetworksLoginAccountNetworks) -> Self {
+        Self {
+            id: NetworkId(network.id),
+            name: network.name,
+            service_ids: network
+                .connections
+                .into_iter()
+                .map(|connection| ServiceId(connection.service.id))
+                .collect(),
+        }
+    }
+}
+
+/// Mutation, which creates a network.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/CreateNetwork.graphql",
+    response_derives = "Debug"
+)]
+pub struct CreateNetwork;
+
+/// Mutation, which deletes a network.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/DeleteNetwork.graphql",
+    response_derives = "Debug"
+)]
+pub struct DeleteNetwork;
+
+/// Mutation, which adds a service to a network.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/AddServiceToNetwork.graphql",
+    response_derives = "Debug"
+)]
+pub struct AddServiceToNetwork;
+
+/// Mutation, which removes a service from a network.
+#[derive(GraphQLQuery)]
+#[graphql(
+    schema_path = "src/graphql/schema.json",
+    query_path = "src/graphql/RemoveServiceFromNetwork.graphql",
+    response_derives = "Debug"
+)]
+pub struct RemoveServiceFromNetwork;
+// endregion
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_job_arguments_from_pairs() {
+        let arguments = std::collections::BTreeMap::from([
+            ("delay".to_string(), "10".to_string()),
+            ("env".to_string(), "prod".to_string()),
+        ]);
+        let query = StartJob::build_query(start_job::Variables {
+            file_id: "file".to_string(),
+            device_ids: vec!["device".to_string()],
+            arguments: start_job::ArgumentInput::from_pairs(arguments),
+        });
+
+        let variables = serde_json::to_value(&query.variables).unwrap();
+        assert_eq!(
+            variables["arguments"],
+            serde_json::json!([
+                { "name": "delay", "value": "10" },
+                { "name": "env", "value": "prod" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_organization_role_round_trip() {
+        for role in [
+            OrganizationRole::Member,
+            OrganizationRole::Admin,
+            OrganizationRole::NoAccess,
+        ] {
+            let json = serde_json::to_string(&role).unwrap();
+            assert_eq!(
+                serde_json::from_str::<OrganizationRole>(&json).unwrap(),
+                role
+            );
+            assert_eq!(role.to_string().parse::<OrganizationRole>().unwrap(), role);
+        }
+        assert_eq!(
+            "admin".parse::<OrganizationRole>().unwrap(),
+            OrganizationRole::Admin
+        );
+    }
+
+    #[test]
+    fn test_organization_role_unknown() {
+        let role: OrganizationRole = serde_json::from_str(r#""AUDITOR""#).unwrap();
+        assert_eq!(role, OrganizationRole::Unknown("AUDITOR".to_string()));
+        assert_eq!(serde_json::to_string(&role).unwrap(), r#""AUDITOR""#);
+    }
+
+    #[test]
+    fn test_permission_round_trip() {
+        for permission in [
+            Permission::View,
+            Permission::Connect,
+            Permission::Scripting,
+            Permission::Manage,
+            Permission::Admin,
+        ] {
+            let json = serde_json::to_string(&permission).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Permission>(&json).unwrap(),
+                permission
+            );
+        }
+        let permission: Permission = serde_json::from_str(r#""BILLING""#).unwrap();
+        assert_eq!(permission, Permission::Unknown("BILLING".to_string()));
+    }
+
+    #[test]
+    fn test_role_typed_permissions() {
+        let role: get_organization_self_membership::GetOrganizationSelfMembershipLoginMembershipCustomRole =
+            serde_json::from_str(
+                r#"{ "id": "role-1", "name": "Operator", "permissions": ["VIEW", "SCRIPTING", "BILLING"] }"#,
+            )
+            .unwrap();
+        assert_eq!(
+            role.typed_permissions(),
+            vec![
+                Permission::View,
+                Permission::Scripting,
+                Permission::Unknown("BILLING".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_job_status_round_trip() {
+        assert_eq!(all_job_statuses().len(), 5);
+        for status in all_job_statuses() {
+            let parsed: get_jobs::JobStatusEnum = status.to_string().parse().unwrap();
+            assert_eq!(&parsed, status);
+        }
+    }
+
+    #[test]
+    fn test_job_status_from_str_case_insensitive() {
+        assert_eq!(
+            "running".parse::<get_jobs::JobStatusEnum>().unwrap(),
+            get_jobs::JobStatusEnum::RUNNING
+        );
+        assert_eq!(
+            "Cancelled".parse::<get_jobs::JobStatusEnum>().unwrap(),
+            get_jobs::JobStatusEnum::CANCELLED
+        );
+    }
+
+    #[test]
+    fn test_job_status_from_str_unknown() {
+        let result = "foobar".parse::<get_jobs::JobStatusEnum>();
+        assert_eq!(result, Err(ParseJobStatusError("foobar".to_string())));
+    }
+
+    #[test]
+    fn test_device_state_round_trip() {
+        for state in [DeviceState::Active, DeviceState::Inactive] {
+            assert_eq!(state.as_str(), state.to_string());
+            assert_eq!(state.as_str().parse::<DeviceState>().unwrap(), state);
+        }
+        assert_eq!(
+            "Inactive".parse::<DeviceState>().unwrap(),
+            DeviceState::Inactive
+        );
+        assert_eq!(
+            "unknown".parse::<DeviceState>(),
+            Err(ParseDeviceStateError("unknown".to_string()))
+        );
+    }
+
+    /// A device, as returned by [`GetDevices`], with the given ID, which is also its name.
+    fn device_json(id: &str, online: bool, last_reported: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": id,
+            "online": online,
+            "created": "2024-08-30T12:00:00.000Z",
+            "state": if online { "active" } else { "inactive" },
+            "lastReported": last_reported,
+            "access": [],
+            "tags": [],
+            "attributes": {},
+            "services": [],
+            "categoryA": null,
+            "categoryB": null,
+            "categoryC": null,
+            "categoryD": null,
+            "categoryE": null
+        })
+    }
+
+    #[test]
+    fn test_partition_devices() {
+        let device =
+            |id: &str, online: bool| device_json(id, online, Some("2024-08-30T12:00:00.000Z"));
+        let response: get_devices::ResponseData = serde_json::from_value(serde_json::json!({
+            "login": {
+                "account": {
+                    "devices": {
+                        "hasMore": false,
+                        "total": 4,
+                        "items": [
+                            device("a", true),
+                            device("b", false),
+                            device("c", true),
+                            device("d", false)
+                        ]
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        let devices = response.login.unwrap().account.unwrap().devices.items;
+
+        let (online, offline) = partition_devices(devices);
+
+        let ids = |devices: &[get_devices::GetDevicesLoginAccountDevicesItems]| {
+            devices
+                .iter()
+                .map(|device| device.id.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&online), vec!["a", "c"]);
+        assert_eq!(ids(&offline), vec!["b", "d"]);
+        assert!(online
+            .iter()
+            .all(get_devices::GetDevicesLoginAccountDevicesItems::is_online));
+    }
+
+    #[test]
+    fn test_device_last_reported_age() {
+        let items: Vec<get_devices::GetDevicesLoginAccountDevicesItems> =
+            serde_json::from_value(serde_json::json!([
+                device_json("a", true, Some("2024-08-30T12:00:00.000Z")),
+                device_json("b", true, None)
+            ]))
+            .unwrap();
+        let devices: Vec<Device> = items.into_iter().map(Device::from).collect();
+
+        let last_reported = chrono::DateTime::parse_from_rfc3339("2024-08-30T12:00:00Z").unwrap();
+        assert_eq!(
+            devices[0].last_reported,
+            Some(last_reported.with_timezone(&Local))
+        );
+        let before = Local::now().signed_duration_since(last_reported);
+        let age = devices[0].age().unwrap();
+        let after = Local::now().signed_duration_since(last_reported);
+        assert!(before <= age && age <= after);
+
+        assert_eq!(devices[1].last_reported, None);
+        assert_eq!(devices[1].age(), None);
+    }
+}
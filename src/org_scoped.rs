@@ -0,0 +1,1023 @@
+//! Contains the [`OrgScopedClient`], which sends all requests in the context of one organization.
+//!
+//! On the docs page of this module, you can only see the builder structs for the functions.
+//!
+//! Please see [`OrgScopedClient`] for the actual functions you can call.
+
+use crate::operations::{
+    add_device_tag, get_device_tags, get_devices, get_files, get_jobs, remove_device_tag,
+    ActiveConnection, Device, DeviceState, FileVersion, Job, JobDeviceResult, Network, NetworkId,
+};
+use crate::{DisconnectResult, PagedResult, R3Client};
+use bon::bon;
+use graphql_client::Response;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Debug, Formatter};
+
+/// A handle to an [`R3Client`], which fills in the organization ID of all requests.
+///
+/// Get one with [`R3Client::in_org`]. It only borrows the client, so you can create one per organization,
+/// if your program works with multiple organizations at the same time.
+/// The functions take the same arguments as the functions of [`R3Client`] with the same name, except for `org_id`.
+///
+/// # Example
+/// ```no_run
+/// # use remoteit_api::R3Client;
+/// # fn example(client: R3Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let org = client.in_org("org-id");
+/// let devices = org.get_devices_typed().limit(10).call()?;
+/// let jobs = org.get_jobs().limit(10).call()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct OrgScopedClient<'a> {
+    client: &'a R3Client,
+    org_id: String,
+}
+
+impl R3Client {
+    /// # Returns
+    /// An [`OrgScopedClient`], which sends all requests in the context of the organization with the given ID.
+    #[must_use]
+    pub fn in_org(&self, org_id: impl Into<String>) -> OrgScopedClient<'_> {
+        OrgScopedClient {
+            client: self,
+            org_id: org_id.into(),
+        }
+    }
+}
+
+impl Debug for OrgScopedClient<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OrgScopedClient")
+            .field("org_id", &self.org_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OrgScopedClient<'_> {
+    /// # Returns
+    /// The client, that sends the requests.
+    #[must_use]
+    pub fn client(&self) -> &R3Client {
+        self.client
+    }
+
+    /// # Returns
+    /// The ID of the organization, in whose context the requests are sent.
+    #[must_use]
+    pub fn org_id(&self) -> &str {
+        &self.org_id
+    }
+}
+
+#[cfg(feature = "blocking")]
+#[bon]
+impl OrgScopedClient<'_> {
+    /// See [`R3Client::get_files`].
+    #[builder]
+    pub fn get_files(
+        &self,
+        /// Optional filter for executable scripts or assets.
+        executable: Option<bool>,
+        /// Optional ID of the user, whose files to get.
+        owner_id: Option<String>,
+    ) -> Result<Response<get_files::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_files()
+            .org_id(self.org_id.clone())
+            .maybe_executable(executable)
+            .maybe_owner_id(owner_id)
+            .call()
+    }
+
+    /// See [`R3Client::my_files`].
+    #[builder]
+    pub fn my_files(
+        &self,
+        /// The ID of the user, whose files to get.
+        user_id: String,
+    ) -> Result<Vec<get_files::GetFilesLoginAccountFiles>, Box<dyn Error>> {
+        self.client
+            .my_files()
+            .org_id(self.org_id.clone())
+            .user_id(user_id)
+            .call()
+    }
+
+    /// See [`R3Client::get_file_versions`].
+    #[builder]
+    pub fn get_file_versions(
+        &self,
+        /// The ID of the file.
+        /// You can get this from the response of [`R3Client::get_files()`].
+        file_id: String,
+    ) -> Result<Vec<FileVersion>, Box<dyn Error>> {
+        self.client
+            .get_file_versions()
+            .org_id(self.org_id.clone())
+            .file_id(file_id)
+            .call()
+    }
+
+    /// See [`R3Client::get_jobs`].
+    #[builder]
+    pub fn get_jobs(
+        &self,
+        /// Optional limit how many results are returned. It is highly recommended to set a limit, because this query can take quite a while otherwise.
+        limit: Option<i64>,
+        /// Optional list of job IDs to filter by.
+        job_id_filter: Option<Vec<String>>,
+        /// Optional list of job statuses to filter by.
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Result<Response<get_jobs::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_jobs()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_job_id_filter(job_id_filter)
+            .maybe_status_filter(status_filter)
+            .call()
+    }
+
+    /// See [`R3Client::get_all_jobs`].
+    #[builder]
+    pub fn get_all_jobs(
+        &self,
+        /// How many jobs to fetch per request.
+        #[builder(default = 100)]
+        page_size: i64,
+        /// Optional list of job IDs to filter by.
+        job_id_filter: Option<Vec<String>>,
+        /// Optional list of job statuses to filter by.
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Result<Vec<get_jobs::GetJobsLoginAccountJobsItems>, Box<dyn Error>> {
+        self.client
+            .get_all_jobs()
+            .org_id(self.org_id.clone())
+            .page_size(page_size)
+            .maybe_job_id_filter(job_id_filter)
+            .maybe_status_filter(status_filter)
+            .call()
+    }
+
+    /// See [`R3Client::get_job`].
+    #[builder]
+    pub fn get_job(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job()`].
+        job_id: String,
+    ) -> Result<Option<Job>, Box<dyn Error>> {
+        self.client
+            .get_job()
+            .org_id(self.org_id.clone())
+            .job_id(job_id)
+            .call()
+    }
+
+    /// See [`R3Client::get_job_results`].
+    #[builder]
+    pub fn get_job_results(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job()`].
+        job_id: String,
+    ) -> Result<Vec<JobDeviceResult>, Box<dyn Error>> {
+        self.client
+            .get_job_results()
+            .org_id(self.org_id.clone())
+            .job_id(job_id)
+            .call()
+    }
+
+    /// See [`R3Client::get_job_summary`].
+    #[builder]
+    pub fn get_job_summary(&self) -> Result<HashMap<get_jobs::JobStatusEnum, u64>, Box<dyn Error>> {
+        self.client
+            .get_job_summary()
+            .org_id(self.org_id.clone())
+            .call()
+    }
+
+    /// See [`R3Client::get_devices`].
+    #[builder]
+    pub fn get_devices(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        /// The filter is applied server-side. The remote.it API returns devices whose name contains the given text.
+        name: Option<String>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_devices()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .maybe_state(state)
+            .maybe_name(name)
+            .call()
+    }
+
+    /// See [`R3Client::get_devices_typed`].
+    #[builder]
+    pub fn get_devices_typed(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        name: Option<String>,
+    ) -> Result<Vec<Device>, Box<dyn Error>> {
+        self.client
+            .get_devices_typed()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .maybe_state(state)
+            .maybe_name(name)
+            .call()
+    }
+
+    /// See [`R3Client::get_devices_paged`].
+    #[builder]
+    pub fn get_devices_paged(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        name: Option<String>,
+    ) -> Result<PagedResult<get_devices::GetDevicesLoginAccountDevicesItems>, Box<dyn Error>> {
+        self.client
+            .get_devices_paged()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .maybe_state(state)
+            .maybe_name(name)
+            .call()
+    }
+
+    /// See [`R3Client::get_device_count`].
+    #[builder]
+    pub fn get_device_count(&self) -> Result<u64, Box<dyn Error>> {
+        self.client
+            .get_device_count()
+            .org_id(self.org_id.clone())
+            .call()
+    }
+
+    /// See [`R3Client::get_online_devices`].
+    #[builder]
+    pub fn get_online_devices(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_online_devices()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .call()
+    }
+
+    /// See [`R3Client::get_offline_devices`].
+    #[builder]
+    pub fn get_offline_devices(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_offline_devices()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .call()
+    }
+
+    /// See [`R3Client::search_devices`].
+    #[builder]
+    pub fn search_devices(
+        &self,
+        /// The text to search for in the device names.
+        query: String,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.client
+            .search_devices()
+            .org_id(self.org_id.clone())
+            .query(query)
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .call()
+    }
+
+    /// See [`R3Client::resolve_device_id`].
+    #[builder]
+    pub fn resolve_device_id(
+        &self,
+        /// The name of the device.
+        name: String,
+    ) -> Result<String, Box<dyn Error>> {
+        self.client
+            .resolve_device_id()
+            .org_id(self.org_id.clone())
+            .name(name)
+            .call()
+    }
+
+    /// See [`R3Client::resolve_device_ids`].
+    #[builder]
+    pub fn resolve_device_ids(
+        &self,
+        /// The names of the devices.
+        names: Vec<String>,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        self.client
+            .resolve_device_ids()
+            .org_id(self.org_id.clone())
+            .names(names)
+            .call()
+    }
+
+    /// See [`R3Client::get_devices_and_jobs`].
+    #[builder]
+    pub fn get_devices_and_jobs(
+        &self,
+        /// Optional limit for the number of devices to return.
+        device_limit: Option<i64>,
+        /// Optional limit for the number of jobs to return.
+        job_limit: Option<i64>,
+    ) -> Result<
+        (
+            Response<get_devices::ResponseData>,
+            Response<get_jobs::ResponseData>,
+        ),
+        Box<dyn Error>,
+    > {
+        self.client
+            .get_devices_and_jobs()
+            .org_id(self.org_id.clone())
+            .maybe_device_limit(device_limit)
+            .maybe_job_limit(job_limit)
+            .call()
+    }
+
+    /// See [`R3Client::get_device_tags`].
+    #[builder]
+    pub fn get_device_tags(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+    ) -> Result<Response<get_device_tags::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_device_tags()
+            .org_id(self.org_id.clone())
+            .device_id(device_id)
+            .call()
+    }
+
+    /// See [`R3Client::add_device_tag`].
+    #[builder]
+    pub fn add_device_tag(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The tag to add. Tags, that don't exist yet, are created.
+        tag: String,
+    ) -> Result<Response<add_device_tag::ResponseData>, Box<dyn Error>> {
+        self.client
+            .add_device_tag()
+            .org_id(self.org_id.clone())
+            .device_id(device_id)
+            .tag(tag)
+            .call()
+    }
+
+    /// See [`R3Client::remove_device_tag`].
+    #[builder]
+    pub fn remove_device_tag(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The tag to remove.
+        tag: String,
+    ) -> Result<Response<remove_device_tag::ResponseData>, Box<dyn Error>> {
+        self.client
+            .remove_device_tag()
+            .org_id(self.org_id.clone())
+            .device_id(device_id)
+            .tag(tag)
+            .call()
+    }
+
+    /// See [`R3Client::get_connections`].
+    #[builder]
+    pub fn get_connections(&self) -> Result<Vec<ActiveConnection>, Box<dyn Error>> {
+        self.client
+            .get_connections()
+            .org_id(self.org_id.clone())
+            .call()
+    }
+
+    /// See [`R3Client::disconnect_all`].
+    #[builder]
+    pub fn disconnect_all(&self) -> Result<Vec<(String, DisconnectResult)>, Box<dyn Error>> {
+        self.client
+            .disconnect_all()
+            .org_id(self.org_id.clone())
+            .call()
+    }
+
+    /// See [`R3Client::get_networks`].
+    #[builder]
+    pub fn get_networks(&self) -> Result<Vec<Network>, Box<dyn Error>> {
+        self.client
+            .get_networks()
+            .org_id(self.org_id.clone())
+            .call()
+    }
+
+    /// See [`R3Client::create_network`].
+    #[builder]
+    pub fn create_network(
+        &self,
+        /// The name of the network.
+        name: String,
+    ) -> Result<NetworkId, Box<dyn Error>> {
+        self.client
+            .create_network()
+            .org_id(self.org_id.clone())
+            .name(name)
+            .call()
+    }
+}
+
+#[cfg(feature = "async")]
+#[bon]
+impl OrgScopedClient<'_> {
+    /// See [`R3Client::get_files_async`].
+    #[builder]
+    pub async fn get_files_async(
+        &self,
+        /// Optional filter for executable scripts or assets.
+        executable: Option<bool>,
+        /// Optional ID of the user, whose files to get.
+        owner_id: Option<String>,
+    ) -> Result<Response<get_files::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_files_async()
+            .org_id(self.org_id.clone())
+            .maybe_executable(executable)
+            .maybe_owner_id(owner_id)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::my_files_async`].
+    #[builder]
+    pub async fn my_files_async(
+        &self,
+        /// The ID of the user, whose files to get.
+        user_id: String,
+    ) -> Result<Vec<get_files::GetFilesLoginAccountFiles>, Box<dyn Error>> {
+        self.client
+            .my_files_async()
+            .org_id(self.org_id.clone())
+            .user_id(user_id)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_file_versions_async`].
+    #[builder]
+    pub async fn get_file_versions_async(
+        &self,
+        /// The ID of the file.
+        /// You can get this from the response of [`R3Client::get_files_async()`].
+        file_id: String,
+    ) -> Result<Vec<FileVersion>, Box<dyn Error>> {
+        self.client
+            .get_file_versions_async()
+            .org_id(self.org_id.clone())
+            .file_id(file_id)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_jobs_async`].
+    #[builder]
+    pub async fn get_jobs_async(
+        &self,
+        /// Optional limit how many results are returned. It is highly recommended to set a limit, because this query can take quite a while otherwise.
+        limit: Option<i64>,
+        /// Optional list of job IDs to filter by.
+        job_id_filter: Option<Vec<String>>,
+        /// Optional list of job statuses to filter by.
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Result<Response<get_jobs::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_jobs_async()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_job_id_filter(job_id_filter)
+            .maybe_status_filter(status_filter)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_all_jobs_async`].
+    #[builder]
+    pub async fn get_all_jobs_async(
+        &self,
+        /// How many jobs to fetch per request.
+        #[builder(default = 100)]
+        page_size: i64,
+        /// Optional list of job IDs to filter by.
+        job_id_filter: Option<Vec<String>>,
+        /// Optional list of job statuses to filter by.
+        status_filter: Option<Vec<get_jobs::JobStatusEnum>>,
+    ) -> Result<Vec<get_jobs::GetJobsLoginAccountJobsItems>, Box<dyn Error>> {
+        self.client
+            .get_all_jobs_async()
+            .org_id(self.org_id.clone())
+            .page_size(page_size)
+            .maybe_job_id_filter(job_id_filter)
+            .maybe_status_filter(status_filter)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_job_async`].
+    #[builder]
+    pub async fn get_job_async(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job_async()`].
+        job_id: String,
+    ) -> Result<Option<Job>, Box<dyn Error>> {
+        self.client
+            .get_job_async()
+            .org_id(self.org_id.clone())
+            .job_id(job_id)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_job_results_async`].
+    #[builder]
+    pub async fn get_job_results_async(
+        &self,
+        /// The ID of the job.
+        /// You get this after starting a job using [`R3Client::start_job_async()`].
+        job_id: String,
+    ) -> Result<Vec<JobDeviceResult>, Box<dyn Error>> {
+        self.client
+            .get_job_results_async()
+            .org_id(self.org_id.clone())
+            .job_id(job_id)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_job_summary_async`].
+    #[builder]
+    pub async fn get_job_summary_async(
+        &self,
+    ) -> Result<HashMap<get_jobs::JobStatusEnum, u64>, Box<dyn Error>> {
+        self.client
+            .get_job_summary_async()
+            .org_id(self.org_id.clone())
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_devices_async`].
+    #[builder]
+    pub async fn get_devices_async(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        /// The filter is applied server-side. The remote.it API returns devices whose name contains the given text.
+        name: Option<String>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_devices_async()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .maybe_state(state)
+            .maybe_name(name)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_devices_typed_async`].
+    #[builder]
+    pub async fn get_devices_typed_async(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        name: Option<String>,
+    ) -> Result<Vec<Device>, Box<dyn Error>> {
+        self.client
+            .get_devices_typed_async()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .maybe_state(state)
+            .maybe_name(name)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_devices_paged_async`].
+    #[builder]
+    pub async fn get_devices_paged_async(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+        /// Optional state to filter by.
+        state: Option<DeviceState>,
+        /// Optional text to search for in the device names.
+        name: Option<String>,
+    ) -> Result<PagedResult<get_devices::GetDevicesLoginAccountDevicesItems>, Box<dyn Error>> {
+        self.client
+            .get_devices_paged_async()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .maybe_state(state)
+            .maybe_name(name)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_device_count_async`].
+    #[builder]
+    pub async fn get_device_count_async(&self) -> Result<u64, Box<dyn Error>> {
+        self.client
+            .get_device_count_async()
+            .org_id(self.org_id.clone())
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_online_devices_async`].
+    #[builder]
+    pub async fn get_online_devices_async(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_online_devices_async()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_offline_devices_async`].
+    #[builder]
+    pub async fn get_offline_devices_async(
+        &self,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_offline_devices_async()
+            .org_id(self.org_id.clone())
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::search_devices_async`].
+    #[builder]
+    pub async fn search_devices_async(
+        &self,
+        /// The text to search for in the device names.
+        query: String,
+        /// Optional limit for the number of devices to return.
+        limit: Option<i64>,
+        /// Optional offset for the devices. Useful for pagination.
+        offset: Option<i64>,
+    ) -> Result<Response<get_devices::ResponseData>, Box<dyn Error>> {
+        self.client
+            .search_devices_async()
+            .org_id(self.org_id.clone())
+            .query(query)
+            .maybe_limit(limit)
+            .maybe_offset(offset)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::resolve_device_id_async`].
+    #[builder]
+    pub async fn resolve_device_id_async(
+        &self,
+        /// The name of the device.
+        name: String,
+    ) -> Result<String, Box<dyn Error>> {
+        self.client
+            .resolve_device_id_async()
+            .org_id(self.org_id.clone())
+            .name(name)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::resolve_device_ids_async`].
+    #[builder]
+    pub async fn resolve_device_ids_async(
+        &self,
+        /// The names of the devices.
+        names: Vec<String>,
+    ) -> Result<Vec<String>, Box<dyn Error>> {
+        self.client
+            .resolve_device_ids_async()
+            .org_id(self.org_id.clone())
+            .names(names)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_devices_and_jobs_async`].
+    #[builder]
+    pub async fn get_devices_and_jobs_async(
+        &self,
+        /// Optional limit for the number of devices to return.
+        device_limit: Option<i64>,
+        /// Optional limit for the number of jobs to return.
+        job_limit: Option<i64>,
+    ) -> Result<
+        (
+            Response<get_devices::ResponseData>,
+            Response<get_jobs::ResponseData>,
+        ),
+        Box<dyn Error>,
+    > {
+        self.client
+            .get_devices_and_jobs_async()
+            .org_id(self.org_id.clone())
+            .maybe_device_limit(device_limit)
+            .maybe_job_limit(job_limit)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_device_tags_async`].
+    #[builder]
+    pub async fn get_device_tags_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+    ) -> Result<Response<get_device_tags::ResponseData>, Box<dyn Error>> {
+        self.client
+            .get_device_tags_async()
+            .org_id(self.org_id.clone())
+            .device_id(device_id)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::add_device_tag_async`].
+    #[builder]
+    pub async fn add_device_tag_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The tag to add. Tags, that don't exist yet, are created.
+        tag: String,
+    ) -> Result<Response<add_device_tag::ResponseData>, Box<dyn Error>> {
+        self.client
+            .add_device_tag_async()
+            .org_id(self.org_id.clone())
+            .device_id(device_id)
+            .tag(tag)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::remove_device_tag_async`].
+    #[builder]
+    pub async fn remove_device_tag_async(
+        &self,
+        /// The ID of the device.
+        device_id: String,
+        /// The tag to remove.
+        tag: String,
+    ) -> Result<Response<remove_device_tag::ResponseData>, Box<dyn Error>> {
+        self.client
+            .remove_device_tag_async()
+            .org_id(self.org_id.clone())
+            .device_id(device_id)
+            .tag(tag)
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_connections_async`].
+    #[builder]
+    pub async fn get_connections_async(&self) -> Result<Vec<ActiveConnection>, Box<dyn Error>> {
+        self.client
+            .get_connections_async()
+            .org_id(self.org_id.clone())
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::disconnect_all_async`].
+    #[builder]
+    pub async fn disconnect_all_async(
+        &self,
+    ) -> Result<Vec<(String, DisconnectResult)>, Box<dyn Error>> {
+        self.client
+            .disconnect_all_async()
+            .org_id(self.org_id.clone())
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::get_networks_async`].
+    #[builder]
+    pub async fn get_networks_async(&self) -> Result<Vec<Network>, Box<dyn Error>> {
+        self.client
+            .get_networks_async()
+            .org_id(self.org_id.clone())
+            .call()
+            .await
+    }
+
+    /// See [`R3Client::create_network_async`].
+    #[builder]
+    pub async fn create_network_async(
+        &self,
+        /// The name of the network.
+        name: String,
+    ) -> Result<NetworkId, Box<dyn Error>> {
+        self.client
+            .create_network_async()
+            .org_id(self.org_id.clone())
+            .name(name)
+            .call()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Credentials, GRAPHQL_PATH};
+
+    fn get_mock_client(server: &mockito::Server) -> R3Client {
+        let credentials = Credentials::builder()
+            .r3_access_key_id("foo")
+            .r3_secret_access_key("YmFy")
+            .build()
+            .unwrap();
+        R3Client::builder()
+            .credentials(credentials)
+            .base_url(server.url())
+            .build()
+    }
+
+    /// Mocks the given operation, expecting it to be sent in the context of `org-1`.
+    fn mock_in_org(
+        server: &mut mockito::Server,
+        operation_name: &str,
+        body: &str,
+    ) -> mockito::Mock {
+        server
+            .mock("POST", GRAPHQL_PATH)
+            .match_body(mockito::Matcher::PartialJson(serde_json::json!({
+                "operationName": operation_name,
+                "variables": { "orgId": "org-1" }
+            })))
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create()
+    }
+
+    #[test]
+    #[cfg(feature = "blocking")]
+    fn test_in_org() {
+        let mut server = mockito::Server::new();
+        let mocks = [
+            mock_in_org(
+                &mut server,
+                "GetDevices",
+                r#"{ "data": { "login": { "account": { "devices": { "hasMore": false, "total": 0, "items": [] } } } } }"#,
+            ),
+            mock_in_org(
+                &mut server,
+                "GetJobs",
+                r#"{ "data": { "login": { "account": { "jobs": { "hasMore": false, "total": 0, "last": null, "items": [] } } } } }"#,
+            ),
+            mock_in_org(
+                &mut server,
+                "GetDeviceCount",
+                r#"{ "data": { "login": { "account": { "devices": { "total": 7 } } } } }"#,
+            ),
+            mock_in_org(
+                &mut server,
+                "GetNetworks",
+                r#"{ "data": { "login": { "account": { "networks": [] } } } }"#,
+            ),
+            mock_in_org(
+                &mut server,
+                "GetJobResults",
+                r#"{ "data": { "login": { "account": { "jobs": { "items": [{ "jobDevices": [] }] } } } } }"#,
+            ),
+        ];
+        let client = get_mock_client(&server);
+        let org = client.in_org("org-1");
+
+        assert_eq!(org.org_id(), "org-1");
+        assert!(org.get_devices_typed().limit(10).call().unwrap().is_empty());
+        org.get_jobs().limit(10).call().unwrap();
+        assert_eq!(org.get_device_count().call().unwrap(), 7);
+        assert!(org.get_networks().call().unwrap().is_empty());
+        assert!(org
+            .get_job_results()
+            .job_id("job-1".to_string())
+            .call()
+            .unwrap()
+            .is_empty());
+
+        for mock in mocks {
+            mock.assert();
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "async")]
+    async fn test_in_org_async() {
+        let mut server = mockito::Server::new_async().await;
+        let mocks = [
+            mock_in_org(
+                &mut server,
+                "GetFiles",
+                r#"{ "data": { "login": { "account": { "files": [] } } } }"#,
+            ),
+            mock_in_org(
+                &mut server,
+                "GetNetworks",
+                r#"{ "data": { "login": { "account": { "networks": [] } } } }"#,
+            ),
+        ];
+        let client = get_mock_client(&server);
+        let org = client.in_org("org-1");
+
+        let response = org.get_files_async().call().await.unwrap();
+        assert!(response.data.is_some());
+        assert!(org.get_networks_async().call().await.unwrap().is_empty());
+
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Contains the [`Paginator`], which pages through list queries, no matter whether the API pages them by offset or by cursor.
+
+use crate::operations::get_jobs;
+use std::error::Error;
+use std::fmt::{Debug, Formatter};
+
+/// Where the next page of a [`Paginator`] starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageCursor {
+    /// The number of items to skip. Used by queries, that are paged by offset, like the devices.
+    Offset(i64),
+    /// The cursor returned with the previous page. Used by queries, that are paged by cursor, like the jobs.
+    After(String),
+}
+
+/// A single page, as fetched by a [`Paginator`].
+pub(crate) struct Page<T> {
+    /// The items on this page.
+    pub(crate) items: Vec<T>,
+    /// Where the next page starts, or [`None`] if this is the last page.
+    pub(crate) next: Option<PageCursor>,
+}
+
+/// Turns a response of [`GetJobs`](crate::operations::GetJobs) into a [`Page`].
+/// The jobs are paged by cursor, so the next page starts after the `last` cursor of this page.
+pub(crate) fn jobs_page(
+    data: get_jobs::ResponseData,
+) -> Page<get_jobs::GetJobsLoginAccountJobsItems> {
+    match data.login.and_then(|login| login.account) {
+        Some(account) => Page {
+            next: account
+                .jobs
+                .last
+                .filter(|_| account.jobs.has_more && !account.jobs.items.is_empty())
+                .map(PageCursor::After),
+            items: account.jobs.items,
+        },
+        None => Page {
+            items: Vec::new(),
+            next: None,
+        },
+    }
+}
+
+/// Fetches the page starting at the given cursor, or the first page if the cursor is [`None`].
+type FetchPage<'a, T> = Box<dyn FnMut(Option<&PageCursor>) -> Result<Page<T>, Box<dyn Error>> + 'a>;
+
+/// Pages through a list query, one request per page.
+///
+/// Whether the API pages the query by offset or by cursor is hidden, so all list queries are iterated the same way.
+/// Get one from e.g. [`R3Client::devices_paginator`](crate::R3Client::devices_paginator)
+/// or [`R3Client::jobs_paginator`](crate::R3Client::jobs_paginator), which need the `blocking` feature.
+///
+/// # Example
+/// ```no_run
+/// # use remoteit_api::R3Client;
+/// # fn example(client: R3Client) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut devices = client.devices_paginator().page_size(100).call();
+/// while let Some(page) = devices.next_page()? {
+///     for device in page {
+///         println!("{}", device.name);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Paginator<'a, T> {
+    fetch: FetchPage<'a, T>,
+    next: Option<PageCursor>,
+    exhausted: bool,
+}
+
+impl<'a, T> Paginator<'a, T> {
+    /// Creates a [`Paginator`], which starts at the first page.
+    #[cfg(feature = "blocking")]
+    pub(crate) fn new(
+        fetch: impl FnMut(Option<&PageCursor>) -> Result<Page<T>, Box<dyn Error>> + 'a,
+    ) -> Self {
+        Self {
+            fetch: Box::new(fetch),
+            next: None,
+            exhausted: false,
+        }
+    }
+
+    /// Fetches the next page.
+    ///
+    /// # Returns
+    /// The items on the next page, or [`None`] if all pages were fetched already.
+    ///
+    /// # Errors
+    /// Any error that occurs while fetching the page.
+    /// The [`Paginator`] stays on the same page in that case, so calling this again retries the page.
+    pub fn next_page(&mut self) -> Result<Option<Vec<T>>, Box<dyn Error>> {
+        if self.exhausted {
+            return Ok(None);
+        }
+        let page = (self.fetch)(self.next.as_ref())?;
+        self.exhausted = page.next.is_none();
+        self.next = page.next;
+        Ok(Some(page.items))
+    }
+
+    /// # Returns
+    /// Where the next page starts, or [`None`] if no page was fetched yet, or all pages were fetched.
+    #[must_use]
+    pub fn cursor(&self) -> Option<&PageCursor> {
+        self.next.as_ref()
+    }
+
+    /// # Returns
+    /// Whether all pages were fetched.
+    #[must_use]
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+impl<T> Debug for Paginator<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Paginator")
+            .field("next", &self.next)
+            .field("exhausted", &self.exhausted)
+            .finish_non_exhaustive()
+    }
+}
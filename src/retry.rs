@@ -0,0 +1,165 @@
+//! Contains the [`RetryPolicy`], which controls how often and after which delay failed requests are retried.
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How long to wait before retrying a failed request.
+#[derive(Clone)]
+pub enum BackoffStrategy {
+    /// Always wait the same time.
+    Fixed(Duration),
+    /// Wait `base` before the first retry, and `factor` times as long before each following retry, but never longer than `max`.
+    Exponential {
+        /// The delay before the first retry.
+        base: Duration,
+        /// The factor, by which the delay grows with each retry.
+        factor: u32,
+        /// The maximum delay.
+        max: Duration,
+    },
+    /// Computes the delay from the number of the retry, starting at `0` for the first retry.
+    Custom(Arc<dyn Fn(u32) -> Duration + Send + Sync>),
+}
+
+impl BackoffStrategy {
+    /// # Returns
+    /// The delay before the given retry. `retry` is `0` for the first retry.
+    #[must_use]
+    pub fn delay(&self, retry: u32) -> Duration {
+        match self {
+            Self::Fixed(delay) => *delay,
+            Self::Exponential { base, factor, max } => factor
+                .checked_pow(retry)
+                .and_then(|multiplier| base.checked_mul(multiplier))
+                .map_or(*max, |delay| delay.min(*max)),
+            Self::Custom(delay) => delay(retry),
+        }
+    }
+}
+
+impl Debug for BackoffStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Fixed(delay) => f.debug_tuple("Fixed").field(delay).finish(),
+            Self::Exponential { base, factor, max } => f
+                .debug_struct("Exponential")
+                .field("base", base)
+                .field("factor", factor)
+                .field("max", max)
+                .finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Controls how the [`R3Client`](crate::R3Client) retries requests, that failed for transient reasons.
+///
+/// A request is retried, if the connection to the API could not be established,
+/// or if the API responded with `429 Too Many Requests` or `503 Service Unavailable`.
+/// In these cases the API did not process the request, so retrying is safe, even for mutations.
+///
+/// Set it with [`R3ClientBuilder::retry_policy`](crate::R3ClientBuilder::retry_policy).
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// How often a request is retried at most. `0` disables retries.
+    pub max_retries: u32,
+    /// How long to wait before each retry.
+    pub backoff: BackoffStrategy,
+}
+
+impl RetryPolicy {
+    /// # Returns
+    /// A [`RetryPolicy`], which never retries.
+    #[must_use]
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            backoff: BackoffStrategy::Fixed(Duration::ZERO),
+        }
+    }
+}
+
+/// Retries up to 3 times, waiting 500ms, 1s and 2s.
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            backoff: BackoffStrategy::Exponential {
+                base: Duration::from_millis(500),
+                factor: 2,
+                max: Duration::from_secs(10),
+            },
+        }
+    }
+}
+
+/// # Returns
+/// Whether a request, that received a response with the given status, should be retried.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        || status == reqwest::StatusCode::SERVICE_UNAVAILABLE
+}
+
+/// # Returns
+/// Whether a request, that failed with the given error, should be retried.
+#[cfg(any(feature = "async", feature = "blocking"))]
+pub(crate) fn is_retryable_error(error: &(dyn std::error::Error + 'static)) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .is_some_and(reqwest::Error::is_connect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delays(strategy: &BackoffStrategy, retries: u32) -> Vec<Duration> {
+        (0..retries).map(|retry| strategy.delay(retry)).collect()
+    }
+
+    #[test]
+    fn test_fixed_backoff() {
+        let strategy = BackoffStrategy::Fixed(Duration::from_millis(100));
+        assert_eq!(delays(&strategy, 3), vec![Duration::from_millis(100); 3]);
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let strategy = BackoffStrategy::Exponential {
+            base: Duration::from_millis(100),
+            factor: 3,
+            max: Duration::from_secs(1),
+        };
+        assert_eq!(
+            delays(&strategy, 5),
+            [100, 300, 900, 1000, 1000]
+                .map(Duration::from_millis)
+                .to_vec()
+        );
+        // Doesn't overflow for large retry numbers.
+        assert_eq!(strategy.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_custom_backoff() {
+        let strategy =
+            BackoffStrategy::Custom(Arc::new(|retry| Duration::from_secs(u64::from(retry) + 1)));
+        assert_eq!(
+            delays(&strategy, 3),
+            [1, 2, 3].map(Duration::from_secs).to_vec()
+        );
+    }
+
+    #[test]
+    fn test_default_policy() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_retries, 3);
+        assert_eq!(
+            delays(&policy.backoff, policy.max_retries),
+            [500, 1000, 2000].map(Duration::from_millis).to_vec()
+        );
+        assert_eq!(RetryPolicy::none().max_retries, 0);
+    }
+}